@@ -0,0 +1,154 @@
+//! The C backend, generates a standalone C program.
+
+use std::fmt::Write as _;
+
+use super::{lower, BackendError, EofBehaviour, Instruction, MachineOptions};
+
+/// Translates a brainfuck program into a standalone C program emulating the machine described by `options`.
+/// The generated program reads bytes from stdin and writes the cells to stdout as UTF-8 characters,
+/// like the interpreter with streamed input.
+pub fn to_c(bf: &str, options: &MachineOptions) -> Result<String, BackendError> {
+    options.validate()?;
+    let instructions = lower(bf)?;
+
+    let mut buf = String::new();
+    write_prelude(&mut buf, options);
+
+    buf.push_str("int main(void) {\n");
+    let mut depth = 1;
+    for instruction in instructions {
+        if instruction == Instruction::LoopEnd {
+            depth -= 1;
+        }
+
+        let line = match instruction {
+            Instruction::Add { offset, amount } => format!("add({offset}, {}u);", options.wrap(amount)),
+            Instruction::Clear { offset } => format!("*at({offset}) = 0;"),
+            Instruction::Move(n) => format!("pointer += {n};"),
+            Instruction::LoopStart => "while (*at(0)) {".to_string(),
+            Instruction::LoopEnd => "}".to_string(),
+            Instruction::Input { offset } => format!("input({offset});"),
+            Instruction::Output { offset } => format!("output({offset});"),
+        };
+        let _ = writeln!(buf, "{}{line}", "    ".repeat(depth));
+
+        if instruction == Instruction::LoopStart {
+            depth += 1;
+        }
+    }
+    buf.push_str("    return 0;\n}\n");
+
+    Ok(buf)
+}
+
+/// Writes the includes, the tape and the helper functions.
+fn write_prelude(buf: &mut String, options: &MachineOptions) {
+    let bits = options.cell_size;
+    let cell = if options.signed { format!("int{bits}_t") } else { format!("uint{bits}_t") };
+
+    let eof = match options.eof {
+        EofBehaviour::Unchanged => "(void)at(offset);",
+        EofBehaviour::Zero => "*at(offset) = 0;",
+        EofBehaviour::Max => "*at(offset) = (cell)(ucell)-1;",
+        EofBehaviour::Abort => "fail(\"the program tried to read past the end of the input\");",
+    };
+
+    let _ = write!(buf, "\
+/* generated by basm */
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+typedef {cell} cell;
+/* arithmetic is done on unsigned cells, so that it wraps */
+typedef uint{bits}_t ucell;
+
+#define TAPE_LENGHT {tape_lenght}L
+
+static cell tape[TAPE_LENGHT];
+static long pointer = 0;
+
+static void fail(const char *reason) {{
+    fflush(stdout);
+    fprintf(stderr, \"%s\\n\", reason);
+    exit(1);
+}}
+
+static cell *at(long offset) {{
+    long index = pointer + offset;
+    if (index < 0) fail(\"the tape pointer has gone into negatives\");
+    if (index >= TAPE_LENGHT) fail(\"the tape size limit was exceded\");
+    return &tape[index];
+}}
+
+static void add(long offset, ucell amount) {{
+    cell *c = at(offset);
+    *c = (cell)(ucell)((ucell)*c + amount);
+}}
+
+static void input(long offset) {{
+    int ch = getchar();
+    if (ch == EOF) {{
+        {eof}
+    }} else {{
+        *at(offset) = (cell)(ucell)(unsigned char)ch;
+    }}
+}}
+
+/* writes the value as an UTF-8 character, invalid characters are written as U+FFFD */
+static void output(long offset) {{
+    long long value = *at(offset);
+    if (value < 0 || value > 0x10FFFF || (value >= 0xD800 && value <= 0xDFFF)) value = 0xFFFD;
+
+    if (value < 0x80) {{
+        putchar((int)value);
+    }} else if (value < 0x800) {{
+        putchar((int)(0xC0 | (value >> 6)));
+        putchar((int)(0x80 | (value & 0x3F)));
+    }} else if (value < 0x10000) {{
+        putchar((int)(0xE0 | (value >> 12)));
+        putchar((int)(0x80 | ((value >> 6) & 0x3F)));
+        putchar((int)(0x80 | (value & 0x3F)));
+    }} else {{
+        putchar((int)(0xF0 | (value >> 18)));
+        putchar((int)(0x80 | ((value >> 12) & 0x3F)));
+        putchar((int)(0x80 | ((value >> 6) & 0x3F)));
+        putchar((int)(0x80 | (value & 0x3F)));
+    }}
+}}
+
+", tape_lenght = options.tape_lenght);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process::Command};
+
+    use crate::backend::tests::{assert_backend_matches_interpreter, run_executable, work_dir};
+
+    use super::*;
+
+    /// Compiles `bf` to C and runs it, returns the output and whether it succeeded.
+    /// Returns `None` if there is no C compiler.
+    fn compile_and_run(name: &str, bf: &str, options: &MachineOptions, input: &str) -> Option<(Vec<u8>, bool)> {
+        let dir = work_dir("c");
+        let source = dir.join(format!("{name}.c"));
+        let executable = dir.join(name);
+        fs::write(&source, to_c(bf, options).unwrap()).unwrap();
+
+        let status = Command::new("cc")
+            .arg("-O1")
+            .arg("-o").arg(&executable)
+            .arg(&source)
+            .status()
+            .ok()?;
+        assert!(status.success(), "the generated C for {name} does not compile");
+
+        Some(run_executable(&executable, input))
+    }
+
+    #[test]
+    fn c_backend_matches_interpreter() {
+        assert_backend_matches_interpreter("c", compile_and_run);
+    }
+}
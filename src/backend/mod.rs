@@ -0,0 +1,334 @@
+//! Backends translating brainfuck into other languages, so that programs can be compiled into native executables.
+//!
+//! The brainfuck is first parsed into the `Operation`s of the optimiser, which are then lowered
+//! into a flat list of [`Instruction`]s shared by all backends.
+//! This way, offsets and clear loops become a single instruction in the generated code.
+
+use thiserror::Error;
+
+use crate::optimiser::{parse_operations, Operation};
+
+mod c;
+pub use c::to_c;
+mod x86_64;
+pub use x86_64::to_x86_64_linux;
+mod wat;
+pub use wat::to_wat;
+
+/// The machine the generated program emulates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MachineOptions {
+    /// The size of cells in bits (only 8, 16 and 32).
+    pub cell_size: usize,
+    /// Whether the cells contain signed numbers.
+    pub signed: bool,
+    /// The number of cells on the tape, the program aborts if it tries to access a cell past it.
+    pub tape_lenght: usize,
+    /// What happens to the cell when the program reads past the end of the input.
+    pub eof: EofBehaviour,
+}
+
+impl Default for MachineOptions {
+    fn default() -> Self {
+        MachineOptions {
+            cell_size: 8,
+            signed: false,
+            tape_lenght: 30_000,
+            eof: EofBehaviour::Unchanged,
+        }
+    }
+}
+
+impl MachineOptions {
+    /// Returns an error if the options cannot be represented by the backends.
+    pub fn validate(&self) -> Result<(), BackendError> {
+        if !matches!(self.cell_size, 8 | 16 | 32) {
+            return Err(BackendError::InvalidCellSize { got: self.cell_size })
+        }
+
+        if self.tape_lenght == 0 {
+            return Err(BackendError::EmptyTape)
+        }
+
+        Ok(())
+    }
+
+    /// Returns `amount` wrapped into the range of an unsigned cell.
+    fn wrap(&self, amount: i64) -> u64 {
+        let modulo = 1i128 << self.cell_size;
+        i128::from(amount).rem_euclid(modulo) as u64
+    }
+}
+
+/// What happens to the cell when the generated program reads past the end of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EofBehaviour {
+    /// The cell is left unchanged.
+    #[default]
+    Unchanged,
+    /// The cell is set to 0.
+    Zero,
+    /// The cell is set to -1 (or the maximum value for unsigned cells).
+    Max,
+    /// The program aborts.
+    Abort,
+}
+
+/// An error that prevented brainfuck from being translated.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum BackendError {
+    /// The program contains brackets without a match, which cannot be represented in structured code.
+    #[error("the program contains unmatched brackets")]
+    UnmatchedBracket,
+    /// The cell size is not supported by the backends.
+    #[error("specified cell size is invalid, expected 8, 16 or 32, got {got}")]
+    InvalidCellSize {
+        /// The specified cell size, which was invalid
+        got: usize,
+    },
+    /// The tape cannot contain any cell.
+    #[error("the tape needs to contain at least one cell")]
+    EmptyTape,
+}
+
+/// A low level instruction, which all backends know how to translate.
+/// Offsets are relative to the tape pointer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Instruction {
+    /// Adds `amount` to the cell at `offset`.
+    Add { offset: isize, amount: i64 },
+    /// Sets the cell at `offset` to 0.
+    Clear { offset: isize },
+    /// Moves the tape pointer.
+    Move(isize),
+    /// Enters the loop if the current cell is not 0, else skips to after the matching `LoopEnd`.
+    LoopStart,
+    /// Goes back to the matching `LoopStart` if the current cell is not 0.
+    LoopEnd,
+    /// Reads a byte into the cell at `offset`.
+    Input { offset: isize },
+    /// Writes the cell at `offset`.
+    Output { offset: isize },
+}
+
+/// Lowers a brainfuck program into [`Instruction`]s.
+pub(crate) fn lower(bf: &str) -> Result<Vec<Instruction>, BackendError> {
+    let (operations, _) = parse_operations(bf);
+
+    let mut instructions = Vec::new();
+    lower_section(&operations, 0, &mut instructions)?;
+
+    Ok(instructions)
+}
+
+/// Lowers the operations of a section into `buf`.
+/// Returns the position of the tape pointer at the end, relative to the start of the section.
+fn lower_section(operations: &[Operation], mut pointer: isize, buf: &mut Vec<Instruction>) -> Result<isize, BackendError> {
+    for op in operations {
+        match op {
+            Operation::Offset { cell, recurrence } => {
+                buf.push(Instruction::Add { offset: cell - pointer, amount: i64::from(*recurrence) });
+            },
+            Operation::InOut { cell, operator: ',' } => buf.push(Instruction::Input { offset: cell - pointer }),
+            Operation::InOut { cell, .. } => buf.push(Instruction::Output { offset: cell - pointer }),
+            Operation::Zero { cell } => buf.push(Instruction::Clear { offset: cell - pointer }),
+            Operation::Set { cell, value } => {
+                buf.push(Instruction::Clear { offset: cell - pointer });
+                buf.push(Instruction::Add { offset: cell - pointer, amount: i64::from(*value) });
+            },
+            Operation::Multiply { cell, factors } => {
+                if *cell != pointer {
+                    buf.push(Instruction::Move(cell - pointer));
+                }
+                pointer = *cell;
+
+                buf.push(Instruction::LoopStart);
+                buf.push(Instruction::Add { offset: 0, amount: -1 });
+                for (offset, factor) in factors {
+                    buf.push(Instruction::Add { offset: *offset, amount: i64::from(*factor) });
+                }
+                buf.push(Instruction::LoopEnd);
+            },
+            Operation::Block { cell, block } => {
+                // loops are always on the current cell, so we need to go there
+                if *cell != pointer {
+                    buf.push(Instruction::Move(cell - pointer));
+                }
+                pointer = *cell;
+
+                buf.push(Instruction::LoopStart);
+                let end = lower_section(&block.operations, 0, buf)?;
+                if end != block.endpoint() {
+                    buf.push(Instruction::Move(block.endpoint() - end));
+                }
+                buf.push(Instruction::LoopEnd);
+            },
+            Operation::LooseBracket { .. } => return Err(BackendError::UnmatchedBracket),
+            Operation::Text { .. } | Operation::Visit { .. } => (),
+        }
+    }
+
+    Ok(pointer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, io::Write as _, path::{Path, PathBuf}, process::{Command, Stdio}};
+
+    use crate::{interpreter::{InterpreterBuilder, SharedBuffer}, optimise, source::SourceFile, transpile};
+
+    use super::*;
+
+    /// The optimised brainfuck of the programs in `test-resources/` which do not take input.
+    pub(super) fn test_programs() -> Vec<(&'static str, String)> {
+        let programs = [
+            ("fib", include_str!("../../test-resources/fib.basm")),
+            ("hello-world", include_str!("../../test-resources/hello-world.basm")),
+            ("hello-world-better", include_str!("../../test-resources/hello-world-better.basm")),
+            ("custom-conditionals", include_str!("../../test-resources/custom-conditionals.basm")),
+        ];
+
+        programs.into_iter()
+            .map(|(name, program)| {
+                let sf = SourceFile::from_raw_parts("testfile".into(), program.to_string()).leak();
+                (name, optimise(&transpile(sf).unwrap()))
+            })
+            .collect()
+    }
+
+    /// Runs `bf` through the interpreter configured like `options`, returns the output and whether it succeeded.
+    pub(super) fn interpret(bf: &str, options: &MachineOptions, input: &str) -> (Vec<u8>, bool) {
+        let output = SharedBuffer::new();
+        let builder = InterpreterBuilder::new(bf)
+            .with_input(SharedBuffer::from_bytes(input))
+            .with_output(output.clone())
+            .with_streamed_input()
+            .with_tape_leght(options.tape_lenght);
+        let builder = match (options.signed, options.cell_size) {
+            (false, 8) => builder.with_u8(),
+            (false, 16) => builder.with_u16(),
+            (false, 32) => builder.with_u32(),
+            (true, 8) => builder.with_i8(),
+            (true, 16) => builder.with_i16(),
+            (_, _) => builder.with_i32(),
+        };
+        let builder = match options.eof {
+            EofBehaviour::Unchanged => builder.with_eof_unchanged(),
+            EofBehaviour::Zero => builder.with_eof_as_zero(),
+            EofBehaviour::Max => builder.with_eof_as_max(),
+            EofBehaviour::Abort => builder.with_eof_aborting(),
+        };
+
+        let result = builder.finish().complete();
+        (output.contents(), result.is_ok())
+    }
+
+    /// The (created) directory the generated programs of `backend` are built in.
+    pub(super) fn work_dir(backend: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("basm-{backend}-backend-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Runs the executable with `input` as stdin, returns the output and whether it succeeded.
+    pub(super) fn run_executable(executable: &Path, input: &str) -> (Vec<u8>, bool) {
+        let mut child = Command::new(executable)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+        let output = child.wait_with_output().unwrap();
+
+        (output.stdout, output.status.success())
+    }
+
+    /// Checks that programs built by `compile_and_run` behave like the interpreter.
+    /// `compile_and_run` returns `None` if the tools required to build the programs are missing.
+    pub(super) fn assert_backend_matches_interpreter(
+        backend: &str,
+        compile_and_run: impl Fn(&str, &str, &MachineOptions, &str) -> Option<(Vec<u8>, bool)>,
+    ) {
+        let check = |name: &str, bf: &str, options: &MachineOptions, input: &str| {
+            let Some(compiled) = compile_and_run(name, bf, options, input) else {
+                eprintln!("the tools to build the {backend} backend's output are not available, skipping {name}");
+                return
+            };
+
+            assert_eq!(compiled, interpret(bf, options, input), "{name}");
+        };
+
+        for (name, bf) in test_programs() {
+            check(name, &bf, &MachineOptions::default(), "");
+        }
+
+        // echo with the different end of input behaviours
+        let echo = ",[.,]";
+        let options = MachineOptions { eof: EofBehaviour::Zero, ..Default::default() };
+        check("echo-zero", echo, &options, "cats");
+        let options = MachineOptions { eof: EofBehaviour::Abort, ..Default::default() };
+        check("echo-abort", echo, &options, "cats");
+        let options = MachineOptions { eof: EofBehaviour::Max, ..Default::default() };
+        check("eof-max", ",+[-.,+]", &options, "dogs");
+
+        // wrapping on the different cell sizes, and characters past ascii
+        let wrapping = "-[->+<]>+++++++++[->+++++++++<]>.";
+        for (cell_size, signed) in [(8, false), (8, true), (16, false), (16, true)] {
+            let options = MachineOptions { cell_size, signed, ..Default::default() };
+            check(&format!("wrapping-{cell_size}-{signed}"), wrapping, &options, "");
+        }
+        for (cell_size, signed) in [(8, false), (8, true), (32, false), (32, true)] {
+            let options = MachineOptions { cell_size, signed, ..Default::default() };
+            check(&format!("underflow-{cell_size}-{signed}"), "-.", &options, "");
+        }
+        let options = MachineOptions { cell_size: 16, ..Default::default() };
+        check("unicode", "++++++++[>++++++++<-]>[>++++<-]>+.", &options, "");
+
+        // tape limits
+        let options = MachineOptions { tape_lenght: 4, ..Default::default() };
+        check("tape-limit", "+[>+.]", &options, "");
+
+        let _ = fs::remove_dir_all(work_dir(backend));
+    }
+
+    #[test]
+    fn lowering() {
+        assert_eq!(
+            lower(">++<-.").unwrap(),
+            vec![
+                Instruction::Add { offset: 1, amount: 2 },
+                Instruction::Add { offset: 0, amount: -1 },
+                Instruction::Output { offset: 0 },
+            ],
+        );
+
+        // clear loops are a single instruction and text is ignored
+        assert_eq!(
+            lower("hi >>[-]<,").unwrap(),
+            vec![
+                Instruction::Clear { offset: 2 },
+                Instruction::Input { offset: 1 },
+            ],
+        );
+
+        // loops move to their cell and end on their endpoint
+        assert_eq!(
+            lower(">[->+<]>[>]").unwrap(),
+            vec![
+                Instruction::Move(1),
+                Instruction::LoopStart,
+                Instruction::Add { offset: 0, amount: -1 },
+                Instruction::Add { offset: 1, amount: 1 },
+                Instruction::LoopEnd,
+                Instruction::Move(1),
+                Instruction::LoopStart,
+                Instruction::Move(1),
+                Instruction::LoopEnd,
+            ],
+        );
+
+        assert_eq!(lower("+[").unwrap_err(), BackendError::UnmatchedBracket);
+        assert_eq!(lower("+]").unwrap_err(), BackendError::UnmatchedBracket);
+    }
+}
@@ -0,0 +1,192 @@
+//! The WebAssembly backend, generates a module in the WebAssembly text format.
+//!
+//! The module imports its io from the host:
+//! - `env.read: () -> i32` returns the next byte of the input, or a negative number at the end of the input.
+//! - `env.write: (i32) -> ()` receives the value of the cell being output.
+//!   Signed cells are sign extended, unsigned 32 bit cells over `i32::MAX` are received as negative.
+//!
+//! It exports its tape as `memory` and the program as `run`.
+//! Accessing a cell outside of the tape, or reading past the end of the input when aborting on it, traps.
+
+use std::fmt::Write as _;
+
+use super::{lower, BackendError, EofBehaviour, Instruction, MachineOptions};
+
+/// The size of a WebAssembly memory page in bytes.
+const PAGE_SIZE: usize = 65_536;
+
+/// Translates a brainfuck program into a WebAssembly text module emulating the machine described by `options`.
+pub fn to_wat(bf: &str, options: &MachineOptions) -> Result<String, BackendError> {
+    options.validate()?;
+    let instructions = lower(bf)?;
+    let cell = CellSize::new(options);
+
+    let mut buf = String::new();
+    write_prelude(&mut buf, options, &cell);
+
+    buf.push_str("  (func (export \"run\")\n");
+    // the loops which were not closed yet
+    let mut loops = Vec::new();
+    let mut loop_count = 0;
+    for instruction in instructions {
+        let indent = "  ".repeat(2 + loops.len());
+        match instruction {
+            Instruction::Add { offset, amount } => {
+                let amount = options.wrap(amount) as u32 as i32;
+                let _ = writeln!(buf, "{indent}(call $add (i32.const {offset}) (i32.const {amount}))");
+            },
+            Instruction::Clear { offset } => {
+                let _ = writeln!(buf, "{indent}({} (call $at (i32.const {offset})) (i32.const 0))", cell.store);
+            },
+            Instruction::Move(n) => {
+                let _ = writeln!(buf, "{indent}(global.set $pointer (i32.add (global.get $pointer) (i32.const {n})))");
+            },
+            Instruction::LoopStart => {
+                let _ = writeln!(buf, "{indent}(block $exit{loop_count} (loop $loop{loop_count}");
+                let _ = writeln!(buf, "{indent}  (br_if $exit{loop_count} (i32.eqz ({} (call $at (i32.const 0)))))", cell.load_unsigned);
+                loops.push(loop_count);
+                loop_count += 1;
+            },
+            Instruction::LoopEnd => {
+                let index = loops.pop().expect("lowering only produces matched loops");
+                // indented like the opening of the loop, not its content
+                let indent = "  ".repeat(2 + loops.len());
+                let _ = writeln!(buf, "{indent}  (br $loop{index})))");
+            },
+            Instruction::Input { offset } => {
+                let _ = writeln!(buf, "{indent}(call $input (i32.const {offset}))");
+            },
+            Instruction::Output { offset } => {
+                let _ = writeln!(buf, "{indent}(call $write ({} (call $at (i32.const {offset}))))", cell.load(options.signed));
+            },
+        }
+    }
+    buf.push_str("  )\n)\n");
+
+    Ok(buf)
+}
+
+/// The memory instructions operating on cells.
+struct CellSize {
+    /// The size of a cell in bytes.
+    bytes: usize,
+    /// Loads a cell, zero extending it.
+    load_unsigned: &'static str,
+    /// Loads a cell, sign extending it.
+    load_signed: &'static str,
+    /// Stores a cell, truncating the value.
+    store: &'static str,
+}
+
+impl CellSize {
+    fn new(options: &MachineOptions) -> CellSize {
+        match options.cell_size {
+            8 => CellSize { bytes: 1, load_unsigned: "i32.load8_u", load_signed: "i32.load8_s", store: "i32.store8" },
+            16 => CellSize { bytes: 2, load_unsigned: "i32.load16_u", load_signed: "i32.load16_s", store: "i32.store16" },
+            _ => CellSize { bytes: 4, load_unsigned: "i32.load", load_signed: "i32.load", store: "i32.store" },
+        }
+    }
+
+    fn load(&self, signed: bool) -> &'static str {
+        if signed { self.load_signed } else { self.load_unsigned }
+    }
+}
+
+/// Writes the imports, the tape and the helper functions.
+fn write_prelude(buf: &mut String, options: &MachineOptions, cell: &CellSize) {
+    let pages = (options.tape_lenght * cell.bytes).div_ceil(PAGE_SIZE);
+
+    let eof = match options.eof {
+        EofBehaviour::Unchanged => "(drop (call $at (local.get $offset)))".to_string(),
+        EofBehaviour::Zero => format!("({} (call $at (local.get $offset)) (i32.const 0))", cell.store),
+        EofBehaviour::Max => format!("({} (call $at (local.get $offset)) (i32.const -1))", cell.store),
+        EofBehaviour::Abort => "unreachable".to_string(),
+    };
+
+    let _ = write!(buf, "\
+;; generated by basm
+(module
+  (import \"env\" \"read\" (func $read (result i32)))
+  (import \"env\" \"write\" (func $write (param i32)))
+  (memory (export \"memory\") {pages})
+  (global $pointer (mut i32) (i32.const 0))
+
+  ;; returns the address of the cell at `offset` from the tape pointer, traps if it is outside of the tape
+  (func $at (param $offset i32) (result i32)
+    (local $index i32)
+    (local.set $index (i32.add (global.get $pointer) (local.get $offset)))
+    (if (i32.ge_u (local.get $index) (i32.const {tape_lenght}))
+      (then unreachable))
+    (i32.mul (local.get $index) (i32.const {bytes})))
+
+  (func $add (param $offset i32) (param $amount i32)
+    (local $address i32)
+    (local.set $address (call $at (local.get $offset)))
+    ({store} (local.get $address) (i32.add ({load} (local.get $address)) (local.get $amount))))
+
+  (func $input (param $offset i32)
+    (local $byte i32)
+    (local.set $byte (call $read))
+    (if (i32.lt_s (local.get $byte) (i32.const 0))
+      (then {eof})
+      (else ({store} (call $at (local.get $offset)) (i32.and (local.get $byte) (i32.const 255))))))
+
+",
+        tape_lenght = options.tape_lenght,
+        bytes = cell.bytes,
+        store = cell.store,
+        load = cell.load_unsigned,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use crate::{optimise, source::SourceFile, transpile};
+
+    use super::*;
+
+    /// Checks the module generated for `program` against the golden file `test-resources/wat/{name}.wat`.
+    /// Set `BASM_BLESS` to write the generated module to the golden file instead.
+    fn assert_golden(name: &str, program: &str) {
+        let sf = SourceFile::from_raw_parts("testfile".into(), program.to_string()).leak();
+        let bf = optimise(&transpile(sf).unwrap());
+        let wat = to_wat(&bf, &MachineOptions::default()).unwrap();
+
+        let golden_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-resources/wat")
+            .join(format!("{name}.wat"));
+        if env::var_os("BASM_BLESS").is_some() {
+            fs::write(&golden_path, &wat).unwrap();
+            return
+        }
+
+        let golden = fs::read_to_string(&golden_path).unwrap();
+        assert_eq!(wat, golden, "the module generated for {name} does not match {}", golden_path.display());
+    }
+
+    #[test]
+    fn wat_golden_files() {
+        assert_golden("fib", include_str!("../../test-resources/fib.basm"));
+        assert_golden("interactive-fib", include_str!("../../test-resources/interactive-fib.basm"));
+        assert_golden("hello-world", include_str!("../../test-resources/hello-world.basm"));
+        assert_golden("hello-world-better", include_str!("../../test-resources/hello-world-better.basm"));
+        assert_golden("custom-conditionals", include_str!("../../test-resources/custom-conditionals.basm"));
+        assert_golden("bf-interpreter", include_str!("../../test-resources/bf-interpreter.basm"));
+        assert_golden("better-bf-interpreter", include_str!("../../test-resources/better-bf-interpreter.basm"));
+    }
+
+    #[test]
+    fn wat_cell_width() {
+        let options = MachineOptions { cell_size: 16, signed: true, tape_lenght: 40_000, eof: EofBehaviour::Max };
+        let wat = to_wat(",-.[-]", &options).unwrap();
+
+        // 40000 cells of 2 bytes need 2 pages
+        assert!(wat.contains("(memory (export \"memory\") 2)"));
+        assert!(wat.contains("(i32.store16 (call $at (local.get $offset)) (i32.const -1))"));
+        assert!(wat.contains("(call $add (i32.const 0) (i32.const 65535))"));
+        assert!(wat.contains("(call $write (i32.load16_s (call $at (i32.const 0))))"));
+        assert!(wat.contains("(i32.store16 (call $at (i32.const 0)) (i32.const 0))"));
+    }
+}
@@ -0,0 +1,331 @@
+//! The x86-64 Linux backend, generates GNU assembler source (AT&T syntax) for a standalone program.
+//! The program does not depend on libc, it communicates through the `read`, `write` and `exit` syscalls.
+//!
+//! Registers:
+//! - `%rbx` contains the address of the tape.
+//! - `%r12` contains the index of the current cell.
+//! - `%rax` contains the index of the accessed cell.
+
+use std::fmt::Write as _;
+
+use super::{lower, BackendError, EofBehaviour, Instruction, MachineOptions};
+
+/// Translates a brainfuck program into GNU assembler source for a standalone x86-64 Linux program
+/// emulating the machine described by `options`.
+/// It can be built with `as -o program.o program.s && ld -o program program.o`.
+/// The program reads bytes from stdin and writes the cells to stdout as UTF-8 characters,
+/// like the interpreter with streamed input.
+pub fn to_x86_64_linux(bf: &str, options: &MachineOptions) -> Result<String, BackendError> {
+    options.validate()?;
+    let instructions = lower(bf)?;
+    let cell = CellSize::new(options);
+
+    let mut buf = String::new();
+    let _ = write!(buf, "\
+# generated by basm
+    .globl _start
+    .lcomm tape, {tape_size}
+
+    .text
+_start:
+    lea tape(%rip), %rbx
+    xor %r12d, %r12d
+", tape_size = options.tape_lenght * cell.bytes);
+
+    // the indexes of the loops which were not closed yet
+    let mut loops = Vec::new();
+    let mut loop_count = 0;
+    for instruction in instructions {
+        match instruction {
+            Instruction::Add { offset, amount } => {
+                write_access(&mut buf, offset, options);
+                let _ = writeln!(buf, "    add{} ${}, {}", cell.suffix, options.wrap(amount), cell.operand());
+            },
+            Instruction::Clear { offset } => {
+                write_access(&mut buf, offset, options);
+                let _ = writeln!(buf, "    mov{} $0, {}", cell.suffix, cell.operand());
+            },
+            Instruction::Move(n) => {
+                let _ = writeln!(buf, "    add ${n}, %r12");
+            },
+            Instruction::LoopStart => {
+                let _ = writeln!(buf, "    jmp .Lcondition{loop_count}\n.Lbody{loop_count}:");
+                loops.push(loop_count);
+                loop_count += 1;
+            },
+            Instruction::LoopEnd => {
+                let index = loops.pop().expect("lowering only produces matched loops");
+                let _ = writeln!(buf, ".Lcondition{index}:");
+                write_access(&mut buf, 0, options);
+                let _ = writeln!(buf, "    cmp{} $0, {}", cell.suffix, cell.operand());
+                let _ = writeln!(buf, "    jne .Lbody{index}");
+            },
+            Instruction::Input { offset } => {
+                write_access(&mut buf, offset, options);
+                let _ = writeln!(buf, "    lea {}, %r13", cell.operand());
+                buf.push_str("    call input\n");
+            },
+            Instruction::Output { offset } => {
+                write_access(&mut buf, offset, options);
+                let _ = writeln!(buf, "    {} {}, {}", cell.load_instruction(options.signed), cell.operand(), cell.load_register(options.signed));
+                buf.push_str("    call output\n");
+            },
+        }
+    }
+
+    buf.push_str("\
+    mov $60, %eax
+    xor %edi, %edi
+    syscall
+");
+
+    write_runtime(&mut buf, options, &cell);
+
+    Ok(buf)
+}
+
+/// Writes the computation of the index of the cell at `offset` into `%rax`, aborting if it is outside of the tape.
+fn write_access(buf: &mut String, offset: isize, options: &MachineOptions) {
+    let _ = write!(buf, "\
+    lea {offset}(%r12), %rax
+    cmp ${}, %rax
+    jae tape_error
+", options.tape_lenght);
+}
+
+/// Describes how cells are accessed.
+struct CellSize {
+    /// The size of a cell in bytes.
+    bytes: usize,
+    /// The suffix of instructions operating on a cell.
+    suffix: char,
+    /// The part of `%ecx` with the size of a cell.
+    register: &'static str,
+}
+
+impl CellSize {
+    fn new(options: &MachineOptions) -> CellSize {
+        match options.cell_size {
+            8 => CellSize { bytes: 1, suffix: 'b', register: "%cl" },
+            16 => CellSize { bytes: 2, suffix: 'w', register: "%cx" },
+            _ => CellSize { bytes: 4, suffix: 'l', register: "%ecx" },
+        }
+    }
+
+    /// The memory operand of the cell whose index is in `%rax`.
+    fn operand(&self) -> String {
+        format!("(%rbx,%rax,{})", self.bytes)
+    }
+
+    /// The instruction loading a cell into a 64 bit register, extending its sign if it is signed.
+    fn load_instruction(&self, signed: bool) -> &'static str {
+        match (self.bytes, signed) {
+            (1, false) => "movzbq",
+            (1, true) => "movsbq",
+            (2, false) => "movzwq",
+            (2, true) => "movswq",
+            // writing to a 32 bit register clears the upper half
+            (_, false) => "movl",
+            (_, true) => "movslq",
+        }
+    }
+
+    /// The register the cell is loaded into by `load_instruction`.
+    fn load_register(&self, signed: bool) -> &'static str {
+        if self.bytes == 4 && !signed {
+            "%edi"
+        } else {
+            "%rdi"
+        }
+    }
+}
+
+/// Writes the routines used by the program and the data they use.
+fn write_runtime(buf: &mut String, options: &MachineOptions, cell: &CellSize) {
+    let eof = match options.eof {
+        EofBehaviour::Unchanged => String::new(),
+        EofBehaviour::Zero => format!("    mov{} $0, (%r13)\n", cell.suffix),
+        EofBehaviour::Max => format!("    mov{} $-1, (%r13)\n", cell.suffix),
+        EofBehaviour::Abort => "\
+    lea eof_message(%rip), %rsi
+    mov $eof_message_lenght, %edx
+    jmp fail
+".to_string(),
+    };
+
+    let _ = write!(buf, "
+# reads a byte into the cell at the address in %r13
+input:
+    xor %eax, %eax
+    xor %edi, %edi
+    lea byte_buffer(%rip), %rsi
+    mov $1, %edx
+    syscall
+    cmp $1, %rax
+    jne 1f
+    movzbl byte_buffer(%rip), %ecx
+    mov{suffix} {register}, (%r13)
+    ret
+1:
+{eof}    ret
+", suffix = cell.suffix, register = cell.register);
+
+    write_output_routine(buf);
+    write_error_routines(buf);
+}
+
+/// Writes the routine encoding cells to UTF-8.
+fn write_output_routine(buf: &mut String) {
+    buf.push_str("
+# writes the value in %rdi as an UTF-8 character, invalid characters are written as U+FFFD
+output:
+    mov %rdi, %rax
+    cmp $0x10FFFF, %rax
+    ja 1f
+    mov %rax, %rcx
+    and $-0x800, %rcx
+    cmp $0xD800, %rcx
+    jne 2f
+1:
+    mov $0xFFFD, %eax
+2:
+    lea utf8_buffer(%rip), %rsi
+    cmp $0x80, %rax
+    jae 3f
+    mov %al, (%rsi)
+    mov $1, %edx
+    jmp 6f
+3:
+    cmp $0x800, %rax
+    jae 4f
+    mov %eax, %ecx
+    shr $6, %ecx
+    or $0xC0, %ecx
+    mov %cl, (%rsi)
+    mov $2, %edx
+    jmp 5f
+4:
+    mov %eax, %ecx
+    shr $12, %ecx
+    cmp $0x10000, %rax
+    jae 7f
+    or $0xE0, %ecx
+    mov %cl, (%rsi)
+    mov %eax, %ecx
+    shr $6, %ecx
+    and $0x3F, %ecx
+    or $0x80, %ecx
+    mov %cl, 1(%rsi)
+    mov $3, %edx
+    jmp 5f
+7:
+    mov %eax, %ecx
+    shr $18, %ecx
+    or $0xF0, %ecx
+    mov %cl, (%rsi)
+    mov %eax, %ecx
+    shr $12, %ecx
+    and $0x3F, %ecx
+    or $0x80, %ecx
+    mov %cl, 1(%rsi)
+    mov %eax, %ecx
+    shr $6, %ecx
+    and $0x3F, %ecx
+    or $0x80, %ecx
+    mov %cl, 2(%rsi)
+    mov $4, %edx
+5:
+    # the last byte is always the lowest 6 bits
+    and $0x3F, %eax
+    or $0x80, %eax
+    mov %al, -1(%rsi,%rdx)
+6:
+    mov $1, %eax
+    mov $1, %edi
+    syscall
+    ret
+");
+}
+
+/// Writes the routines aborting the program and their messages.
+fn write_error_routines(buf: &mut String) {
+    buf.push_str("
+# aborts because the index in %rax is outside of the tape
+tape_error:
+    test %rax, %rax
+    js 1f
+    lea limit_message(%rip), %rsi
+    mov $limit_message_lenght, %edx
+    jmp fail
+1:
+    lea negative_message(%rip), %rsi
+    mov $negative_message_lenght, %edx
+
+# writes the message at %rsi of lenght %rdx to stderr and exits with an error
+fail:
+    mov $1, %eax
+    mov $2, %edi
+    syscall
+    mov $60, %eax
+    mov $1, %edi
+    syscall
+
+    .section .rodata
+limit_message:
+    .ascii \"the tape size limit was exceded\\n\"
+    .set limit_message_lenght, . - limit_message
+negative_message:
+    .ascii \"the tape pointer has gone into negatives\\n\"
+    .set negative_message_lenght, . - negative_message
+eof_message:
+    .ascii \"the program tried to read past the end of the input\\n\"
+    .set eof_message_lenght, . - eof_message
+
+    .lcomm byte_buffer, 1
+    .lcomm utf8_buffer, 4
+");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process::Command};
+
+    use crate::backend::tests::{assert_backend_matches_interpreter, run_executable, work_dir};
+
+    use super::*;
+
+    /// Assembles and links `bf` translated to assembly and runs it, returns the output and whether it succeeded.
+    /// Returns `None` if the assembler or linker is missing, or if this is not a x86-64 Linux machine.
+    fn compile_and_run(name: &str, bf: &str, options: &MachineOptions, input: &str) -> Option<(Vec<u8>, bool)> {
+        if !cfg!(all(target_arch = "x86_64", target_os = "linux")) {
+            return None
+        }
+
+        let dir = work_dir("x86_64");
+        let source = dir.join(format!("{name}.s"));
+        let object = dir.join(format!("{name}.o"));
+        let executable = dir.join(name);
+        fs::write(&source, to_x86_64_linux(bf, options).unwrap()).unwrap();
+
+        let status = Command::new("as")
+            .arg("-o").arg(&object)
+            .arg(&source)
+            .status()
+            .ok()?;
+        assert!(status.success(), "the generated assembly for {name} does not assemble");
+
+        let status = Command::new("ld")
+            .arg("-o").arg(&executable)
+            .arg(&object)
+            .status()
+            .ok()?;
+        assert!(status.success(), "the generated assembly for {name} does not link");
+
+        Some(run_executable(&executable, input))
+    }
+
+    #[test]
+    fn x86_64_backend_matches_interpreter() {
+        assert_backend_matches_interpreter("x86_64", compile_and_run);
+    }
+}
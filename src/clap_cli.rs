@@ -1,9 +1,11 @@
 //! The cli parser defined via the clap crate and its tooling.
 
-use clap::{command, Args, Parser};
+use std::{fmt, fs::{self, File}, ops::{Add, Range}, str::FromStr, time::Duration};
+
+use clap::{command, Args, Parser, ValueEnum};
 use thiserror::Error;
 
-use crate::interpreter::{InterpreterBuilder, InterpreterTrait};
+use crate::{backend::{self, BackendError, EofBehaviour, MachineOptions}, compiler::{MainContext, TargetCell}, optimiser::{OptimisationLevel, Pass, Pipeline}, interpreter::{InterpreterBuilder, InterpreterTrait, Snapshot, SnapshotError, TraceFilter}};
 
 /// The basm cli tool for transpiling basm into brainfuck and interpreting basm code transpiled into brainfuck.
 #[derive(Parser)]
@@ -14,6 +16,8 @@ pub enum CliCommand {
     Compile(CompileArgs),
     /// Compiles and interprets the program
     Run(RunArgs),
+    /// Prints the steps recorded in a trace file (see `run --trace`)
+    TraceView(TraceViewArgs),
 }
 
 /// Arguments for the `run` command.
@@ -23,9 +27,10 @@ pub struct RunArgs {
     /// Path to the basm file
     pub file_path: String,
 
-    /// Sets the size of cells in bits (only 8, 16 and 32)
-    #[arg(long, short, default_value_t = 8)]
-    pub cell_size: usize,
+    /// Sets the size of cells in bits (only 8, 16, 32 and 64),
+    /// or `big` for signed cells of arbitrary size which never overflow
+    #[arg(long, short, default_value_t = CellSize::Bits(8))]
+    pub cell_size: CellSize,
 
     /// Sets the cells as signed containing signed numbers
     #[arg(long, short = 'i', default_value_t = false)]
@@ -39,6 +44,22 @@ pub struct RunArgs {
     #[arg(long, short)]
     pub tape_limit: Option<usize>,
 
+    /// Makes the tape extend infinitely to the left of the starting cell too
+    #[arg(long, default_value_t = false, conflicts_with = "circular_tape")]
+    pub bidirectional_tape: bool,
+
+    /// Makes the tape circular with this many cells, going past an end of the tape leads to the other end
+    #[arg(long)]
+    pub circular_tape: Option<usize>,
+
+    /// Aborts the execution of the program after executing this many operations
+    #[arg(long)]
+    pub max_steps: Option<usize>,
+
+    /// Aborts the execution of the program after running for this many seconds, not counting the time waiting for input
+    #[arg(long)]
+    pub timeout: Option<f64>,
+
     /// Treats the input as integer numbers rather than characters
     #[arg(long, short = 'n', default_value_t = false)]
     pub number_input: bool,
@@ -51,6 +72,14 @@ pub struct RunArgs {
     #[arg(long, short = 's', default_value_t = false)]
     pub single_input: bool,
 
+    /// Reads the input from stdin as a stream of bytes without prompting (for piping input into programs)
+    #[arg(long, default_value_t = false)]
+    pub stream_input: bool,
+
+    /// What happens to the cell when the program reads past the end of the input
+    #[arg(long, value_enum, default_value_t = EofPolicy::Unchanged)]
+    pub eof: EofPolicy,
+
     /// Interprets the file as brainfuck, skips the compiling process
     #[arg(long, short, default_value_t = false)]
     pub raw: bool,
@@ -59,17 +88,159 @@ pub struct RunArgs {
     #[arg(long, short = 'p', default_value_t = false)]
     pub show: bool,
 
-    /// Skips the use of the inbuilt brainfuck optimizer
+    /// Skips the use of the inbuilt brainfuck optimizer (same as `-O0`)
     #[arg(long, short = 'u', default_value_t = false)]
     pub unoptimized: bool,
 
+    /// How much the brainfuck gets optimized, 0 skips the optimizer, 1 keeps the behaviour strictly the same,
+    /// 2 may change where the tape pointer ends, 3 runs the optimizations until they stop changing the program
+    #[arg(short = 'O', long, value_enum, default_value_t = OptLevel::O2, conflicts_with = "unoptimized")]
+    pub opt_level: OptLevel,
+
+    /// The optimization passes to run instead of the ones of the level, separated by commas
+    /// (reorder, merge-offsets, merge-multiplications, known-values, dead-writes)
+    #[arg(long, value_delimiter = ',', conflicts_with = "unoptimized")]
+    pub passes: Option<Vec<Pass>>,
+
+    /// Uses the fast execution engine, which executes common loops in one step
+    /// (only with wrapping cells, without profiling and without tracing)
+    #[arg(long, default_value_t = false)]
+    pub fast: bool,
+
     /// Dump the tape and tape pointer position to terminal once the program ends
     /// (includes by erroring out)
     #[arg(long, short = 'd', default_value_t = false)]
     pub dump: bool,
+
+    /// Prints the loops in which the program spent the most time once it ends, with the basm instructions which generated them
+    /// (basm files are run unoptimized when profiling so that their loops can be traced back to the basm)
+    #[arg(long, default_value_t = false)]
+    pub profile: bool,
+
+    /// Writes a report of the final state of the program (tape, pointer, outcome) in the specified format
+    /// to the file given with `--report-out`
+    #[arg(long, value_enum, requires = "report_out")]
+    pub report: Option<ReportFormat>,
+
+    /// The path of the file to write the report to, kept apart from the output of the program so it can be parsed
+    #[arg(long, requires = "report")]
+    pub report_out: Option<String>,
+
+    /// Records every executed operation to the specified trace file (slow, the file can get very large)
+    #[arg(long)]
+    pub trace: Option<String>,
+
+    /// Writes the state of the interpreter to the specified file once the program ends
+    /// (includes by erroring out), it can be resumed with `--resume`
+    #[arg(long)]
+    pub snapshot_on_exit: Option<String>,
+
+    /// Resumes the execution from the specified snapshot file (see `--snapshot-on-exit`),
+    /// the cell type, overflow and end of input behaviours, tape and input and output modes are those of the snapshot
+    #[arg(long)]
+    pub resume: Option<String>,
+}
+
+/// The size of the cells of the interpreter.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CellSize {
+    /// Cells of this many bits.
+    Bits(usize),
+    /// Cells of arbitrary size.
+    Big,
+}
+
+impl FromStr for CellSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "big" {
+            return Ok(CellSize::Big)
+        }
+
+        s.parse::<usize>()
+            .map(CellSize::Bits)
+            .map_err(|_| "expected a number of bits or `big`".to_string())
+    }
+}
+
+impl fmt::Display for CellSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellSize::Bits(bits) => write!(f, "{bits}"),
+            CellSize::Big => write!(f, "big"),
+        }
+    }
+}
+
+/// The optimization level of the `-O` flag.
+#[derive(ValueEnum)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OptLevel {
+    /// No optimization
+    #[value(name = "0")]
+    O0,
+    /// Optimizations keeping the behaviour strictly the same
+    #[value(name = "1")]
+    O1,
+    /// The default optimizations
+    #[value(name = "2")]
+    O2,
+    /// The default optimizations until they stop changing the program
+    #[value(name = "3")]
+    O3,
+}
+
+/// Builds the optimisation pipeline described by the flags, `None` if the program should not be optimised.
+fn optimisation_pipeline(unoptimized: bool, level: OptLevel, passes: Option<&Vec<Pass>>) -> Option<Pipeline> {
+    if unoptimized {
+        return None
+    }
+
+    let level = match level {
+        OptLevel::O0 => OptimisationLevel::O0,
+        OptLevel::O1 => OptimisationLevel::O1,
+        OptLevel::O2 => OptimisationLevel::O2,
+        OptLevel::O3 => OptimisationLevel::O3,
+    };
+
+    match (level.pipeline(), passes) {
+        // at -O0 the passes simply run once
+        (pipeline, Some(passes)) => Some(pipeline.unwrap_or_else(|| Pipeline::new([])).with_passes(passes.iter().copied())),
+        (pipeline, None) => pipeline,
+    }
+}
+
+/// The behaviour of the interpreter when reading past the end of the input.
+#[derive(ValueEnum)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EofPolicy {
+    /// Leaves the cell unchanged
+    Unchanged,
+    /// Sets the cell to 0
+    Zero,
+    /// Sets the cell to -1 (or the maximum value for unsigned cells)
+    Max,
+    /// Aborts the execution of the program
+    Error,
+}
+
+/// The format of the report written by `run --report`.
+#[derive(ValueEnum)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReportFormat {
+    /// A JSON document
+    Json,
 }
 
 impl RunArgs {
+    /// The optimisation pipeline described by the flags, `None` if the program should not be optimised.
+    pub fn optimisation_pipeline(&self) -> Option<Pipeline> {
+        // the source map of basm files only matches the unoptimised program
+        let unoptimized = self.unoptimized || (self.profile && !self.raw);
+        optimisation_pipeline(unoptimized, self.opt_level, self.passes.as_ref())
+    }
+
     /// Builds an interpreter configured using the cli flags.
     /// May return `Err` containing a `String` if the arguments are invalid
     pub fn build_interpreter(&self, program: &str) -> Result<Box<dyn InterpreterTrait>, InterpreterBuildingError> {
@@ -77,13 +248,16 @@ impl RunArgs {
 
         // cell type
         let builder = match (self.signed, self.cell_size) {
-            (false, 8) => builder.with_u8(),
-            (false, 16) => builder.with_u16(),
-            (false, 32) => builder.with_u32(),
-            (true, 8) => builder.with_i8(),
-            (true, 16) => builder.with_i16(),
-            (true, 32) => builder.with_i32(),
-            (_, s) => return Err(InterpreterBuildingError::InvalidCellSize { got: s }),
+            (false, CellSize::Bits(8)) => builder.with_u8(),
+            (false, CellSize::Bits(16)) => builder.with_u16(),
+            (false, CellSize::Bits(32)) => builder.with_u32(),
+            (false, CellSize::Bits(64)) => builder.with_u64(),
+            (true, CellSize::Bits(8)) => builder.with_i8(),
+            (true, CellSize::Bits(16)) => builder.with_i16(),
+            (true, CellSize::Bits(32)) => builder.with_i32(),
+            (true, CellSize::Bits(64)) => builder.with_i64(),
+            (_, CellSize::Big) => builder.with_big_int(),
+            (_, CellSize::Bits(s)) => return Err(InterpreterBuildingError::InvalidCellSize { got: s }),
         };
 
         // overflow behaviour
@@ -100,6 +274,54 @@ impl RunArgs {
             builder.without_tape_lenght()
         };
 
+        // tape mode
+        let builder = match (self.bidirectional_tape, self.circular_tape) {
+            (_, Some(0)) => return Err(InterpreterBuildingError::EmptyCircularTape),
+            (_, Some(lenght)) => builder.with_circular_tape(lenght),
+            (true, None) => builder.with_bidirectional_tape(),
+            (false, None) => builder.with_right_infinite_tape(),
+        };
+
+        // execution budgets
+        let builder = if let Some(steps) = self.max_steps {
+            builder.with_step_limit(steps)
+        } else {
+            builder.without_step_limit()
+        };
+
+        let builder = if let Some(seconds) = self.timeout {
+            let Ok(timeout) = Duration::try_from_secs_f64(seconds) else {
+                return Err(InterpreterBuildingError::InvalidTimeout { got: seconds })
+            };
+            builder.with_timeout(timeout)
+        } else {
+            builder.without_timeout()
+        };
+
+        // engine
+        let builder = if self.fast {
+            builder.with_fast_engine()
+        } else {
+            builder.with_standard_engine()
+        };
+
+        // profiling
+        let builder = if self.profile {
+            builder.with_profiling()
+        } else {
+            builder.without_profiling()
+        };
+
+        // tracing
+        let builder = if let Some(path) = &self.trace {
+            let Ok(file) = File::create(path) else {
+                return Err(InterpreterBuildingError::InaccessibleTraceFile { path: path.clone() })
+            };
+            builder.with_trace(file)
+        } else {
+            builder.without_trace()
+        };
+
         // input type
         let builder = if self.number_input {
             builder.with_input_as_number()
@@ -121,6 +343,31 @@ impl RunArgs {
             builder.without_bulk_input()
         };
 
+        // input source
+        let builder = if self.stream_input {
+            builder.with_streamed_input()
+        } else {
+            builder.with_interactive_input()
+        };
+
+        let builder = match self.eof {
+            EofPolicy::Unchanged => builder.with_eof_unchanged(),
+            EofPolicy::Zero => builder.with_eof_as_zero(),
+            EofPolicy::Max => builder.with_eof_as_max(),
+            EofPolicy::Error => builder.with_eof_aborting(),
+        };
+
+        // resuming
+        if let Some(path) = &self.resume {
+            let Ok(snapshot) = fs::read_to_string(path) else {
+                return Err(InterpreterBuildingError::InaccessibleSnapshotFile { path: path.clone() })
+            };
+
+            return Snapshot::parse(&snapshot)
+                .and_then(|snapshot| builder.resume(&snapshot))
+                .map_err(InterpreterBuildingError::InvalidSnapshot)
+        }
+
         Ok(builder.finish())
     }
 }
@@ -129,11 +376,35 @@ impl RunArgs {
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum InterpreterBuildingError {
     /// Cell size was invalid, and thus the interpreter could not be initialized.
-    #[error("specified cell size is invalid, expected 8, 16 or 32, got {got},")]
+    #[error("specified cell size is invalid, expected 8, 16, 32, 64 or big, got {got},")]
     InvalidCellSize {
         /// The specified cell size, which was invalid
         got: usize,
-    }
+    },
+    /// The timeout was negative or too big, and thus the interpreter could not be initialized.
+    #[error("specified timeout is invalid, expected a positive number of seconds, got {got}")]
+    InvalidTimeout {
+        /// The specified timeout, which was invalid
+        got: f64,
+    },
+    /// The circular tape did not have any cell, and thus the interpreter could not be initialized.
+    #[error("a circular tape needs at least one cell")]
+    EmptyCircularTape,
+    /// The trace file could not be created, and thus the interpreter could not be initialized.
+    #[error("failed to create the trace file at {path}")]
+    InaccessibleTraceFile {
+        /// The path of the trace file
+        path: String,
+    },
+    /// The snapshot file could not be read, and thus the interpreter could not be initialized.
+    #[error("failed to read the snapshot file at {path}")]
+    InaccessibleSnapshotFile {
+        /// The path of the snapshot file
+        path: String,
+    },
+    /// The snapshot could not be resumed, and thus the interpreter could not be initialized.
+    #[error(transparent)]
+    InvalidSnapshot(SnapshotError),
 }
 
 /// Arguments for the `compile` command.
@@ -151,7 +422,208 @@ pub struct CompileArgs {
     #[arg(long, short = 'p', default_value_t = false)]
     pub show: bool,
 
-    /// Skips the use of the inbuilt brainfuck optimizer
+    /// Skips the use of the inbuilt brainfuck optimizer (same as `-O0`)
     #[arg(long, short = 'u', default_value_t = false)]
     pub unoptimized: bool,
-}
\ No newline at end of file
+
+    /// How much the brainfuck gets optimized, 0 skips the optimizer, 1 keeps the behaviour strictly the same,
+    /// 2 may change where the tape pointer ends, 3 runs the optimizations until they stop changing the program
+    #[arg(short = 'O', long, value_enum, default_value_t = OptLevel::O2, conflicts_with = "unoptimized")]
+    pub opt_level: OptLevel,
+
+    /// The optimization passes to run instead of the ones of the level, separated by commas
+    /// (reorder, merge-offsets, merge-multiplications, known-values, dead-writes)
+    #[arg(long, value_delimiter = ',', conflicts_with = "unoptimized")]
+    pub passes: Option<Vec<Pass>>,
+
+    /// Checks that the optimised program behaves like the unoptimised one by running both,
+    /// fails on the first divergence
+    #[arg(long, default_value_t = false, conflicts_with = "unoptimized")]
+    pub verify_optimisation: bool,
+
+    /// An input given to the programs when verifying the optimisation, can be repeated
+    /// (defaults to a few inputs, including an empty one)
+    #[arg(long, requires = "verify_optimisation")]
+    pub verify_input: Vec<String>,
+
+    /// Prints the length and the number of tape pointer moves of the bf before and after each optimization pass
+    #[arg(long, default_value_t = false, conflicts_with = "unoptimized")]
+    pub opt_report: bool,
+
+    /// Also prints the operations changed by each optimization pass side by side
+    #[arg(long, default_value_t = false, requires = "opt_report")]
+    pub opt_report_diff: bool,
+
+    /// The type of the cells the program will run on (u8, i8, u16, i16, u32 or i32),
+    /// values which don't fit are errors and constants may wrap around when it is shorter
+    /// (for targets other than bf, it is the type set by `--cell-size` and `--signed`)
+    #[arg(long)]
+    pub target_cell: Option<TargetCell>,
+
+    /// The language to compile the program to
+    #[arg(long, value_enum, default_value_t = Target::Bf)]
+    pub target: Target,
+
+    /// Sets the size of cells in bits of the compiled program (only 8, 16 and 32, not for bf)
+    #[arg(long, short, default_value_t = 8)]
+    pub cell_size: usize,
+
+    /// Sets the cells of the compiled program as signed (not for bf)
+    #[arg(long, short = 'i', default_value_t = false)]
+    pub signed: bool,
+
+    /// The lenght of the tape of the program in cells, compilation fails if an instruction addresses a cell past it
+    /// (for all targets, bf included). Programs compiled to other targets than bf also abort if they exceed it (defaults to 30000)
+    #[arg(long, short)]
+    pub tape_limit: Option<usize>,
+
+    /// Prints the cells addressed by the program, and the aliases and meta-instructions using them
+    #[arg(long, default_value_t = false)]
+    pub memory_map: bool,
+
+    /// What happens to the cell when the compiled program reads past the end of the input (not for bf)
+    #[arg(long, value_enum, default_value_t = EofPolicy::Unchanged)]
+    pub eof: EofPolicy,
+}
+
+/// The cells of `--target-cell` are not the ones of the compiled program.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("the target cell {target_cell} does not match the {machine_cell} cells set by --cell-size and --signed")]
+pub struct TargetCellMismatchError {
+    /// The cells given to `--target-cell`
+    pub target_cell: TargetCell,
+    /// The cells of the compiled program
+    pub machine_cell: TargetCell,
+}
+
+/// The language `compile` outputs.
+#[derive(ValueEnum)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Target {
+    /// Brainfuck
+    Bf,
+    /// A standalone C program
+    C,
+    /// GNU assembler source for a standalone x86-64 Linux program
+    #[value(name = "x86_64-linux")]
+    X86_64Linux,
+    /// A WebAssembly text module, importing its io from the host
+    Wat,
+}
+
+impl Target {
+    /// The extension of the files of the target language.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Target::Bf => "bf",
+            Target::C => "c",
+            Target::X86_64Linux => "s",
+            Target::Wat => "wat",
+        }
+    }
+
+    /// Translates the brainfuck `program` into the target language.
+    pub fn translate(self, program: String, options: &MachineOptions) -> Result<String, BackendError> {
+        match self {
+            Target::Bf => Ok(program),
+            Target::C => backend::to_c(&program, options),
+            Target::X86_64Linux => backend::to_x86_64_linux(&program, options),
+            Target::Wat => backend::to_wat(&program, options),
+        }
+    }
+}
+
+impl CompileArgs {
+    /// The context the program is compiled in, according to the cli flags.
+    /// Errors if `--target-cell` does not match the cells of a target other than bf.
+    pub fn compiler_context(&self) -> Result<MainContext, TargetCellMismatchError> {
+        let mut context = MainContext::new();
+        if let Some(target_cell) = self.target_cell()? {
+            context = context.with_target_cell(target_cell);
+        }
+        if let Some(tape_limit) = self.tape_limit {
+            context = context.with_tape_limit(tape_limit);
+        }
+
+        Ok(context)
+    }
+
+    /// The cells the program will run on, the ones of `--target-cell` for bf
+    /// and the ones set by `--cell-size` and `--signed` for the other targets.
+    /// Errors if `--target-cell` does not match the latter.
+    pub fn target_cell(&self) -> Result<Option<TargetCell>, TargetCellMismatchError> {
+        if self.target == Target::Bf {
+            return Ok(self.target_cell)
+        }
+
+        // invalid cell sizes are reported by the backends
+        let machine_cell = TargetCell::from_bits(self.cell_size as u32, self.signed);
+        match (self.target_cell, machine_cell) {
+            (Some(target_cell), Some(machine_cell)) if target_cell != machine_cell => {
+                Err(TargetCellMismatchError { target_cell, machine_cell })
+            },
+            _ => Ok(machine_cell),
+        }
+    }
+
+    /// The optimisation pipeline described by the flags, `None` if the program should not be optimised.
+    pub fn optimisation_pipeline(&self) -> Option<Pipeline> {
+        optimisation_pipeline(self.unoptimized, self.opt_level, self.passes.as_ref())
+    }
+
+    /// The machine the compiled program emulates, according to the cli flags.
+    pub fn machine_options(&self) -> MachineOptions {
+        MachineOptions {
+            cell_size: self.cell_size,
+            signed: self.signed,
+            tape_lenght: self.tape_limit.unwrap_or(MachineOptions::default().tape_lenght),
+            eof: match self.eof {
+                EofPolicy::Unchanged => EofBehaviour::Unchanged,
+                EofPolicy::Zero => EofBehaviour::Zero,
+                EofPolicy::Max => EofBehaviour::Max,
+                EofPolicy::Error => EofBehaviour::Abort,
+            },
+        }
+    }
+}
+
+/// Arguments for the `trace-view` command.
+#[derive(Args)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct TraceViewArgs {
+    /// Path to the trace file
+    pub file_path: String,
+
+    /// Only shows the operations on the cells within the range (e.g: `3`, `3..6` or `-2..0`),
+    /// relative to the cell the program started on
+    #[arg(long, value_parser = parse_range::<isize>, allow_hyphen_values = true)]
+    pub cells: Option<Range<isize>>,
+
+    /// Only shows the operations with an instruction pointer within the range (e.g: `12` or `12..40`)
+    #[arg(long, value_parser = parse_range::<usize>)]
+    pub instructions: Option<Range<usize>>,
+}
+
+impl TraceViewArgs {
+    /// Creates the filter selecting the trace entries to show.
+    pub fn filter(&self) -> TraceFilter {
+        TraceFilter {
+            cells: self.cells.clone(),
+            instructions: self.instructions.clone(),
+        }
+    }
+}
+
+/// Parses either a single index (`n`) or an exclusive range of indexes (`start..end`).
+fn parse_range<T: FromStr + Add<Output = T> + From<u8> + Copy>(s: &str) -> Result<Range<T>, String> {
+    const EXPECTED: &str = "expected an index (`n`) or a range (`start..end`)";
+
+    if let Some((start, end)) = s.split_once("..") {
+        let start = start.parse::<T>().map_err(|_| EXPECTED.to_string())?;
+        let end = end.parse::<T>().map_err(|_| EXPECTED.to_string())?;
+        Ok(start..end)
+    } else {
+        let index = s.parse::<T>().map_err(|_| EXPECTED.to_string())?;
+        Ok(index..index + T::from(1))
+    }
+}
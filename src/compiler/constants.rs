@@ -0,0 +1,162 @@
+//! Generation of the brainfuck adding constant values to cells, as short as possible.
+
+/// A way of adding a constant value to a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constant {
+    /// Adds the value with `+`, or `-` if it is negative.
+    Offset(i64),
+    /// Adds `factor` to the cell `counter` times with a loop counting down in a scratch cell, then adds `remainder`.
+    /// Aka `++++++++++[>++++++++++<-]>+` adds 101 to the cell right of the scratch cell.
+    Multiplication {
+        /// The number of iterations of the loop, strictly positive.
+        counter: i64,
+        /// The value added to the cell on each iteration.
+        factor: i64,
+        /// The value added to the cell after the loop.
+        remainder: i64,
+    },
+}
+
+impl Constant {
+    /// Finds the shortest constant adding `value` to a cell.
+    /// Multiplication loops are only considered if there is a scratch cell, `scratch_distance` cells away from the cell.
+    /// If the cells wrap around `modulus`, values are equal modulo it, so counting down may be shorter
+    /// (e.g. adding 200 to a `u8` cell is the same as subtracting 56).
+    pub fn shortest(value: i64, scratch_distance: Option<u32>, modulus: Option<u64>) -> Constant {
+        let values = match modulus {
+            Some(modulus) => {
+                let modulus = modulus as i64;
+                let value = value.rem_euclid(modulus);
+                vec![value, value - modulus]
+            },
+            None => vec![value],
+        };
+
+        let candidates = values.iter().map(|value| Constant::Offset(*value));
+        let Some(distance) = scratch_distance else {
+            return candidates.min_by_key(|constant| constant.len(0))
+                .expect("there is at least one value")
+        };
+
+        candidates.chain(values.iter().flat_map(|value| multiplications(*value)))
+            .min_by_key(|constant| constant.len(distance))
+            .expect("there is at least one value")
+    }
+
+    /// The number of operators of the constant, including the moves to a scratch cell `scratch_distance` cells away and back.
+    pub fn len(&self, scratch_distance: u32) -> usize {
+        match *self {
+            Constant::Offset(value) => value.unsigned_abs() as usize,
+            Constant::Multiplication { counter, factor, remainder } => {
+                // `[`, `-` and `]`, and going to the scratch cell, to the cell and back in the loop, and to the cell again
+                let operators = counter.unsigned_abs() + factor.unsigned_abs() + remainder.unsigned_abs() + 3;
+                operators as usize + 4 * scratch_distance as usize
+            },
+        }
+    }
+
+    /// The brainfuck of the constant, starting and ending on the cell, with the scratch cell `scratch_offset` cells away.
+    pub fn to_brainfuck(self, scratch_offset: i64) -> String {
+        match self {
+            Constant::Offset(value) => offset(value),
+            Constant::Multiplication { counter, factor, remainder } => {
+                let to_scratch = moves(scratch_offset);
+                let to_cell = moves(-scratch_offset);
+                format!("{to_scratch}{}[{to_cell}{}{to_scratch}-]{to_cell}{}", offset(counter), offset(factor), offset(remainder))
+            },
+        }
+    }
+}
+
+/// The multiplication loops adding `value` which may be the shortest, one for each number of iterations worth trying.
+fn multiplications(value: i64) -> impl Iterator<Item = Constant> {
+    // a loop running `counter` times adding `factor` is as long as one running `factor` times adding `counter`,
+    // so we only need to try the counters up to the square root
+    let max_counter = value.unsigned_abs().isqrt() as i64 + 1;
+    (2..=max_counter).flat_map(move |counter| {
+        let factor = value / counter;
+        // the remainder is the smallest with either the rounded down or up factor
+        [factor, factor + value.signum()].map(|factor| Constant::Multiplication {
+            counter,
+            factor,
+            remainder: value - counter * factor,
+        })
+    })
+    .filter(|constant| !matches!(constant, Constant::Multiplication { factor: 0, .. }))
+}
+
+/// The `+` or `-` operators adding `value`.
+fn offset(value: i64) -> String {
+    let operator = if value.is_positive() { "+" } else { "-" };
+    operator.repeat(value.unsigned_abs() as usize)
+}
+
+/// The `>` or `<` operators moving the tape pointer by `distance`.
+fn moves(distance: i64) -> String {
+    let operator = if distance.is_positive() { ">" } else { "<" };
+    operator.repeat(distance.unsigned_abs() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::InterpreterBuilder;
+
+    use super::*;
+
+    #[test]
+    fn shortest_constants() {
+        assert_eq!(Constant::shortest(12, None, None), Constant::Offset(12));
+        assert_eq!(Constant::shortest(-7, Some(1), None), Constant::Offset(-7));
+        assert_eq!(
+            Constant::shortest(100, Some(1), None),
+            Constant::Multiplication { counter: 10, factor: 10, remainder: 0 },
+        );
+        assert_eq!(Constant::shortest(100, Some(1), None).to_brainfuck(-1), "<++++++++++[>++++++++++<-]>");
+        assert_eq!(
+            Constant::shortest(-39, Some(2), None),
+            Constant::Multiplication { counter: 5, factor: -8, remainder: 1 },
+        );
+
+        // loops are longer when the scratch cell is far away
+        assert_eq!(Constant::shortest(30, Some(1), None).len(1), 18);
+        assert_eq!(Constant::shortest(30, Some(6), None), Constant::Offset(30));
+
+        // counting down is shorter on wrapping cells
+        assert_eq!(Constant::shortest(200, None, Some(256)), Constant::Offset(-56));
+        assert_eq!(Constant::shortest(-250, None, Some(256)), Constant::Offset(6));
+        assert_eq!(Constant::shortest(200, Some(1), None).len(1), 37);
+        assert_eq!(Constant::shortest(200, Some(1), Some(256)).len(1), 22);
+    }
+
+    #[test]
+    fn constants_under_the_interpreter() {
+        // the cell is 1, and the scratch cell is either 0 or 3
+        let run = |bf: &str, u8_cells: bool| {
+            let mut builder = InterpreterBuilder::new(bf);
+            if !u8_cells {
+                builder = builder.with_u32();
+            }
+            let mut interpreter = builder.finish();
+            interpreter.complete().unwrap();
+
+            let tape = interpreter.report(None).tape;
+            [0, 1, 3].map(|cell| tape.get(cell).cloned().unwrap_or_else(|| "0".to_string()))
+        };
+
+        for value in (0..300).chain([1000, 0x0010_FFFF]) {
+            for scratch in [0u32, 3] {
+                let expected = ["0".to_string(), value.to_string(), "0".to_string()];
+
+                let constant = Constant::shortest(value, Some(scratch.abs_diff(1)), None);
+                let bf = format!(">{}", constant.to_brainfuck(i64::from(scratch) - 1));
+                assert_eq!(run(&bf, false), expected, "{bf}");
+
+                if value < 256 {
+                    let constant = Constant::shortest(value, Some(scratch.abs_diff(1)), Some(256));
+                    let bf = format!(">{}", constant.to_brainfuck(i64::from(scratch) - 1));
+                    assert_eq!(run(&bf, true), expected, "{bf}");
+                }
+            }
+        }
+    }
+}
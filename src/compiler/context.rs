@@ -3,8 +3,13 @@ use super::SendSyncInstruction;
 use super::NormalizedScope;
 use super::Aliases;
 use super::{AliasValue, AliasesTrait};
+use super::{SourceMap, SourceMapEntry};
+use super::TargetCell;
+use super::MemoryMap;
 
-use std::{collections::HashMap, fmt::Debug, rc::Rc};
+use std::{collections::HashMap, fmt::Debug, ops::Range, rc::Rc};
+
+use crate::source::SfSlice;
 
 /// Trait abstracting over the logic of aliasing and subscoping of contexts.
 pub trait ContextTrait: AliasesTrait {
@@ -40,6 +45,39 @@ pub struct MainContext {
     pointer: u32,
     instructions: HashMap<String, Rc<dyn SendSyncInstruction>>,
     global_aliases: Aliases,
+    source_map: SourceMap,
+    /// The names of the meta-instructions currently being inlined, the outermost first.
+    meta_stack: Vec<String>,
+    /// The cells the program will run on, if they are known.
+    target_cell: Option<TargetCell>,
+    /// The number of cells of the tape the program will run on, if it is limited.
+    tape_limit: Option<usize>,
+    memory_map: MemoryMap,
+    /// The operands of the instructions currently being compiled, the outermost instruction first.
+    operand_stack: Vec<Vec<Operand>>,
+}
+
+/// An operand of an instruction being compiled, used to attribute the cells it addresses to the aliases of its arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operand {
+    /// The value of the operand.
+    pub value: u32,
+    /// The aliases used in the expression of the operand.
+    pub aliases: Vec<String>,
+    /// How the instruction uses the operand.
+    pub role: OperandRole,
+}
+
+/// How an instruction uses one of its operands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OperandRole {
+    /// The operand is a cell the instruction addresses, aka the first operand of `INCR`.
+    Address,
+    /// The operand is the argument of this name of a meta-instruction,
+    /// it is a cell if the instructions of the meta-instruction address it.
+    MetaArgument(String),
+    /// The operand is only a value, aka the second operand of `INCR`.
+    Value,
 }
 
 impl Debug for MainContext {
@@ -47,6 +85,8 @@ impl Debug for MainContext {
         f.debug_struct("InnerMainContext")
             .field("pointer", &self.pointer)
             .field("instructions", &self.instructions.keys())
+            .field("target_cell", &self.target_cell)
+            .field("tape_limit", &self.tape_limit)
             .finish()
     }
 }
@@ -57,6 +97,12 @@ impl Default for MainContext {
             pointer: Default::default(),
             instructions: instruction::built_in(),
             global_aliases: Aliases::new(),
+            source_map: SourceMap::new(),
+            meta_stack: Vec::new(),
+            target_cell: None,
+            tape_limit: None,
+            memory_map: MemoryMap::new(),
+            operand_stack: Vec::new(),
         }
     }
 }
@@ -81,6 +127,32 @@ impl MainContext {
         MainContext::default()
     }
 
+    /// Sets the cells the program will run on.
+    /// Values are then checked to fit in the cells, and constants may count down past 0 when it is shorter.
+    #[must_use]
+    pub fn with_target_cell(mut self, target_cell: TargetCell) -> MainContext {
+        self.target_cell = Some(target_cell);
+        self
+    }
+
+    /// Gets the cells the program will run on, `None` if they are unknown.
+    pub fn target_cell(&self) -> Option<TargetCell> {
+        self.target_cell
+    }
+
+    /// Sets the number of cells of the tape the program will run on.
+    /// Compilation then fails on the first instruction addressing a cell past the end of the tape.
+    #[must_use]
+    pub fn with_tape_limit(mut self, tape_limit: usize) -> MainContext {
+        self.tape_limit = Some(tape_limit);
+        self
+    }
+
+    /// Gets the number of cells of the tape the program will run on, `None` if it is not limited.
+    pub fn tape_limit(&self) -> Option<usize> {
+        self.tape_limit
+    }
+
     /// Gets the current pointer position.
     pub fn pointer(&self) -> u32 {
         self.pointer
@@ -103,6 +175,88 @@ impl MainContext {
             Rc::clone(i)
         })
     }
+
+    /// Marks the start of the inlining of the meta-instruction `name`.
+    /// Instructions added to the source map will be marked as coming from it until [`MainContext::exit_meta_instruction`] is called.
+    pub fn enter_meta_instruction(&mut self, name: &str) {
+        self.meta_stack.push(name.to_string());
+    }
+
+    /// Marks the end of the inlining of the last entered meta-instruction.
+    pub fn exit_meta_instruction(&mut self) {
+        self.meta_stack.pop();
+    }
+
+    /// Records that the `instruction` generated the `bf_range` of the program.
+    pub fn add_to_source_map(&mut self, instruction: SfSlice, bf_range: Range<usize>) {
+        self.source_map.push(SourceMapEntry {
+            bf_range,
+            instruction,
+            meta_instructions: self.meta_stack.clone(),
+        });
+    }
+
+    /// Marks the start of the compilation of an instruction with its `operands`.
+    /// Cells addressed will be attributed to the aliases of the operands addressing them until [`MainContext::exit_instruction`] is called.
+    pub fn enter_instruction(&mut self, operands: Vec<Operand>) {
+        self.operand_stack.push(operands);
+    }
+
+    /// Marks the end of the compilation of the last entered instruction.
+    pub fn exit_instruction(&mut self) {
+        self.operand_stack.pop();
+    }
+
+    /// Records that the `cell` was addressed in the memory map,
+    /// along with the meta-instructions and aliases of the instructions being compiled.
+    ///
+    /// The cell is addressed by the innermost instruction, so it gets the aliases of its address operands of the same value.
+    /// The meta-instructions it is inlined in then give the aliases of their arguments to the cell if it was addressed through them.
+    pub fn record_cell(&mut self, cell: u32) {
+        let mut frames = self.operand_stack.iter().rev();
+        let mut aliases = frames.next()
+            .into_iter()
+            .flatten()
+            .filter(|operand| operand.value == cell && operand.role == OperandRole::Address)
+            .flat_map(|operand| operand.aliases.iter().map(String::as_str))
+            .collect::<Vec<_>>();
+
+        for frame in frames {
+            let through_arguments = frame.iter()
+                .filter(|operand| operand.value == cell)
+                .filter(|operand| matches!(&operand.role, OperandRole::MetaArgument(name) if aliases.contains(&name.as_str())))
+                .flat_map(|operand| operand.aliases.iter().map(String::as_str))
+                .collect::<Vec<_>>();
+            aliases.extend(through_arguments);
+        }
+
+        self.memory_map.record(cell, aliases, &self.meta_stack);
+    }
+
+    /// Records that the tape pointer was moved by raw brainfuck, so the memory map may be missing cells.
+    pub fn record_raw_moves(&mut self) {
+        self.memory_map.record_raw_moves();
+    }
+
+    /// Gets the memory map of the instructions compiled so far.
+    pub fn memory_map(&self) -> &MemoryMap {
+        &self.memory_map
+    }
+
+    /// Takes the memory map out of the context, leaving an empty one.
+    pub fn take_memory_map(&mut self) -> MemoryMap {
+        std::mem::take(&mut self.memory_map)
+    }
+
+    /// Gets the source map of the instructions compiled so far.
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Takes the source map out of the context, leaving an empty one.
+    pub fn take_source_map(&mut self) -> SourceMap {
+        std::mem::take(&mut self.source_map)
+    }
 }
 
 impl ContextTrait for MainContext {
@@ -34,6 +34,19 @@ impl Expression {
 
         Ok(base)
     }
+
+    /// Returns the names of the aliases used in the expression.
+    pub fn aliases(&self) -> impl Iterator<Item = &str> {
+        let values = self.mods.iter().map(|m| match m {
+            Mod::Increment { value, .. } | Mod::Decrement { value, .. }
+            | Mod::Multiply { value, .. } | Mod::Divide { value, .. } => value,
+        });
+
+        std::iter::once(&self.base).chain(values).filter_map(|value| match value {
+            ValueRepresentation::Ident(ident) => Some(ident.value()),
+            _ => None,
+        })
+    }
 }
 
 impl ValueRepresentation {
@@ -1,13 +1,13 @@
 //! Declares many objects relative to built-in and meta-instructions.
 //! Refer to `syntax-draft` for documentation about built-in instructions.
 
-use std::{collections::HashMap, fmt::Debug, rc::Rc};
+use std::{collections::HashMap, fmt::Debug, ops::RangeInclusive, rc::Rc};
 
 use thiserror::Error;
 
 use crate::parser::{MetaField, Scope, SignatureArgument};
 
-use super::{context::ContextTrait, normalized_items::NormalizedScope, AliasesTrait, Argument, CompilerError, MainContext};
+use super::{constants::Constant, context::ContextTrait, target_cell::TargetCell, normalized_items::NormalizedScope, AliasesTrait, Argument, CompilerError, MainContext};
 
 pub fn built_in() -> HashMap<String, Rc<dyn SendSyncInstruction>> {
     let mut map = HashMap::new();
@@ -36,17 +36,35 @@ pub trait Instruction {
     /// The number of arguments. Use this as a constant.
     fn arguments(&self) -> &[ArgumentKind];
 
+    /// The arguments which may follow the ones of [`Instruction::arguments`]. Use this as a constant.
+    fn optional_arguments(&self) -> &[ArgumentKind] {
+        &[]
+    }
+
+    /// The indices of the operands which are cells the instruction addresses. Use this as a constant.
+    /// Cells are attributed to the aliases of these operands in the memory map, not to the ones of values.
+    fn addressed_arguments(&self) -> &[usize] {
+        &[]
+    }
+
+    /// The names of the arguments within the instruction, only meta-instructions name them. Use this as a constant.
+    fn argument_names(&self) -> &[String] {
+        &[]
+    }
+
     /// Compiles the given instruction into string format, checks the validity of the arguments passed in.
-    /// Will return an error if the number of arguments does not match the one specified by [`Instruction::arguments`].
+    /// Will return an error if the number of arguments does not match the one specified by [`Instruction::arguments`]
+    /// and [`Instruction::optional_arguments`].
     fn compile_checked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
-        if args.len() > self.arguments().len() {
-            return Err(InstructionError::TooManyArguments { got: args.len(), expected: self.arguments().len() })
+        let max_arguments = self.arguments().len() + self.optional_arguments().len();
+        if args.len() > max_arguments {
+            return Err(InstructionError::TooManyArguments { got: args.len(), expected: max_arguments })
         } else if args.len() < self.arguments().len() {
             return Err(InstructionError::TooFewArguments { got: args.len(), expected: self.arguments().len() })
         }
 
         // finds non-matching arguments
-        let res = self.arguments().iter().enumerate().find(|(i, expected)| {
+        let res = self.arguments().iter().chain(self.optional_arguments()).take(args.len()).enumerate().find(|(i, expected)| {
             match expected {
                 ArgumentKind::Operand => !args[*i].is_operand(),
                 ArgumentKind::Scope => !args[*i].is_scope(),
@@ -130,8 +148,12 @@ impl Instruction for Raw {
         &[ArgumentKind::String]
     }
 
-    fn compile_unchecked(&self, buf: &mut String, _ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
-        buf.push_str(&args[0].clone().unwrap_string());
+    fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
+        let raw = args[0].clone().unwrap_string();
+        if raw.contains(['<', '>']) {
+            ctx.record_raw_moves();
+        }
+        buf.push_str(&raw);
 
         Ok(())
     }
@@ -144,6 +166,10 @@ impl Instruction for Bbox {
         &[ArgumentKind::Operand]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         move_pointer_to(buf, ctx, args[0].clone().unwrap_operand());
 
@@ -172,6 +198,10 @@ impl Instruction for Zero {
         &[ArgumentKind::Operand]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         move_pointer_to(buf, ctx, args[0].clone().unwrap_operand());
         buf.push_str("[-]");
@@ -187,14 +217,21 @@ impl Instruction for Incr {
         &[ArgumentKind::Operand, ArgumentKind::Operand]
     }
 
+    fn optional_arguments(&self) -> &[ArgumentKind] {
+        &[ArgumentKind::Operand]
+    }
+
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0, 2]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let pos = args[0].clone().unwrap_operand();
         let incrementation = args[1].clone().unwrap_operand();
+        let scratch = scratch_cell(args, 2, pos..=pos)?;
+        check_value(ctx, incrementation as i64, 1)?;
 
-        move_pointer_to(buf, ctx, pos);
-        for _ in 0..incrementation {
-            buf.push('+');
-        }
+        add_constant(buf, ctx, pos, incrementation as i64, scratch);
 
         Ok(())
     }
@@ -207,15 +244,27 @@ impl Instruction for Decr {
         &[ArgumentKind::Operand, ArgumentKind::Operand]
     }
 
+    fn optional_arguments(&self) -> &[ArgumentKind] {
+        &[ArgumentKind::Operand]
+    }
+
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0, 2]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let pos = args[0].clone().unwrap_operand();
-        let incrementation = args[1].clone().unwrap_operand();
-
-        move_pointer_to(buf, ctx, pos);
-        for _ in 0..incrementation {
-            buf.push('-');
+        let decrementation = args[1].clone().unwrap_operand();
+        let scratch = scratch_cell(args, 2, pos..=pos)?;
+        // signed cells may go down to their minimum, which is further away than their maximum
+        if ctx.target_cell().is_some_and(TargetCell::is_signed) {
+            check_value(ctx, -(decrementation as i64), 1)?;
+        } else {
+            check_value(ctx, decrementation as i64, 1)?;
         }
 
+        add_constant(buf, ctx, pos, -(decrementation as i64), scratch);
+
         Ok(())
     }
 }
@@ -227,6 +276,10 @@ impl Instruction for Copy {
         &[ArgumentKind::Operand, ArgumentKind::Operand, ArgumentKind::Operand]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0, 1, 2]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let origin = args[0].clone().unwrap_operand();
         let pos1 = args[1].clone().unwrap_operand();
@@ -254,6 +307,10 @@ impl Instruction for Addp {
         &[ArgumentKind::Operand, ArgumentKind::Operand]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0, 1]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let pos1 = args[0].clone().unwrap_operand();
         let pos2 = args[1].clone().unwrap_operand();
@@ -278,6 +335,10 @@ impl Instruction for Subp {
         &[ArgumentKind::Operand, ArgumentKind::Operand]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0, 1]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let pos1 = args[0].clone().unwrap_operand();
         let pos2 = args[1].clone().unwrap_operand();
@@ -302,32 +363,33 @@ impl Instruction for Whne {
         &[ArgumentKind::Operand, ArgumentKind::Operand, ArgumentKind::Scope]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let variable = args[0].clone().unwrap_operand();
         let compared = args[1].clone().unwrap_operand();
         let scope = args[2].clone().unwrap_scope();
+        check_value(ctx, compared as i64, 1)?;
+
+        let modulus = ctx.target_cell().map(TargetCell::modulus);
+        let subtract = Constant::shortest(-(compared as i64), None, modulus).to_brainfuck(0);
+        let add = Constant::shortest(compared as i64, None, modulus).to_brainfuck(0);
 
         move_pointer_to(buf, ctx, variable);
-        for _ in 0..compared {
-            buf.push('-');
-        }
+        buf.push_str(&subtract);
         buf.push('[');
-        for _ in 0..compared {
-            buf.push('+');
-        }
+        buf.push_str(&add);
 
         if let Err(e) = scope.compile(ctx, buf) {
             return Err(InstructionError::ArgumentScopeError(scope.from.clone(), Box::new(e)))
         }
 
         move_pointer_to(buf, ctx, variable);
-        for _ in 0..compared {
-            buf.push('-');
-        }
+        buf.push_str(&subtract);
         buf.push(']');
-        for _ in 0..compared {
-            buf.push('+');
-        }
+        buf.push_str(&add);
 
         Ok(())
     }
@@ -340,6 +402,10 @@ impl Instruction for In {
         &[ArgumentKind::Operand]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let pos1 = args[0].clone().unwrap_operand();
 
@@ -357,6 +423,10 @@ impl Instruction for Out {
         &[ArgumentKind::Operand]
     }
 
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let pos1 = args[0].clone().unwrap_operand();
 
@@ -374,16 +444,24 @@ impl Instruction for Lstr {
         &[ArgumentKind::Operand, ArgumentKind::String]
     }
 
+    fn optional_arguments(&self) -> &[ArgumentKind] {
+        &[ArgumentKind::Operand]
+    }
+
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0, 2]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let start_addr = args[0].clone().unwrap_operand();
         let string = args[1].clone().unwrap_string();
+        let end_addr = start_addr + (string.chars().count() as u32).saturating_sub(1);
+        let scratch = scratch_cell(args, 2, start_addr..=end_addr)?;
+        check_string(ctx, &string, 1)?;
 
         for (i, ch) in string.chars().enumerate() {
             let addr = start_addr + i as u32;
-            move_pointer_to(buf, ctx, addr);
-            for _ in 0..(ch as u32) {
-                buf.push('+');
-            }
+            add_constant(buf, ctx, addr, ch as i64, scratch);
         }
 
         Ok(())
@@ -397,37 +475,38 @@ impl Instruction for Pstr {
         &[ArgumentKind::Operand, ArgumentKind::String]
     }
 
+    fn optional_arguments(&self) -> &[ArgumentKind] {
+        &[ArgumentKind::Operand]
+    }
+
+    fn addressed_arguments(&self) -> &[usize] {
+        &[0, 2]
+    }
+
     fn compile_unchecked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         let buf_cell = args[0].clone().unwrap_operand();
         let string = args[1].clone().unwrap_string();
+        let scratch = scratch_cell(args, 2, buf_cell..=buf_cell)?;
+        let scratch_distance = scratch.map(|scratch| scratch.abs_diff(buf_cell));
+        let modulus = ctx.target_cell().map(TargetCell::modulus);
+        check_string(ctx, &string, 1)?;
 
         move_pointer_to(buf, ctx, buf_cell);
 
-        let mut last_value: u32 = 0;
+        let mut last_value: i64 = 0;
         for ch in string.chars() {
-            let value = ch as u32;
-            
-            let dist_from_zero = value;
-            let dist_from_last = last_value.abs_diff(value);
+            let value = ch as i64;
+
+            let from_zero = Constant::shortest(value, scratch_distance, modulus);
+            let from_last = Constant::shortest(value - last_value, scratch_distance, modulus);
 
             // if byte is closer to zero than the current cell
-            if dist_from_zero < dist_from_last {
+            if from_zero.len(scratch_distance.unwrap_or(0)) < from_last.len(scratch_distance.unwrap_or(0)) {
                 buf.push_str("[-]");
-                (0..value).for_each(|_| buf.push('+'));
-
-                buf.push('.');
-                last_value = value;
-                continue
-            }
-
-            // else we just move from the last to the one we want
-            let movement = value as i16 - last_value as i16;
-            for _ in 0..movement.abs() {
-                if movement.is_positive() {
-                    buf.push('+');
-                } else {
-                    buf.push('-');
-                }
+                add_constant(buf, ctx, buf_cell, value, scratch);
+            } else {
+                // else we just move from the last to the one we want
+                add_constant(buf, ctx, buf_cell, value - last_value, scratch);
             }
 
             buf.push('.');
@@ -496,6 +575,10 @@ impl Instruction for MetaInstruction {
         &self.arguments
     }
 
+    fn argument_names(&self) -> &[String] {
+        &self.argument_names
+    }
+
     fn compile_checked(&self, buf: &mut String, ctx: &mut MainContext, args: &[Argument]) -> Result<(), InstructionError> {
         if args.len() > self.arguments().len() {
             return Err(InstructionError::TooManyArguments { got: args.len(), expected: self.arguments().len() })
@@ -537,7 +620,12 @@ impl Instruction for MetaInstruction {
         let normalized = NormalizedScope::new(self.from.contents.clone(), &mut scope_ctx);
 
         let res = match normalized {
-            Ok(n) => n.compile(ctx, buf),
+            Ok(n) => {
+                ctx.enter_meta_instruction(self.name());
+                let res = n.compile(ctx, buf);
+                ctx.exit_meta_instruction();
+                res
+            },
             Err(e) => return Err(InstructionError::CouldNotInlineMeta(self.from.clone(), Box::new(e)))
         };
 
@@ -578,6 +666,48 @@ fn move_pointer_to(buf: &mut String, ctx: &mut MainContext, nposition: u32) {
     }
 
     ctx.set_pointer(nposition);
+    ctx.record_cell(nposition);
+}
+
+/// Adds `value` to the cell at `position`, with a multiplication loop in the `scratch` cell if there is one and it is shorter.
+/// The scratch cell needs to be 0, and is left at 0.
+/// If the target cell of `ctx` is known, the value may be reached by wrapping around.
+fn add_constant(buf: &mut String, ctx: &mut MainContext, position: u32, value: i64, scratch: Option<u32>) {
+    let modulus = ctx.target_cell().map(TargetCell::modulus);
+    let constant = Constant::shortest(value, scratch.map(|scratch| scratch.abs_diff(position)), modulus);
+
+    move_pointer_to(buf, ctx, position);
+    buf.push_str(&constant.to_brainfuck(scratch.map_or(0, |scratch| scratch as i64 - position as i64)));
+    if let (Some(scratch), Constant::Multiplication { .. }) = (scratch, constant) {
+        ctx.record_cell(scratch);
+    }
+}
+
+/// Returns the optional scratch cell argument at `idx`, errors if it is one of the `written` cells.
+fn scratch_cell(args: &[Argument], idx: usize, written: RangeInclusive<u32>) -> Result<Option<u32>, InstructionError> {
+    let Some(scratch) = args.get(idx).map(|arg| arg.clone().unwrap_operand()) else { return Ok(None) };
+    if written.contains(&scratch) {
+        return Err(InstructionError::ScratchCellOverlaps { scratch })
+    }
+
+    Ok(Some(scratch))
+}
+
+/// Errors if the target cell of `ctx` can't hold the `value` of the argument at `place`.
+fn check_value(ctx: &MainContext, value: i64, place: usize) -> Result<(), InstructionError> {
+    match ctx.target_cell() {
+        Some(target_cell) if !target_cell.contains(value) => Err(InstructionError::ValueOutOfRange { value, target_cell, place }),
+        _ => Ok(()),
+    }
+}
+
+/// Errors if the target cell of `ctx` can't hold one of the characters of the `string` argument at `place`.
+fn check_string(ctx: &MainContext, string: &str, place: usize) -> Result<(), InstructionError> {
+    let Some(target_cell) = ctx.target_cell() else { return Ok(()) };
+    match string.chars().find(|ch| !target_cell.contains(*ch as i64)) {
+        Some(character) => Err(InstructionError::CharacterOutOfRange { character, target_cell, place }),
+        None => Ok(()),
+    }
 }
 
 /// Subtype of `CompilerError`, when instruction error at compile (writing to bf) time.
@@ -607,6 +737,22 @@ pub enum InstructionError {
     MalformedAlis,
     #[error("error in scope argument")]
     ArgumentScopeError(Scope, #[source] Box<CompilerError>),
+    #[error("the value {value} does not fit in {target_cell} cells")]
+    ValueOutOfRange {
+        value: i64,
+        target_cell: TargetCell,
+        place: usize,
+    },
+    #[error("the character '{character}' ({}) does not fit in {target_cell} cells", *character as u32)]
+    CharacterOutOfRange {
+        character: char,
+        target_cell: TargetCell,
+        place: usize,
+    },
+    #[error("the scratch cell {scratch} is also a cell written by the instruction")]
+    ScratchCellOverlaps {
+        scratch: u32,
+    },
 }
 
 impl InstructionError {
@@ -627,7 +773,7 @@ impl InstructionError {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::{interpreter::InterpreterBuilder, source::SourceFile, transpile};
+    use crate::{compiler::{MainContext, TargetCell}, interpreter::InterpreterBuilder, source::SourceFile, transpile, transpile_with_context, Lint};
 
     /// Test implementing custom branch instructions like IFNE and IFEQ
     /// and uses it to check wheter 9+10 == 19.
@@ -692,10 +838,11 @@ mod tests {
 
         let mut inter = InterpreterBuilder::new(&program)
             .with_output_as_character()
+            .with_output_capture()
             .finish();
         inter.complete().unwrap();
 
-        assert_eq!(inter.captured_output(), "Hello, world!\n");
+        assert_eq!(inter.captured_output().unwrap(), "Hello, world!\n");
     }
 
     #[test]
@@ -708,9 +855,100 @@ mod tests {
         let mut inter = InterpreterBuilder::new(&program)
             .with_output_as_character()
             .with_u32()
+            .with_output_capture()
+            .finish();
+        inter.complete().unwrap();
+
+        assert_eq!(inter.captured_output().unwrap(), "Ĥéllo, wôrld!");
+    }
+
+    #[test]
+    fn constants_with_scratch_cell() {
+        let file = "
+        [main] [
+        INCR 1 200 0;
+        DECR 2 99 0;
+        LSTR 3 \"Hi!\" 6;
+        PSTR 7 \"Hello\" 6;
+        ]
+        ";
+        let sf = SourceFile::from_raw_parts(PathBuf::new(), file.to_string())
+            .leak();
+        let program = transpile(sf).unwrap();
+        assert!(program.contains('['), "constants should use multiplication loops");
+
+        let mut inter = InterpreterBuilder::new(&program)
+            .with_output_as_character()
+            .with_u32()
+            .with_output_capture()
             .finish();
         inter.complete().unwrap();
+        assert_eq!(inter.captured_output().unwrap(), "Hello");
+
+        let tape = inter.tape().downcast_ref::<Vec<u32>>()
+            .unwrap();
+        assert_eq!(tape[..8], [0, 200, 99u32.wrapping_neg(), 'H' as u32, 'i' as u32, '!' as u32, 0, 0]);
+
+        // the scratch cell can't be written by the instruction
+        let file = "[main] [ LSTR 3 \"Hi!\" 4; ]";
+        let sf = SourceFile::from_raw_parts(PathBuf::new(), file.to_string())
+            .leak();
+        assert!(transpile(sf).is_err());
+    }
+
+    #[test]
+    fn values_checked_against_target_cell() {
+        let transpile_for = |file: &str, target_cell| {
+            let sf = SourceFile::from_raw_parts(PathBuf::new(), file.to_string())
+                .leak();
+            transpile_with_context(sf, MainContext::new().with_target_cell(target_cell))
+                .map(|(program, _)| program)
+                .map_err(|errors| (sf, errors))
+        };
 
-        assert_eq!(inter.captured_output(), "Ĥéllo, wôrld!");
+        let file = "[main] [ INCR 0 300; ]";
+        let (sf, errors) = transpile_for(file, TargetCell::U8).unwrap_err();
+        let start = file.find("300").unwrap();
+        assert_eq!(errors[0].lint(), Lint::new_error_range(sf, start..start+3));
+        assert!(transpile_for(file, TargetCell::U16).is_ok());
+
+        // signed cells can go down further than up
+        assert!(transpile_for("[main] [ INCR 0 128; ]", TargetCell::I8).is_err());
+        assert!(transpile_for("[main] [ DECR 0 128; ]", TargetCell::I8).is_ok());
+        assert!(transpile_for("[main] [ DECR 0 129; ]", TargetCell::I8).is_err());
+        assert!(transpile_for("[main] [ WHNE 0 256 []; ]", TargetCell::U8).is_err());
+
+        let file = "[main] [ LSTR 0 \"Ĥi\"; ]";
+        let (sf, errors) = transpile_for(file, TargetCell::U8).unwrap_err();
+        let start = file.find('"').unwrap();
+        assert_eq!(errors[0].lint(), Lint::new_error_range(sf, start..file.rfind('"').unwrap()+1));
+        assert!(transpile_for(file, TargetCell::U16).is_ok());
+        assert!(transpile_for("[main] [ PSTR 0 \"é\"; ]", TargetCell::I8).is_err());
+        assert!(transpile_for("[main] [ PSTR 0 \"é\"; ]", TargetCell::U8).is_ok());
+    }
+
+    #[test]
+    fn constants_wrap_around_target_cell() {
+        let file = "
+        [main] [
+        INCR 0 250;
+        DECR 1 255;
+        WHNE 2 254 [ INCR 2 1; ];
+        PSTR 3 \"\u{1}\u{ff}\";
+        OUT 0; OUT 1; OUT 2;
+        ]
+        ";
+        let sf = SourceFile::from_raw_parts(PathBuf::new(), file.to_string())
+            .leak();
+        let (program, _) = transpile_with_context(sf, MainContext::new().with_target_cell(TargetCell::U8)).unwrap();
+        // counting down is shorter
+        assert!(!program.contains("++++++++++"), "{program}");
+
+        let mut inter = InterpreterBuilder::new(&program)
+            .with_output_as_number()
+            .with_output_capture()
+            .finish();
+        inter.complete().unwrap();
+        assert_eq!(inter.captured_output().unwrap().trim(), "1 255 250 1 254");
     }
-}
\ No newline at end of file
+}
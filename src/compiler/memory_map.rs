@@ -0,0 +1,204 @@
+//! Static analysis of the cells of the tape addressed by a program.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use colored::Colorize as _;
+
+/// The cells of the tape a program addresses, as known at compile time.
+/// Only the cells instructions move the tape pointer to are known,
+/// the moves of `RAW` instructions may reach others (see [`MemoryMap::is_complete`]).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MemoryMap {
+    cells: BTreeMap<u32, CellUsage>,
+    raw_moves: bool,
+}
+
+/// How a program uses a cell.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CellUsage {
+    /// The number of times instructions address the cell.
+    pub accesses: usize,
+    /// The aliases of the arguments addressing the cell, aka `Aacc` for `INCR Aacc 1;` or `sp` for `ZERO sp+1;`.
+    pub aliases: BTreeSet<String>,
+    /// The meta-instructions the cell is addressed from, including the ones they are inlined in.
+    pub meta_instructions: BTreeSet<String>,
+}
+
+impl MemoryMap {
+    /// Creates a new empty [`MemoryMap`].
+    pub fn new() -> MemoryMap {
+        MemoryMap::default()
+    }
+
+    /// Records that the `cell` was addressed by an instruction with arguments using the `aliases`,
+    /// inlined from the `meta_instructions`.
+    pub fn record<'a>(&mut self, cell: u32, aliases: impl IntoIterator<Item = &'a str>, meta_instructions: &[String]) {
+        let usage = self.cells.entry(cell).or_default();
+        usage.accesses += 1;
+        usage.aliases.extend(aliases.into_iter().map(str::to_string));
+        usage.meta_instructions.extend(meta_instructions.iter().cloned());
+    }
+
+    /// Records that a `RAW` instruction moved the tape pointer, so the map may be missing cells.
+    pub fn record_raw_moves(&mut self) {
+        self.raw_moves = true;
+    }
+
+    /// Returns `true` if no `RAW` instruction moved the tape pointer, so the map has all the cells the program addresses.
+    pub fn is_complete(&self) -> bool {
+        !self.raw_moves
+    }
+
+    /// Returns the cells addressed and how they are used, in increasing order.
+    pub fn cells(&self) -> impl Iterator<Item = (u32, &CellUsage)> {
+        self.cells.iter().map(|(cell, usage)| (*cell, usage))
+    }
+
+    /// Returns how the `cell` is used, `None` if it is never addressed.
+    pub fn usage(&self, cell: u32) -> Option<&CellUsage> {
+        self.cells.get(&cell)
+    }
+
+    /// Returns the highest cell addressed, `None` if the program does not address any.
+    pub fn highest_cell(&self) -> Option<u32> {
+        self.cells.last_key_value().map(|(cell, _)| *cell)
+    }
+
+    /// Returns the number of cells the tape needs to hold all the cells addressed.
+    pub fn tape_length(&self) -> usize {
+        self.highest_cell().map_or(0, |cell| cell as usize + 1)
+    }
+
+    /// Prints the cells addressed and how they are used to stdout.
+    pub fn print(&self) {
+        println!("{}", "-- MEMORY MAP --".red().underline().bold());
+        println!(
+            "{} {} cells addressed, tape of {} cells",
+            "total:".black(),
+            self.cells.len(),
+            self.tape_length(),
+        );
+        if !self.is_complete() {
+            println!("{}", "RAW instructions move the tape pointer, they may address other cells".yellow());
+        }
+
+        for (cell, usage) in self.cells() {
+            print!("{} {} accesses", format!("#{cell}:").black(), usage.accesses);
+            if !usage.aliases.is_empty() {
+                print!(", {} {}", "aliases".black(), join(&usage.aliases));
+            }
+            if !usage.meta_instructions.is_empty() {
+                print!(", {} {}", "in".black(), join(&usage.meta_instructions));
+            }
+            println!();
+        }
+        println!();
+    }
+}
+
+fn join(names: &BTreeSet<String>) -> String {
+    names.iter().map(String::as_str).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{compiler::MainContext, source::SourceFile, transpile_with_context, Lint};
+
+    use super::*;
+
+    fn memory_map(file: &str) -> MemoryMap {
+        let sf = SourceFile::from_raw_parts(PathBuf::new(), file.to_string())
+            .leak();
+        let (_, mut context) = transpile_with_context(sf, MainContext::new()).unwrap();
+        context.take_memory_map()
+    }
+
+    #[test]
+    fn cells_addressed() {
+        let map = memory_map("
+        [@CLR Acell] [
+        ZERO Acell;
+        ]
+
+        [main] [
+        ALIS sp 4;
+        INCR 1 100 7;
+        COPY 1 sp sp+1;
+        CLR sp+1;
+        LSTR 10 \"ab\";
+        ]
+        ");
+
+        assert_eq!(map.cells().map(|(cell, _)| cell).collect::<Vec<_>>(), [1, 4, 5, 7, 10, 11]);
+        assert_eq!(map.highest_cell(), Some(11));
+        assert_eq!(map.tape_length(), 12);
+        assert!(map.is_complete());
+
+        // the scratch cell is addressed too
+        assert_eq!(map.usage(7), Some(&CellUsage { accesses: 1, ..Default::default() }));
+        assert_eq!(map.usage(4).unwrap().aliases, BTreeSet::from(["sp".to_string()]));
+        let usage = map.usage(5).unwrap();
+        assert_eq!(usage.aliases, BTreeSet::from(["sp".to_string(), "Acell".to_string()]));
+        assert_eq!(usage.meta_instructions, BTreeSet::from(["CLR".to_string()]));
+        assert!(map.usage(2).is_none());
+
+        // only the operands addressing cells give them their aliases
+        let map = memory_map("[main] [ ALIS amount 5; INCR 5 amount; ]");
+        assert!(map.usage(5).unwrap().aliases.is_empty());
+        let map = memory_map("
+        [@ADD Acell Aamount] [
+        INCR Acell Aamount;
+        ]
+
+        [main] [
+        ALIS amount 5;
+        ALIS sp 3;
+        ADD sp amount;
+        ]
+        ");
+        assert_eq!(map.usage(3).unwrap().aliases, BTreeSet::from(["sp".to_string(), "Acell".to_string()]));
+        assert!(map.usage(5).is_none());
+
+        let map = memory_map("[main] [ RAW \"[>]\"; OUT 3; ]");
+        assert!(!map.is_complete());
+        assert_eq!(map.highest_cell(), Some(3));
+        assert_eq!(memory_map("[main] [ RAW \"+\"; ]").highest_cell(), None);
+    }
+
+    #[test]
+    fn tape_limit_exceeded() {
+        let file = "
+        [@CLR Acell] [
+        ZERO Acell;
+        ]
+
+        [main] [
+        OUT 2;
+        CLR 3;
+        ]
+        ";
+        let transpile_with_limit = |tape_limit| {
+            let sf = SourceFile::from_raw_parts(PathBuf::new(), file.to_string())
+                .leak();
+            transpile_with_context(sf, MainContext::new().with_tape_limit(tape_limit))
+                .map(|_| ())
+                .map_err(|errors| (sf, errors))
+        };
+
+        assert!(transpile_with_limit(4).is_ok());
+
+        // the error is on the meta-instruction, caused by the instruction addressing the cell within it
+        let (sf, errors) = transpile_with_limit(3).unwrap_err();
+        let start = file.find("CLR 3").unwrap();
+        assert_eq!(errors[0].lint(), Lint::new_error_range(sf, start..start+"CLR 3;".len()));
+        let start = file.find("ZERO").unwrap();
+        let source = errors[0].compiler_source().unwrap();
+        assert_eq!(source.lint(), Lint::new_error_range(sf, start..start+"ZERO Acell;".len()));
+
+        let (sf, errors) = transpile_with_limit(2).unwrap_err();
+        let start = file.find("OUT").unwrap();
+        assert_eq!(errors[0].lint(), Lint::new_error_range(sf, start..start+"OUT 2;".len()));
+    }
+}
@@ -1,6 +1,9 @@
 //! The basm compiler.
 
 mod instruction;
+mod constants;
+mod target_cell;
+pub use target_cell::{TargetCell, UnknownTargetCellError};
 use instruction::{InstructionError, MetaInstruction, SendSyncInstruction};
 pub use normalized_items::NormalizedScope;
 use thiserror::Error;
@@ -10,7 +13,11 @@ mod aliases;
 use aliases::Aliases;
 pub use aliases::{AliasValue, AliasesTrait};
 mod context;
-pub use context::{ContextTrait, MainContext, ScopeContext};
+pub use context::{ContextTrait, MainContext, Operand, OperandRole, ScopeContext};
+mod source_map;
+pub use source_map::{SourceMap, SourceMapEntry};
+mod memory_map;
+pub use memory_map::{CellUsage, MemoryMap};
 
 use std::fmt::Debug;
 
@@ -21,6 +28,18 @@ pub fn compile(program: &ParsedFile) -> Result<String, CompilerError> {
     Compiler::compile(program)
 }
 
+/// Compiles a [`ParsedProgram`] into a brainfuck program in string format,
+/// alongside the [`SourceMap`] linking the brainfuck back to the basm instructions.
+pub fn compile_with_source_map(program: &ParsedFile) -> Result<(String, SourceMap), CompilerError> {
+    Compiler::compile_with_source_map(program)
+}
+
+/// Compiles a [`ParsedProgram`] into a brainfuck program in string format, starting from the `context`
+/// (e.g. one made for a [`TargetCell`]), alongside the context once the whole program was compiled.
+pub fn compile_with_context(program: &ParsedFile, context: MainContext) -> Result<(String, MainContext), CompilerError> {
+    Compiler::compile_with_context(program, context)
+}
+
 /// The heart of the compilation logic. 732
 pub struct Compiler {
     /// The program being built.
@@ -32,9 +51,23 @@ impl Compiler {
     /// Compiles a [`ParsedFile`] into a string representation of the brainfuck program.
     /// Errors if the program does not contain a main field.
     pub fn compile(program: &ParsedFile) -> Result<String, CompilerError> {
+        Compiler::compile_with_source_map(program).map(|(program, _)| program)
+    }
+
+    /// Compiles a [`ParsedFile`] into a string representation of the brainfuck program and its [`SourceMap`].
+    /// Errors if the program does not contain a main field.
+    pub fn compile_with_source_map(program: &ParsedFile) -> Result<(String, SourceMap), CompilerError> {
+        Compiler::compile_with_context(program, MainContext::new())
+            .map(|(program, mut context)| (program, context.take_source_map()))
+    }
+
+    /// Compiles a [`ParsedFile`] into a string representation of the brainfuck program, starting from the `context`.
+    /// Returns the context once the program was compiled, holding the [`SourceMap`].
+    /// Errors if the program does not contain a main field.
+    pub fn compile_with_context(program: &ParsedFile, context: MainContext) -> Result<(String, MainContext), CompilerError> {
         let mut compiler = Compiler {
             program_buffer: String::new(),
-            context: MainContext::new(),
+            context,
         };
 
         if let Some(setup_field) = program.setup_field.clone() {
@@ -52,7 +85,7 @@ impl Compiler {
         let normalized_main = NormalizedScope::new(main_field.contents, &mut compiler.context.build_subscope_context())?;
         normalized_main.compile(&mut compiler.context, &mut compiler.program_buffer)?;
 
-        Ok(compiler.program_buffer)
+        Ok((compiler.program_buffer, compiler.context))
     }
 
     /// Evaluates a meta-instruction.
@@ -142,6 +175,16 @@ pub enum CompilerError {
     /// An expression tried to divide by 0.
     #[error("expression tried to divide by zero")]
     DivisionByZero(Expression),
+    /// An instruction addressed a cell past the end of the tape declared in the context.
+    #[error("cell {cell} is past the end of the tape of {tape_limit} cells")]
+    TapeLimitExceeded {
+        /// The cell addressed.
+        cell: u32,
+        /// The number of cells of the tape.
+        tape_limit: usize,
+        /// The instruction addressing the cell.
+        instruction: ParsedInstruction,
+    },
     /// A program which is compiled, needs a main field definied in a file.
     /// If there is no main field, this error will be thrown.
     #[error("the program is missing a [main] field")]
@@ -155,12 +198,16 @@ impl CompilerErrorTrait for CompilerError {
             CompilerError::Instruction(ie, instruction) => {
                 match ie {
                     InstructionError::ArgumentScopeError(_, e) => return e.lint(),
+                    InstructionError::ValueOutOfRange { place, .. } | InstructionError::CharacterOutOfRange { place, .. } => {
+                        instruction.arguments[*place].slice()
+                    },
                     _ => instruction.slice(),
                 }
             },
             CompilerError::InstructionNotDefined(i) => i.slice(),
             CompilerError::DoubleDeclaration(f) => f.name.slice(),
             CompilerError::DivisionByZero(e) => e.slice(),
+            CompilerError::TapeLimitExceeded { instruction, .. } => instruction.slice(),
             CompilerError::MissingMain => return None,
         };
 
@@ -224,9 +271,10 @@ mod tests {
         let bf_prog = transpile(sf).unwrap();
         let mut inter = InterpreterBuilder::new(&bf_prog)
             .with_output_as_number()
+            .with_output_capture()
             .finish();
         inter.complete().unwrap();
-        assert_eq!(inter.captured_output().trim(), "42");
+        assert_eq!(inter.captured_output().unwrap().trim(), "42");
 
         let prog_str = "
         [setup] [
@@ -248,9 +296,10 @@ mod tests {
         let bf_prog = transpile(sf).unwrap();
         let mut inter = InterpreterBuilder::new(&bf_prog)
             .with_output_as_number()
+            .with_output_capture()
             .finish();
         inter.complete().unwrap();
-        assert_eq!(inter.captured_output().trim(), "42");
+        assert_eq!(inter.captured_output().unwrap().trim(), "42");
     }
 
     #[test]
@@ -279,9 +328,10 @@ mod tests {
         let bf_prog = transpile(sf).unwrap();
         let mut inter = InterpreterBuilder::new(&bf_prog)
             .with_output_as_number()
+            .with_output_capture()
             .finish();
         inter.complete().unwrap();
-        assert_eq!(inter.captured_output().trim(), "10");
+        assert_eq!(inter.captured_output().unwrap().trim(), "10");
     }
 
     #[test]
@@ -309,9 +359,10 @@ mod tests {
         let bf_prog = transpile(sf).unwrap();
         let mut inter = InterpreterBuilder::new(&bf_prog)
             .with_output_as_number()
+            .with_output_capture()
             .finish();
         inter.complete().unwrap();
-        assert_eq!(inter.captured_output().trim(), "7 42");
+        assert_eq!(inter.captured_output().unwrap().trim(), "7 42");
 
         // -- this shouldn't work!! --
         let prog_str = "
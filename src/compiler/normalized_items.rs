@@ -2,9 +2,9 @@ use std::{fmt::Debug, rc::Rc};
 
 use either::Either;
 
-use crate::parser::{Expression, Instruction as ParsedInstruction, Scope as ParsedScope, ValueRepresentation, Argument as ParsedArgument};
+use crate::parser::{Expression, Instruction as ParsedInstruction, LanguageItem as _, Scope as ParsedScope, ValueRepresentation, Argument as ParsedArgument};
 
-use super::{context::ContextTrait, instruction::{InstructionError, SendSyncInstruction}, Argument, CompilerError, MainContext};
+use super::{context::{ContextTrait, Operand, OperandRole}, instruction::{InstructionError, SendSyncInstruction}, Argument, CompilerError, MainContext};
 
 /// An instruction with all arguments normalized.
 #[derive(Clone)]
@@ -87,12 +87,48 @@ impl NormalizedInstruction {
     }
 
     /// Compiles the current instruction into the `buf` in string format.
+    /// Records the brainfuck generated in the source map of `ctx`.
+    /// Also records the cells addressed in the memory map of `ctx`, and errors if one is past its tape limit.
     pub fn compile(&self, ctx: &mut MainContext, buf: &mut String) -> Result<(), CompilerError> {
-        if let Err(ie) = self.kind.compile_checked(buf, ctx, &self.arguments) {
-            Err(CompilerError::Instruction(ie, self.from.clone()))
-        } else {
-            Ok(())
+        let start = buf.len();
+        ctx.enter_instruction(self.operands());
+        let res = self.kind.compile_checked(buf, ctx, &self.arguments);
+        ctx.exit_instruction();
+
+        if let Err(ie) = res {
+            return Err(CompilerError::Instruction(ie, self.from.clone()))
+        }
+
+        // nested instructions are checked first, so the error is on the innermost instruction addressing the cell
+        if let (Some(tape_limit), Some(cell)) = (ctx.tape_limit(), ctx.memory_map().highest_cell()) {
+            if cell as usize >= tape_limit {
+                return Err(CompilerError::TapeLimitExceeded { cell, tape_limit, instruction: self.from.clone() })
+            }
         }
+
+        ctx.add_to_source_map(self.from.slice(), start..buf.len());
+        Ok(())
+    }
+
+    /// Returns the operands along with the aliases used in their expression and how the instruction uses them.
+    fn operands(&self) -> Vec<Operand> {
+        self.arguments.iter().zip(&self.from.arguments)
+            .enumerate()
+            .filter_map(|(i, (argument, parsed))| match (argument, parsed) {
+                (Argument::Operand(value), ParsedArgument::Expression(expression)) => {
+                    let role = if self.kind.addressed_arguments().contains(&i) {
+                        OperandRole::Address
+                    } else if let Some(name) = self.kind.argument_names().get(i) {
+                        OperandRole::MetaArgument(name.clone())
+                    } else {
+                        OperandRole::Value
+                    };
+
+                    Some(Operand { value: *value, aliases: expression.aliases().map(str::to_string).collect(), role })
+                },
+                _ => None,
+            })
+            .collect()
     }
 }
 
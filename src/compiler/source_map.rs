@@ -0,0 +1,109 @@
+//! Tracking of which basm instructions generated which parts of the transpiled brainfuck.
+
+use std::ops::Range;
+
+use crate::{source::SfSlice, utils::FindLnCol as _};
+
+/// Maps ranges of the transpiled brainfuck back to the basm instructions which generated them.
+/// The offsets are in bytes and relative to the unoptimised brainfuck.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SourceMap {
+    entries: Vec<SourceMapEntry>,
+}
+
+/// A basm instruction along with the part of brainfuck it generated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceMapEntry {
+    /// The range of the brainfuck generated by the instruction.
+    /// This includes the brainfuck generated by the instructions nested within it.
+    pub bf_range: Range<usize>,
+    /// The instruction in the basm source.
+    pub instruction: SfSlice,
+    /// The names of the meta-instructions the instruction was inlined from, the outermost first.
+    pub meta_instructions: Vec<String>,
+}
+
+impl SourceMap {
+    /// Creates a new empty [`SourceMap`].
+    pub fn new() -> SourceMap {
+        SourceMap::default()
+    }
+
+    /// Adds an entry to the source map.
+    pub fn push(&mut self, entry: SourceMapEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Returns all the entries of the source map.
+    /// Nested instructions are placed before the instructions containing them.
+    pub fn entries(&self) -> &[SourceMapEntry] {
+        &self.entries
+    }
+
+    /// Returns the innermost instruction which generated the whole of `bf_range`.
+    /// Returns `None` if no instruction covers the range.
+    pub fn find_covering(&self, bf_range: Range<usize>) -> Option<&SourceMapEntry> {
+        self.entries.iter()
+            .filter(|e| e.bf_range.start <= bf_range.start && bf_range.end <= e.bf_range.end)
+            .min_by_key(|e| e.bf_range.len())
+    }
+}
+
+impl SourceMapEntry {
+    /// Returns a short description of the instruction and where it comes from.
+    /// Ex: `COPY a b sp; (Ln 12, Col 5) in COPC`
+    pub fn describe(&self) -> String {
+        let source = self.instruction.source();
+        let (ln, col) = source.byte_find_ln_col(self.instruction.start())
+            .unwrap_or((0, 0));
+
+        // we only keep the first line of instructions spanning over many lines (like WHNE)
+        let text = self.instruction.as_ref().lines().next().unwrap_or_default().trim();
+        let mut desc = format!("{text} (Ln {ln}, Col {col})");
+
+        if !self.meta_instructions.is_empty() {
+            desc.push_str(" in ");
+            desc.push_str(&self.meta_instructions.join(" > "));
+        }
+
+        desc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{source::SourceFile, transpile_with_source_map};
+
+    #[test]
+    fn source_map_covering() {
+        let prog_str = "
+        [@MOVE Asrc Adst] [
+        ADDP Adst Asrc;
+        ]
+
+        [main] [
+        INCR 0 3;
+        MOVE 0 1;
+        OUT 1;
+        ]";
+
+        let sf = SourceFile::from_raw_parts("testfile".into(), prog_str.to_string())
+            .leak();
+        let (bf, source_map) = transpile_with_source_map(sf).unwrap();
+        assert_eq!(bf, "+++[->+<]>.");
+
+        // the loop is generated by the ADDP inside of MOVE
+        let entry = source_map.find_covering(3..9).unwrap();
+        assert_eq!(entry.instruction.as_ref(), "ADDP Adst Asrc;");
+        assert_eq!(entry.meta_instructions, vec!["MOVE".to_string()]);
+        assert_eq!(entry.describe(), "ADDP Adst Asrc; (Ln 3, Col 9) in MOVE");
+
+        let entry = source_map.find_covering(0..3).unwrap();
+        assert_eq!(entry.instruction.as_ref(), "INCR 0 3;");
+        assert!(entry.meta_instructions.is_empty());
+
+        // across two instructions
+        let entry = source_map.find_covering(8..10);
+        assert!(entry.is_none());
+    }
+}
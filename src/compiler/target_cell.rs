@@ -0,0 +1,136 @@
+//! The cells of the machine a program is compiled for.
+
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+/// The type of the cells a program will run on, used to check values fit in the cells
+/// and to generate shorter constants knowing how the cells wrap around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TargetCell {
+    /// Unsigned cells of 8 bits.
+    U8,
+    /// Signed cells of 8 bits.
+    I8,
+    /// Unsigned cells of 16 bits.
+    U16,
+    /// Signed cells of 16 bits.
+    I16,
+    /// Unsigned cells of 32 bits.
+    U32,
+    /// Signed cells of 32 bits.
+    I32,
+}
+
+impl TargetCell {
+    /// All the cell types.
+    pub const ALL: [TargetCell; 6] = [TargetCell::U8, TargetCell::I8, TargetCell::U16, TargetCell::I16, TargetCell::U32, TargetCell::I32];
+
+    /// Returns the cell type of `bits` bits, signed or not, `None` if there is none of this size.
+    pub fn from_bits(bits: u32, signed: bool) -> Option<TargetCell> {
+        TargetCell::ALL.into_iter().find(|cell| cell.bits() == bits && cell.is_signed() == signed)
+    }
+
+    /// The name of the cell type, as used by `--target-cell`.
+    pub fn name(self) -> &'static str {
+        match self {
+            TargetCell::U8 => "u8",
+            TargetCell::I8 => "i8",
+            TargetCell::U16 => "u16",
+            TargetCell::I16 => "i16",
+            TargetCell::U32 => "u32",
+            TargetCell::I32 => "i32",
+        }
+    }
+
+    /// The size of the cells in bits.
+    pub fn bits(self) -> u32 {
+        match self {
+            TargetCell::U8 | TargetCell::I8 => 8,
+            TargetCell::U16 | TargetCell::I16 => 16,
+            TargetCell::U32 | TargetCell::I32 => 32,
+        }
+    }
+
+    /// Returns `true` if the cells hold signed numbers.
+    pub fn is_signed(self) -> bool {
+        matches!(self, TargetCell::I8 | TargetCell::I16 | TargetCell::I32)
+    }
+
+    /// The number of values a cell can hold, values are the same modulo it.
+    pub fn modulus(self) -> u64 {
+        1 << self.bits()
+    }
+
+    /// The smallest value a cell can hold.
+    pub fn min(self) -> i64 {
+        if self.is_signed() {
+            -(1 << (self.bits() - 1))
+        } else {
+            0
+        }
+    }
+
+    /// The largest value a cell can hold.
+    pub fn max(self) -> i64 {
+        if self.is_signed() {
+            (1 << (self.bits() - 1)) - 1
+        } else {
+            (1 << self.bits()) - 1
+        }
+    }
+
+    /// Returns `true` if a cell can hold `value` without wrapping around.
+    pub fn contains(self, value: i64) -> bool {
+        (self.min()..=self.max()).contains(&value)
+    }
+}
+
+impl fmt::Display for TargetCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for TargetCell {
+    type Err = UnknownTargetCellError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TargetCell::ALL.into_iter()
+            .find(|cell| cell.name() == s)
+            .ok_or_else(|| UnknownTargetCellError { name: s.to_string() })
+    }
+}
+
+/// The name of a cell type does not match any cell type.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("unknown cell type `{name}`, expected one of {}", TargetCell::ALL.map(TargetCell::name).join(", "))]
+pub struct UnknownTargetCellError {
+    /// The name which did not match
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_cell_ranges() {
+        assert_eq!((TargetCell::U8.min(), TargetCell::U8.max()), (0, 255));
+        assert_eq!((TargetCell::I8.min(), TargetCell::I8.max()), (-128, 127));
+        assert_eq!((TargetCell::I32.min(), TargetCell::I32.max()), (i32::MIN as i64, i32::MAX as i64));
+        assert_eq!(TargetCell::U16.modulus(), 65536);
+
+        assert!(TargetCell::U8.contains(255));
+        assert!(!TargetCell::U8.contains(256));
+        assert!(!TargetCell::U8.contains(-1));
+        assert!(TargetCell::I8.contains(-128));
+        assert!(!TargetCell::I8.contains(128));
+
+        assert_eq!(TargetCell::from_bits(16, true), Some(TargetCell::I16));
+        assert_eq!(TargetCell::from_bits(64, false), None);
+
+        assert_eq!("i16".parse(), Ok(TargetCell::I16));
+        assert!("u64".parse::<TargetCell>().is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! The unbounded cell type, see [`InterpreterBuilder::with_big_int`](super::InterpreterBuilder::with_big_int).
+
+use std::{fmt::{self, Debug}, ops::{Add, Div, Mul, Rem, Sub}, str::FromStr};
+
+use num::{bigint::ParseBigIntError, traits::{ConstZero, SaturatingAdd, SaturatingSub, WrappingAdd, WrappingMul, WrappingSub}, BigInt, CheckedAdd, CheckedSub, FromPrimitive, Num, NumCast, One, ToPrimitive, Zero};
+
+/// A cell containing a signed integer of arbitrary size.
+/// Since it can never overflow, the wrapping, checked and saturating operations are the same as the regular ones.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BigCell(pub BigInt);
+
+impl Debug for BigCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for BigCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<i8> for BigCell {
+    fn from(value: i8) -> Self {
+        BigCell(BigInt::from(value))
+    }
+}
+
+impl FromStr for BigCell {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigInt::from_str(s).map(BigCell)
+    }
+}
+
+macro_rules! impl_binary_operation {
+    ($trait:ident, $method:ident) => {
+        impl $trait for BigCell {
+            type Output = BigCell;
+
+            fn $method(self, rhs: BigCell) -> BigCell {
+                BigCell(self.0.$method(rhs.0))
+            }
+        }
+    };
+}
+
+impl_binary_operation!(Add, add);
+impl_binary_operation!(Sub, sub);
+impl_binary_operation!(Mul, mul);
+impl_binary_operation!(Div, div);
+impl_binary_operation!(Rem, rem);
+
+impl Zero for BigCell {
+    fn zero() -> Self {
+        BigCell(BigInt::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl ConstZero for BigCell {
+    const ZERO: Self = BigCell(BigInt::ZERO);
+}
+
+impl One for BigCell {
+    fn one() -> Self {
+        BigCell(BigInt::one())
+    }
+}
+
+impl Num for BigCell {
+    type FromStrRadixErr = ParseBigIntError;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        BigInt::from_str_radix(str, radix).map(BigCell)
+    }
+}
+
+impl ToPrimitive for BigCell {
+    fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        self.0.to_i128()
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        self.0.to_u128()
+    }
+}
+
+impl NumCast for BigCell {
+    fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+        n.to_i128().and_then(BigInt::from_i128)
+            .or_else(|| n.to_u128().and_then(BigInt::from_u128))
+            .map(BigCell)
+    }
+}
+
+macro_rules! impl_infallible_operation {
+    ($trait:ident, $method:ident, $operation:tt) => {
+        impl $trait for BigCell {
+            fn $method(&self, v: &Self) -> Self {
+                BigCell(&self.0 $operation &v.0)
+            }
+        }
+    };
+}
+
+impl_infallible_operation!(WrappingAdd, wrapping_add, +);
+impl_infallible_operation!(WrappingSub, wrapping_sub, -);
+impl_infallible_operation!(WrappingMul, wrapping_mul, *);
+impl_infallible_operation!(SaturatingAdd, saturating_add, +);
+impl_infallible_operation!(SaturatingSub, saturating_sub, -);
+
+impl CheckedAdd for BigCell {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        Some(BigCell(&self.0 + &v.0))
+    }
+}
+
+impl CheckedSub for BigCell {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        Some(BigCell(&self.0 - &v.0))
+    }
+}
@@ -0,0 +1,338 @@
+//! The fast execution engine.
+//! Compiles the standard byte codes into higher level byte codes which do more in a single step.
+//! See [`InterpreterBuilder::with_fast_engine`](super::InterpreterBuilder::with_fast_engine).
+
+use std::fmt::Debug;
+
+use super::{ByteCode, CellKind, Interpreter, InterpreterError, NumOpsPlus};
+
+/// Converts standard byte codes into fast engine byte codes.
+/// Also returns the offset in bytes of the first operator of each new byte code in the brainfuck source.
+pub(super) fn to_fast_bytecode(instructions: &[ByteCode], offsets: &[usize]) -> (Vec<ByteCode>, Vec<usize>) {
+    let mut fast = Vec::new();
+
+    let mut idx = 0;
+    while idx < instructions.len() {
+        let offset = offsets[idx];
+
+        match &instructions[idx] {
+            ByteCode::LeftBracket(_) => {
+                if let Some((loop_codes, lenght)) = recognise_loop(&instructions[idx..]) {
+                    fast.extend(loop_codes.into_iter().map(|bc| (bc, offset)));
+                    idx += lenght;
+                } else {
+                    fast.push((ByteCode::LeftBracket(None), offset));
+                    idx += 1;
+                }
+            },
+            bc if is_foldable(bc) => {
+                let lenght = instructions[idx..].iter()
+                    .take_while(|bc| is_foldable(bc))
+                    .count();
+                let folded = fold(&instructions[idx..idx+lenght]);
+
+                fast.extend(folded.to_bytecode().into_iter().map(|bc| (bc, offset)));
+                idx += lenght;
+            },
+            ByteCode::RightBracket(_) => {
+                fast.push((ByteCode::RightBracket(None), offset));
+                idx += 1;
+            },
+            bc => {
+                fast.push((bc.clone(), offset));
+                idx += 1;
+            },
+        }
+    }
+
+    fast.into_iter().unzip()
+}
+
+/// Returns `true` if the byte code only does arithmetic or pointer movement.
+fn is_foldable(bc: &ByteCode) -> bool {
+    matches!(bc, ByteCode::PointerAdd(_) | ByteCode::PointerSub(_) | ByteCode::Add(_) | ByteCode::Sub(_))
+}
+
+/// The effect of a run of arithmetic and pointer movement byte codes.
+#[derive(Debug, Default)]
+struct Folded {
+    /// The total amount added to each touched cell, relative to the starting tape pointer.
+    /// In the order the cells were first touched.
+    deltas: Vec<(isize, i64)>,
+    /// The displacement of the tape pointer at the end of the run.
+    displacement: isize,
+}
+
+impl Folded {
+    /// Returns the delta of the cell at `offset`, 0 if it was not touched.
+    fn delta_at(&self, offset: isize) -> i64 {
+        self.deltas.iter()
+            .find(|(o, _)| *o == offset)
+            .map_or(0, |(_, d)| *d)
+    }
+
+    /// Converts the effect into `AddAt`s and a `Move`.
+    fn to_bytecode(&self) -> Vec<ByteCode> {
+        let mut codes = Vec::new();
+
+        for (offset, delta) in &self.deltas {
+            // touched cells with a null delta are still added to, so that they are allocated like in the standard engine
+            if *delta == 0 {
+                codes.push(ByteCode::AddAt { offset: *offset, amount: 0 });
+            }
+
+            let mut remaining = *delta;
+            while remaining != 0 {
+                let amount = remaining.clamp(-i64::from(i8::MAX), i64::from(i8::MAX));
+                remaining -= amount;
+                codes.push(ByteCode::AddAt { offset: *offset, amount: amount as i8 });
+            }
+        }
+
+        if self.displacement != 0 {
+            codes.push(ByteCode::Move(self.displacement));
+        }
+
+        codes
+    }
+}
+
+/// Computes the effect of a run of arithmetic and pointer movement byte codes.
+fn fold(run: &[ByteCode]) -> Folded {
+    let mut folded = Folded::default();
+
+    for bc in run {
+        let delta = match bc {
+            ByteCode::PointerAdd(n) => {
+                folded.displacement += *n as isize;
+                continue
+            },
+            ByteCode::PointerSub(n) => {
+                folded.displacement -= *n as isize;
+                continue
+            },
+            ByteCode::Add(n) => i64::from(*n),
+            ByteCode::Sub(n) => -i64::from(*n),
+            _ => unreachable!("only foldable byte codes should be folded"),
+        };
+
+        let displacement = folded.displacement;
+        if let Some((_, d)) = folded.deltas.iter_mut().find(|(o, _)| *o == displacement) {
+            *d += delta;
+        } else {
+            folded.deltas.push((displacement, delta));
+        }
+    }
+
+    folded
+}
+
+/// Tries to convert the loop starting at the start of `instructions` into fast byte codes.
+/// Returns the byte codes and the number of standard byte codes they replace.
+fn recognise_loop(instructions: &[ByteCode]) -> Option<(Vec<ByteCode>, usize)> {
+    // we only handle loops without nested loops or io
+    let body_lenght = instructions[1..].iter()
+        .take_while(|bc| is_foldable(bc))
+        .count();
+    let ByteCode::RightBracket(_) = instructions.get(body_lenght+1)? else {
+        return None
+    };
+
+    let folded = fold(&instructions[1..=body_lenght]);
+    let lenght = body_lenght + 2;
+
+    // scan loop, ex: `[>>]`
+    if folded.deltas.is_empty() && folded.displacement != 0 {
+        return Some((vec![ByteCode::Scan(folded.displacement)], lenght))
+    }
+
+    if folded.displacement != 0 {
+        return None
+    }
+
+    // clear loop, ex: `[-]` or `[+]`
+    if folded.deltas.len() == 1 && folded.delta_at(0).abs() == 1 {
+        return Some((vec![ByteCode::Clear(folded.delta_at(0) as i8)], lenght))
+    }
+
+    // multiplication loop, ex: `[->+++>-<<]`
+    // the loop has to run exactly as many times as the value of the cell, so it has to decrement by one
+    if folded.delta_at(0) != -1 {
+        return None
+    }
+
+    let mut codes = Vec::new();
+    for (offset, delta) in &folded.deltas {
+        if *offset == 0 {
+            continue
+        }
+
+        let factor = i8::try_from(*delta).ok()
+            .filter(|f| *f != i8::MIN)?;
+        codes.push(ByteCode::MulAdd { offset: *offset, factor });
+    }
+    codes.push(ByteCode::Clear(-1));
+
+    Some((codes, lenght))
+}
+
+// Execution of the fast byte codes.
+#[allow(private_bounds)]
+impl<T: NumOpsPlus + TryFrom<i8>> Interpreter<T>
+where <T as TryFrom<i8>>::Error: Debug {
+    #[inline]
+    pub(super) fn add_at(&mut self, offset: isize, amount: i8) -> Result<(), InterpreterError> {
+        let index = self.index_at(offset)?;
+        let amount_t = T::try_from(amount.abs())
+            .expect("Since the amount is i8 and positive, it should be always be able to be stored as T");
+
+        let cell = self.get_mut_cell_at_or_insert_default(index)?;
+        if amount < 0 {
+            *cell = cell.wrapping_sub(&amount_t);
+        } else {
+            *cell = cell.wrapping_add(&amount_t);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn move_pointer(&mut self, displacement: isize) -> Result<(), InterpreterError> {
+        self.tape_pointer = self.index_at(displacement)?;
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn clear(&mut self, step: i8) -> Result<bool, InterpreterError> {
+        let value = self.get_mut_cell_or_insert_default()?.clone();
+        if self.never_reaches_zero(&value, step) {
+            return Ok(false)
+        }
+
+        *self.get_mut_cell_or_insert_default()? = T::ZERO;
+
+        Ok(true)
+    }
+
+    #[inline]
+    pub(super) fn mul_add(&mut self, offset: isize, factor: i8) -> Result<(), InterpreterError> {
+        let value = self.get_mut_cell_or_insert_default()?.clone();
+        // the loop never ends, the `Clear` after it repeats forever
+        if value == T::ZERO || self.never_reaches_zero(&value, -1) {
+            return Ok(())
+        }
+
+        let index = self.index_at(offset)?;
+        let factor_t = T::try_from(factor.abs())
+            .expect("Since the factor is i8 and positive, it should be always be able to be stored as T");
+        let product = value.wrapping_mul(&factor_t);
+
+        let cell = self.get_mut_cell_at_or_insert_default(index)?;
+        if factor < 0 {
+            *cell = cell.wrapping_sub(&product);
+        } else {
+            *cell = cell.wrapping_add(&product);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn scan(&mut self, displacement: isize) -> Result<(), InterpreterError> {
+        while *self.get_mut_cell_or_insert_default()? != T::ZERO {
+            self.tape_pointer = self.index_at(displacement)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if a loop adding `step` to the cell each iteration never ends starting from `value`.
+    /// Only big cells going away from 0 never reach it, the others wrap around.
+    #[inline]
+    fn never_reaches_zero(&self, value: &T, step: i8) -> bool {
+        self.config.cell_kind == CellKind::Big
+            && if step < 0 { *value < T::ZERO } else { *value > T::ZERO }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{interpreter::{brainfuck_to_bytecode, InterpreterBuilder}, source::SourceFile, transpile};
+
+    use super::*;
+
+    fn fast_bytecode(bf: &str) -> Vec<ByteCode> {
+        let (instructions, offsets) = brainfuck_to_bytecode(bf);
+        to_fast_bytecode(&instructions, &offsets).0
+    }
+
+    #[test]
+    fn fast_bytecode_recognition() {
+        assert_eq!(fast_bytecode("[-]"), vec![ByteCode::Clear(-1)]);
+        assert_eq!(fast_bytecode("[>>]"), vec![ByteCode::Scan(2)]);
+        assert_eq!(
+            fast_bytecode("[->+++>-<<]"),
+            vec![
+                ByteCode::MulAdd { offset: 1, factor: 3 },
+                ByteCode::MulAdd { offset: 2, factor: -1 },
+                ByteCode::Clear(-1),
+            ],
+        );
+        assert_eq!(
+            fast_bytecode(">+>--<<<+-"),
+            vec![
+                ByteCode::AddAt { offset: 1, amount: 1 },
+                ByteCode::AddAt { offset: 2, amount: -2 },
+                ByteCode::AddAt { offset: -1, amount: 0 },
+                ByteCode::Move(-1),
+            ],
+        );
+        // not a multiplication loop, the cell is decremented by 2
+        assert_eq!(fast_bytecode("[-->+<]")[0], ByteCode::LeftBracket(None));
+        // not a scan loop, there is io
+        assert_eq!(fast_bytecode("[.>]")[0], ByteCode::LeftBracket(None));
+    }
+
+    /// Runs `bf` on both engines and checks that they end up in the same state with the same output.
+    fn assert_engines_agree(bf: &str, input: &str) {
+        let run = |fast: bool| {
+            let builder = InterpreterBuilder::new(bf)
+                .with_input_as_number()
+                .with_output_as_number()
+                .with_bulk_input()
+                .with_output_capture();
+            let builder = if fast { builder.with_fast_engine() } else { builder.with_standard_engine() };
+
+            let mut inter = builder.finish();
+            if !input.is_empty() {
+                assert!(inter.add_to_input_buffer(input));
+            }
+            let result = inter.complete();
+            (result, inter.tape().downcast_ref::<Vec<u8>>().unwrap().clone(), inter.tape_pointer(), inter.captured_output().unwrap().to_string())
+        };
+
+        assert_eq!(run(false), run(true));
+    }
+
+    #[test]
+    fn fast_engine_matches_standard_engine() {
+        assert_engines_agree(include_str!("../../test-resources/fib.bf"), "");
+        assert_engines_agree("++++++++++++>++++<[->>+<<]>[>[->+<<<+>>]>[-<+>]<<-]", "");
+        assert_engines_agree("+++++[>+++++<-]>[>++<-]>>+++[<]>.>.", "");
+        assert_engines_agree(",[>+>+<<-]>[<+>-]>[.-]<<.", "12");
+        assert_engines_agree("+[-<+]", "");
+
+        let programs = [
+            include_str!("../../test-resources/fib.basm"),
+            include_str!("../../test-resources/hello-world.basm"),
+            include_str!("../../test-resources/hello-world-better.basm"),
+            include_str!("../../test-resources/custom-conditionals.basm"),
+        ];
+        for program in programs {
+            let sf = SourceFile::from_raw_parts("testfile".into(), program.to_string()).leak();
+            let bf = transpile(sf).unwrap();
+            assert_engines_agree(&bf, "");
+        }
+    }
+}
@@ -0,0 +1,2005 @@
+//! A fairly naive implementation of a brainfuck interpreter.
+
+use std::{any::Any, collections::VecDeque, fmt::Debug, io::{Read, Write}, str::FromStr, time::{Duration, Instant}};
+
+use colored::Colorize as _;
+use num::{traits::{ConstZero, SaturatingAdd, SaturatingSub, WrappingAdd, WrappingMul, WrappingSub}, CheckedAdd, CheckedSub, Num, NumCast};
+use thiserror::Error;
+
+mod report;
+pub use report::RunReport;
+mod profile;
+pub use profile::{Profile, ByteCodeProfile, LoopProfile};
+mod trace;
+pub use trace::{TraceEntry, TraceFilter, TraceReadError, read_trace, TRACE_HEADER};
+use trace::TraceRecorder;
+mod streams;
+mod state;
+mod fast;
+mod big_cell;
+pub use big_cell::BigCell;
+mod snapshot;
+pub use snapshot::{Snapshot, SnapshotError};
+pub use streams::SharedBuffer;
+use streams::{InputStream, OutputStream, Streams};
+
+/// Number of steps between each check of the clock when a timeout is set.
+const TIMEOUT_CHECK_INTERVAL: usize = 1024;
+
+/// Interpreter for brainfuck programs.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Interpreter<T> {
+    config: InterpreterConfig,
+    tape: Vec<T>,
+    instructions: Vec<ByteCode>,
+    /// The offset in bytes of each byte code in the brainfuck source.
+    offsets: Vec<usize>,
+    tape_pointer: usize,
+    /// The index in `tape` of the cell the program started on.
+    /// Only changes with a bidirectional tape, when cells are inserted at the start of the tape.
+    tape_origin: usize,
+    instruction_pointer: usize,
+    /// The number of byte codes executed so far.
+    executed_operations: usize,
+    /// The moment the first byte code was executed, used to enforce the timeout.
+    started_at: Option<Instant>,
+    /// The time spent waiting for input since `started_at`, which does not count towards the timeout.
+    input_wait: Duration,
+    /// The number of times each byte code was executed, if profiling is enabled.
+    execution_counts: Option<Vec<u64>>,
+    /// Where the executed steps are written to, if tracing is enabled.
+    trace: Option<TraceRecorder>,
+
+    input: InputStream,
+    output: OutputStream,
+    input_buffer: VecDeque<T>,
+    /// A copy of the output of the program, if output capture is enabled.
+    captured_output: Option<String>,
+}
+
+// Generic, non-cell related methods.
+impl<T> Interpreter<T> {
+    fn new(instructions: Vec<ByteCode>, offsets: Vec<usize>, config: InterpreterConfig, streams: Streams) -> Interpreter<T> {
+        let execution_counts = config.profiling.then(|| vec![0; instructions.len()]);
+        let captured_output = config.capture_output.then(String::new);
+
+        let mut interpreter = Interpreter {
+            config,
+            instructions,
+            offsets,
+            tape: Vec::new(),
+            tape_pointer: 0,
+            tape_origin: 0,
+            instruction_pointer: 0,
+            executed_operations: 0,
+            started_at: None,
+            input_wait: Duration::ZERO,
+            execution_counts,
+            trace: streams.trace,
+
+            input: streams.input,
+            output: streams.output,
+            input_buffer: VecDeque::new(),
+            captured_output,
+        };
+
+        interpreter.calculate_bracket_matches();
+
+        interpreter
+    }
+
+    /// Returns the index of the cell at `index` on the tape, relative to the cell the program started on.
+    fn logical_index(&self, index: usize) -> isize {
+        index as isize - self.tape_origin as isize
+    }
+
+    /// Creates a builder for [`Interpreter`].
+    pub fn builder(instructions: &str) -> InterpreterBuilder {
+        InterpreterBuilder::new(instructions)
+    }
+
+    /// Gives a string copying the output created by the program.
+    /// Returns `None` if the interpreter was not configured to capture its output.
+    pub fn captured_output(&self) -> Option<&str> {
+        self.captured_output.as_deref()
+    }
+
+    /// Finds and sets the matches for all brackets bytecodes.
+    fn calculate_bracket_matches(&mut self) {
+        // indexes of the left brackets which are still waiting for their match
+        let mut opened = Vec::new();
+
+        for idx in 0..self.instructions.len() {
+            match self.instructions[idx] {
+                ByteCode::LeftBracket(_) => opened.push(idx),
+                ByteCode::RightBracket(_) => {
+                    // unmatched right brackets stay `None`
+                    let Some(left_idx) = opened.pop() else {
+                        continue
+                    };
+
+                    self.instructions[left_idx] = ByteCode::LeftBracket(Some(idx));
+                    self.instructions[idx] = ByteCode::RightBracket(Some(left_idx));
+                },
+                _ => (),
+            }
+        }
+
+        // the left brackets remaining in `opened` are unmatched and stay `None`
+    }
+}
+
+// Utility methods for dealing with cells.
+#[allow(private_bounds)]
+impl<T: NumOpsPlus + TryFrom<i8>> Interpreter<T>
+where <T as TryFrom<i8>>::Error: Debug {
+    /// Mutably gets the cell currently pointed to by the tape pointer.
+    /// If the pointer outside of the tape, inserts `T::default` until the array is big enough to be index at that location.
+    #[inline]
+    fn get_mut_cell_or_insert_default(&mut self) -> Result<&mut T, InterpreterError> {
+        self.get_mut_cell_at_or_insert_default(self.tape_pointer)
+    }
+
+    /// Mutably gets the cell at `index`.
+    /// If `index` is outside of the tape, inserts `T::default` until the array is big enough to be index at that location.
+    fn get_mut_cell_at_or_insert_default(&mut self, index: usize) -> Result<&mut T, InterpreterError> {
+        unsafe {
+            if self.tape.len() > index {
+                return Ok(self.tape.get_unchecked_mut(index));
+            }
+        }
+
+        // if we get here then the tape is not long enough for our index
+        let lenght_limit = self.config.lenght_limit.unwrap_or(usize::MAX);
+        if index+1 > lenght_limit {
+            return Err(InterpreterError::TapeLimitExceded { limit: lenght_limit, tried: index+1 });
+        }
+        
+        let extention = (index+1) - self.tape.len();
+        self.tape.extend(vec![T::default(); extention]);
+
+        unsafe {
+            Ok(self.tape.get_unchecked_mut(index))
+        }
+    }
+
+    /// Prints a dump of the tape state and pointer state to stdout.
+    fn print_dump(&self) {
+        println!("{}", "-- TAPE STATE STRING --".yellow().underline().bold());
+        for (i, cell) in self.tape.iter().enumerate() {
+            let ch = char::from_u32(cell.to_u32().unwrap_or(65_533)).unwrap_or('�');
+            let ch_str = if ch == '\n' {
+                "\\n".to_string()
+            } else if ch.is_control() {
+                "�".to_string()
+            } else {
+                ch.to_string()
+            };
+
+            println!("{} {ch_str}", format!("{}:", self.logical_index(i)).to_string().black());
+        }
+        println!();
+
+        println!("{}", "-- TAPE STATE NUMERIC --".green().underline().bold());
+        for (i, cell) in self.tape.iter().enumerate() {
+            println!("{} {cell:?}", format!("{}:", self.logical_index(i)).to_string().black());
+        }
+        println!();
+
+        println!("{}: {}", "LAST VALID TAPE POINTER POSITION".blue().underline().bold(), self.logical_index(self.tape_pointer));
+    }
+
+    /// Returns an error if executing one more byte code would go over the step limit or the timeout.
+    fn check_budgets(&mut self) -> Result<(), InterpreterError> {
+        if let Some(limit) = self.config.step_limit {
+            if self.executed_operations >= limit {
+                return Err(InterpreterError::StepLimitExceded {
+                    limit,
+                    steps: self.executed_operations,
+                    instruction_pointer: self.instruction_pointer,
+                })
+            }
+        }
+
+        if let Some(timeout) = self.config.timeout {
+            // the clock starts with the first byte code executed
+            let started_at = *self.started_at.get_or_insert_with(Instant::now);
+
+            // reading the clock is slow, so we only do it every so often
+            if self.executed_operations.is_multiple_of(TIMEOUT_CHECK_INTERVAL)
+                && started_at.elapsed().saturating_sub(self.input_wait) >= timeout {
+                return Err(InterpreterError::TimedOut {
+                    timeout,
+                    steps: self.executed_operations,
+                    instruction_pointer: self.instruction_pointer,
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a [`RunReport`] of the current state of the interpreter.
+    /// `error` is the error which stopped the execution, if there was one.
+    fn report(&self, error: Option<InterpreterError>) -> RunReport {
+        RunReport {
+            tape: self.tape.iter().map(|c| format!("{c:?}")).collect(),
+            tape_pointer: self.logical_index(self.tape_pointer),
+            tape_origin: self.tape_origin,
+            cell_kind: self.config.cell_kind.name().to_string(),
+            executed_operations: self.executed_operations,
+            error,
+        }
+    }
+
+    /// Converts a string to a cell value. Behaviour changes based on whether input is number or character.
+    /// Returns `None` if `string` could not be parsed into a value.
+    fn string_to_value(&self, string: &str) -> Option<T> {
+        if self.config.input_as_number {
+            if let Ok(v) = string.trim().parse() {
+                Some(v)
+            } else {
+                None
+            }
+        } else {
+            let ch = string.chars().next()?;
+            T::from(ch as u32)
+        }
+    }
+
+    /// Adds items to `Interpreter::input_buffer`.
+    /// Does nothing if there the interpreter is configurated to not use bulked input.
+    /// Returns `true` if the addition to the buffer was successful.
+    fn add_to_input_buffer(&mut self, string: &str) -> bool {
+        if !self.config.bulk_input {
+            return false;
+        }
+
+        let mut values = {
+            let elements = if self.config.input_as_number {
+                string.trim().split(' ').map(|e| e.to_string()).collect::<Vec<_>>()
+            } else {
+                // it would be nice if there was a method returning slices to these chars
+                string.chars().map(|c| c.to_string()).collect()
+            };
+
+            let maybe_values = elements.iter().map(|e| self.string_to_value(&e));
+            if maybe_values.clone().find(|e| e.is_none()).is_some() {
+                return false;
+            }
+            let values = maybe_values.filter_map(|e| e).collect::<VecDeque<_>>();
+
+            values
+        };
+
+        self.input_buffer.append(&mut values);
+
+        true
+    }
+}
+
+#[allow(private_bounds)] // these are not meant to be used outside here
+impl<T: NumOpsPlus + TryFrom<i8>> Interpreter<T>
+where <T as TryFrom<i8>>::Error: Debug {
+    #[inline]
+    fn increment(&mut self, recurence: i8) -> Result<(), InterpreterError> {
+        let recurence_t = T::try_from(recurence)
+            .expect("Since recurence is i8 and should be positive, it should be always be able to be stored as T");
+
+        let overflow_behaviour = self.config.overflow_behaviour.clone();
+
+        let value = self.get_mut_cell_or_insert_default()?;
+        match overflow_behaviour {
+            OverflowBehaviour::Abort => {
+                if let Some(nval) = value.checked_add(&recurence_t) {
+                    *value = nval;
+                } else {
+                    return Err(InterpreterError::AbortedDueToOverflow { at: self.tape_pointer })
+                }
+            },
+            OverflowBehaviour::Saturate => *value = value.saturating_add(&recurence_t),
+            OverflowBehaviour::Wrap => *value = value.wrapping_add(&recurence_t),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn decrement(&mut self, recurence: i8) -> Result<(), InterpreterError> {
+        let recurence_t = T::try_from(recurence)
+            .expect("Since recurence is i8 and should be positive, it should be always be able to be stored as T");
+
+        let overflow_behaviour = self.config.overflow_behaviour.clone();
+
+        let value = self.get_mut_cell_or_insert_default()?;
+        match overflow_behaviour {
+            OverflowBehaviour::Abort => {
+                if let Some(nval) = value.checked_sub(&recurence_t) {
+                    *value = nval;
+                } else {
+                    return Err(InterpreterError::AbortedDueToOverflow { at: self.tape_pointer })
+                }
+            },
+            OverflowBehaviour::Saturate => *value = value.saturating_sub(&recurence_t),
+            OverflowBehaviour::Wrap => *value = value.wrapping_sub(&recurence_t),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn pointer_increment(&mut self, recurence: i8) -> Result<(), InterpreterError> {
+        self.tape_pointer = self.index_at(recurence as isize)?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn pointer_decrement(&mut self, recurence: i8) -> Result<(), InterpreterError> {
+        self.tape_pointer = self.index_at(-(recurence as isize))?;
+
+        Ok(())
+    }
+
+    /// Returns the index of the cell at `offset` from the tape pointer, according to the tape mode.
+    /// With a bidirectional tape, cells may get inserted at the start of the tape, moving the tape pointer.
+    #[inline]
+    fn index_at(&mut self, offset: isize) -> Result<usize, InterpreterError> {
+        match self.config.tape_mode {
+            TapeMode::RightInfinite => self.tape_pointer.checked_add_signed(offset)
+                .ok_or(InterpreterError::TapePointerOob),
+            TapeMode::Circular(lenght) => {
+                let index = (self.tape_pointer as i128 + offset as i128).rem_euclid(lenght as i128);
+                Ok(index as usize)
+            },
+            TapeMode::Bidirectional => {
+                if let Some(index) = self.tape_pointer.checked_add_signed(offset) {
+                    return Ok(index)
+                }
+
+                self.grow_left(offset.unsigned_abs() - self.tape_pointer)?;
+                Ok(self.tape_pointer.checked_add_signed(offset).expect("the tape was grown to contain the index"))
+            },
+        }
+    }
+
+    /// Inserts at least `missing` cells at the start of the tape.
+    fn grow_left(&mut self, missing: usize) -> Result<(), InterpreterError> {
+        let lenght_limit = self.config.lenght_limit.unwrap_or(usize::MAX);
+        let tried = self.tape.len().saturating_add(missing);
+        if tried > lenght_limit {
+            return Err(InterpreterError::TapeLimitExceded { limit: lenght_limit, tried });
+        }
+
+        // we grow by as much as the tape already contains, so that programs going left don't move the tape all the time
+        let growth = missing.max(self.tape.len())
+            .min(lenght_limit - self.tape.len());
+        self.tape.splice(0..0, std::iter::repeat_n(T::default(), growth));
+        self.tape_pointer += growth;
+        self.tape_origin += growth;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn input(&mut self) -> Result<(), InterpreterError> {
+        if let Some(bulk) = self.input_buffer.pop_front() {
+            let cell = self.get_mut_cell_or_insert_default()?;
+            *cell = bulk; 
+
+            return Ok(())
+        }
+
+        // waiting for the input does not count towards the timeout
+        let reading_since = self.config.timeout.map(|_| Instant::now());
+        let value = if self.config.streamed_input {
+            self.read_streamed_value()
+        } else {
+            Ok(self.ask_value())
+        };
+        if let Some(reading_since) = reading_since {
+            self.input_wait += reading_since.elapsed();
+        }
+
+        let Some(value) = value? else {
+            return self.end_of_input()
+        };
+
+        let cell = self.get_mut_cell_or_insert_default()?;
+        *cell = value;
+
+        Ok(())
+    }
+
+    /// Prompts the user for an input until a valid value is given.
+    /// Returns `None` if the input was closed.
+    fn ask_value(&mut self) -> Option<T> {
+        loop {
+            // the prompt is not part of the output of the program, so it goes to stderr and is not captured
+            eprint!("\n?: ");
+            let string = self.input.read_line()?;
+            if string.is_empty() {
+                continue
+            }
+
+            let (value, rest) = if !self.config.input_as_number {
+                let (first, rest) = string.split_at(1);
+                let value = self.string_to_value(first);
+                if let Some(value) = value {
+                    (value, rest)
+                } else {
+                    continue
+                }
+            } else {
+                let (first, rest) = string.split_once(' ').unwrap_or((&string, ""));
+                let value = self.string_to_value(first);
+                if let Some(value) = value {
+                    (value, rest)
+                } else {
+                    continue
+                }
+            };
+
+            if !self.add_to_input_buffer(rest) && self.config.bulk_input {
+                continue
+            }
+            
+            break Some(value);
+        }
+    }
+
+    /// Reads the next value from the input without prompting.
+    /// Character input takes a single byte, number input takes the next whitespace separated word.
+    /// Returns `None` if the input reached its end.
+    fn read_streamed_value(&mut self) -> Result<Option<T>, InterpreterError> {
+        if !self.config.input_as_number {
+            // bytes above 127 wrap around in `i8` cells
+            return Ok(self.input.read_byte().map(|b| T::from(b).or_else(|| T::from(b as i8)).expect("all cell types can contain a byte")))
+        }
+
+        let mut word = Vec::new();
+        while let Some(byte) = self.input.read_byte() {
+            if !byte.is_ascii_whitespace() {
+                word.push(byte);
+            } else if !word.is_empty() {
+                break
+            }
+        }
+
+        if word.is_empty() {
+            return Ok(None)
+        }
+
+        let word = String::from_utf8_lossy(&word);
+        self.string_to_value(&word)
+            .map(Some)
+            .ok_or_else(|| InterpreterError::InvalidInput { input: word.to_string() })
+    }
+
+    /// Applies the end of input behaviour to the current cell.
+    fn end_of_input(&mut self) -> Result<(), InterpreterError> {
+        let value = match self.config.eof_behaviour {
+            EofBehaviour::Unchanged => return Ok(()),
+            EofBehaviour::Zero => T::ZERO,
+            EofBehaviour::Max => T::ZERO.wrapping_sub(&T::one()),
+            EofBehaviour::Abort => return Err(InterpreterError::EndOfInput),
+        };
+
+        let cell = self.get_mut_cell_or_insert_default()?;
+        *cell = value;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn output(&mut self) -> Result<(), InterpreterError> {
+        let value = self.get_mut_cell_or_insert_default()?.clone();
+        let string = if self.config.output_as_number {
+            format!("{value:?} ")
+        } else {
+            let ch = char::from_u32(value.to_u32().unwrap_or(65_533)).unwrap_or('�');
+            ch.to_string()
+        };
+
+        if let Some(captured) = &mut self.captured_output {
+            captured.push_str(&string);
+        }
+
+        self.output.write_str(&string)
+    }
+
+    #[inline]
+    fn left_bracket(&mut self, matching_idx: Option<usize>) -> Result<(), InterpreterError> {
+        if *self.get_mut_cell_or_insert_default()? != T::ZERO {
+            return Ok(())
+        }
+
+        if matching_idx.is_none() {
+            return Err(InterpreterError::UnmatchedBracket);
+        } else {
+            self.instruction_pointer = matching_idx.unwrap();
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn right_bracket(&mut self, matching_idx: Option<usize>) -> Result<(), InterpreterError> {
+        if *self.get_mut_cell_or_insert_default()? == T::ZERO {
+            return Ok(())
+        }
+
+        if matching_idx.is_none() {
+            return Err(InterpreterError::UnmatchedBracket);
+        } else {
+            self.instruction_pointer = matching_idx.unwrap();
+        }
+
+        Ok(())
+    }
+}
+
+/// Trait for [`Interpreter`] behaviour.
+pub trait InterpreterTrait {
+    /// Advances for one instruction.
+    /// Returns `false` if the pointer is out of the instruction list. (aka) it is done.
+    fn advance(&mut self) -> Result<bool, InterpreterError>;
+
+    /// Runs until it either runs into an error or completes the program.
+    fn complete(&mut self) -> Result<(), InterpreterError> {
+        while self.advance()? {}
+
+        Ok(())
+    }
+
+    /// References the tape.
+    /// 
+    /// # **THIS IS A REFERENCE TO `Vec<T>`**.
+    /// Prefer [`InterpreterTrait::cell_i64`] and [`InterpreterTrait::cell_u64`] which do not need downcasting.
+    // I HATE ANY, WHY IS IT SO HARD TO DOWNCAST
+    fn tape(&self) -> &dyn Any;
+
+    /// Prints a dump of the tape state and pointer state to stdout.
+    /// Reflects `Interpreter::print_dump`.
+    fn print_dump(&self);
+
+    /// Creates a [`RunReport`] of the current state of the interpreter.
+    /// `error` is the error which stopped the execution, if there was one.
+    fn report(&self, error: Option<InterpreterError>) -> RunReport;
+
+    /// Returns the execution counts of the byte codes and loops executed so far.
+    /// Returns `None` if the interpreter was not configured to profile.
+    fn profile(&self) -> Option<Profile>;
+
+    /// Adds items to `Interpreter::input_buffer`.
+    /// Does nothing if there the interpreter is configurated to not use bulked input.
+    /// Returns `true` if the addition to the buffer was successful.
+    fn add_to_input_buffer(&mut self, string: &str) -> bool;
+
+    /// Reflects `Interpreter::captured_output`.
+    fn captured_output(&self) -> Option<&str>;
+
+    /// Returns the number of cells allocated on the tape.
+    fn tape_len(&self) -> usize;
+
+    /// Returns the index on the tape of the cell the program started on.
+    /// It is 0, unless the tape is bidirectional and the program went left of its starting cell.
+    fn tape_origin(&self) -> usize;
+
+    /// Returns the value of the cell at `index` as a `i64`, whatever the type of the cells.
+    /// Cells which were not allocated yet are 0.
+    /// Returns `None` if the value cannot be represented as a `i64`.
+    fn cell_i64(&self, index: usize) -> Option<i64>;
+
+    /// Returns the value of the cell at `index` as a `u64`, whatever the type of the cells.
+    /// Cells which were not allocated yet are 0.
+    /// Returns `None` if the value cannot be represented as a `u64`. (e.g: it is negative)
+    fn cell_u64(&self, index: usize) -> Option<u64>;
+
+    /// Sets the value of the cell at `index`, allocating the tape up to it if needed.
+    /// Errors if the value does not fit in the cells or if the tape limit is exceded.
+    fn set_cell_i64(&mut self, index: usize, value: i64) -> Result<(), InterpreterError>;
+
+    /// Sets the value of the cell at `index`, allocating the tape up to it if needed.
+    /// Errors if the value does not fit in the cells or if the tape limit is exceded.
+    fn set_cell_u64(&mut self, index: usize, value: u64) -> Result<(), InterpreterError>;
+
+    /// Returns the position of the tape pointer.
+    fn tape_pointer(&self) -> usize;
+
+    /// Moves the tape pointer to `pointer`, the tape is allocated up to it on the next access.
+    /// Errors if `pointer` is past the end of a circular tape or past the tape limit, the pointer is then not moved.
+    fn set_tape_pointer(&mut self, pointer: usize) -> Result<(), InterpreterError>;
+
+    /// Returns the index of the next byte code to execute.
+    fn instruction_pointer(&self) -> usize;
+
+    /// Returns the operator of the next byte code to execute, `None` if the program is done.
+    fn next_operator(&self) -> Option<char>;
+
+    /// Returns the offset in bytes in the brainfuck source of the next byte code to execute, `None` if the program is done.
+    fn bf_offset(&self) -> Option<usize>;
+
+    /// Puts the interpreter back in the state it was built in.
+    /// The configuration and the input and output streams are kept.
+    fn reset(&mut self);
+
+    /// Takes a [`Snapshot`] of the current state, which can be resumed with [`InterpreterBuilder::resume`].
+    fn snapshot(&self) -> Snapshot;
+
+    /// Restores the state of `snapshot`, the configuration of the interpreter is kept.
+    /// Errors if the snapshot was taken from another program, from another cell type or from another engine.
+    /// Prefer [`InterpreterBuilder::resume`], which also applies the configuration of the snapshot.
+    fn restore_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError>;
+
+    /// Runs until the instruction pointer reaches `index`, after executing at least one byte code.
+    /// Returns `false` if the program completed before reaching it.
+    fn run_until_instruction(&mut self, index: usize) -> Result<bool, InterpreterError> {
+        while self.advance()? {
+            if self.instruction_pointer() == index {
+                return Ok(true)
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Runs until the program outputs something.
+    /// Returns `false` if the program completed without outputting.
+    fn run_until_output(&mut self) -> Result<bool, InterpreterError> {
+        loop {
+            let is_output = self.next_operator() == Some('.');
+            if !self.advance()? {
+                return Ok(false)
+            }
+
+            if is_output {
+                return Ok(true)
+            }
+        }
+    }
+}
+
+impl<T> InterpreterTrait for Interpreter<T>
+where T: NumOpsPlus + TryFrom<i8>, 
+    <T as TryFrom<i8>>::Error: Debug {
+    fn complete(&mut self) -> Result<(), InterpreterError> {
+        // calling `advance` from here does not go through the trait object, which is much faster
+        while self.advance()? {}
+
+        Ok(())
+    }
+
+    fn advance(&mut self) -> Result<bool, InterpreterError> {
+        // thanks clippy i didn't know i could do that, that's... kind of weird syntax, but very useful
+        let Some(instruction) = self.instructions.get(self.instruction_pointer).cloned() else {
+            // we are done once the pointer is out of the instruction list
+            self.flush_trace()?;
+            return Ok(false);
+        };
+
+        if let Err(e) = self.check_budgets() {
+            self.flush_trace()?;
+            return Err(e)
+        }
+
+        let executed_index = self.instruction_pointer;
+        let tape_pointer = self.logical_index(self.tape_pointer);
+        let before = self.trace_cell_value();
+        // a loop which never ends stays on the same byte code
+        let mut repeat = false;
+
+        let result = match &instruction {
+            ByteCode::Add(n) => self.increment(*n),
+            ByteCode::Sub(n) => self.decrement(*n),
+            ByteCode::PointerAdd(n) => self.pointer_increment(*n),
+            ByteCode::PointerSub(n) => self.pointer_decrement(*n),
+            ByteCode::Out => self.output(),
+            ByteCode::In => self.input(),
+            ByteCode::LeftBracket(j) => self.left_bracket(*j),
+            ByteCode::RightBracket(j) => self.right_bracket(*j),
+            ByteCode::AddAt { offset, amount } => self.add_at(*offset, *amount),
+            ByteCode::Move(n) => self.move_pointer(*n),
+            ByteCode::Clear(step) => self.clear(*step).map(|ends| repeat = !ends),
+            ByteCode::MulAdd { offset, factor } => self.mul_add(*offset, *factor),
+            ByteCode::Scan(n) => self.scan(*n),
+        };
+
+        if let Err(e) = result {
+            self.flush_trace()?;
+            return Err(e)
+        }
+
+        if let Some(before) = before {
+            self.record_step(executed_index, tape_pointer, &before)?;
+        }
+
+        if let Some(counts) = &mut self.execution_counts {
+            counts[executed_index] += 1;
+        }
+
+        if !repeat {
+            self.instruction_pointer += 1;
+        }
+        self.executed_operations += 1;
+
+        Ok(true)
+    }
+
+    fn tape(&self) -> &dyn Any {
+        &self.tape
+    }
+
+    fn print_dump(&self) {
+        self.print_dump();
+    }
+
+    fn report(&self, error: Option<InterpreterError>) -> RunReport {
+        self.report(error)
+    }
+
+    fn profile(&self) -> Option<Profile> {
+        self.profile()
+    }
+
+    fn add_to_input_buffer(&mut self, string: &str) -> bool {
+        self.add_to_input_buffer(string)
+    }
+
+    fn captured_output(&self) -> Option<&str> {
+        self.captured_output()
+    }
+
+    fn tape_len(&self) -> usize {
+        self.tape.len()
+    }
+
+    fn tape_origin(&self) -> usize {
+        self.tape_origin
+    }
+
+    fn cell_i64(&self, index: usize) -> Option<i64> {
+        self.cell_i64(index)
+    }
+
+    fn cell_u64(&self, index: usize) -> Option<u64> {
+        self.cell_u64(index)
+    }
+
+    fn set_cell_i64(&mut self, index: usize, value: i64) -> Result<(), InterpreterError> {
+        self.set_cell_i64(index, value)
+    }
+
+    fn set_cell_u64(&mut self, index: usize, value: u64) -> Result<(), InterpreterError> {
+        self.set_cell_u64(index, value)
+    }
+
+    fn tape_pointer(&self) -> usize {
+        self.tape_pointer
+    }
+
+    fn set_tape_pointer(&mut self, pointer: usize) -> Result<(), InterpreterError> {
+        let limit = match self.config.tape_mode {
+            TapeMode::Circular(lenght) => Some(lenght),
+            _ => self.config.lenght_limit,
+        };
+        if let Some(limit) = limit.filter(|limit| pointer >= *limit) {
+            return Err(InterpreterError::TapeLimitExceded { limit, tried: pointer.saturating_add(1) })
+        }
+
+        self.tape_pointer = pointer;
+        Ok(())
+    }
+
+    fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    fn next_operator(&self) -> Option<char> {
+        self.instructions.get(self.instruction_pointer)
+            .map(ByteCode::operator)
+    }
+
+    fn bf_offset(&self) -> Option<usize> {
+        self.offsets.get(self.instruction_pointer).copied()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        self.snapshot()
+    }
+
+    fn restore_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        self.restore_snapshot(snapshot)
+    }
+}
+
+/// Builder for [`Interpreter`].
+/// Clones of a builder share its input, output and trace streams.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InterpreterBuilder {
+    instructions: Vec<ByteCode>,
+    offsets: Vec<usize>,
+    inner: InterpreterConfig,
+    streams: Streams,
+}
+
+impl InterpreterBuilder {
+    /// Creates a new [`InterpreterBuilder`].
+    pub fn new(instructions: &str) -> Self {
+        let program_hash = snapshot::program_hash(instructions);
+        let (instructions, offsets) = brainfuck_to_bytecode(instructions);
+
+        InterpreterBuilder {
+            instructions,
+            offsets,
+            inner: InterpreterConfig { program_hash, ..InterpreterConfig::default() },
+            streams: Streams::default(),
+        }
+    }
+
+    /// Sets the cell type to unsigned integers of 8 bits (`u8`).
+    #[must_use]
+    pub fn with_u8(mut self) -> Self {
+        self.inner.cell_kind = CellKind::U8;
+        self
+    }
+
+    /// Sets the cell type to unsigned integers of 16 bits (`u16`).
+    #[must_use]
+    pub fn with_u16(mut self) -> Self {
+        self.inner.cell_kind = CellKind::U16;
+        self
+    }
+    
+    /// Sets the cell type to unsigned integers of 32 bits (`u32`).
+    #[must_use]
+    pub fn with_u32(mut self) -> Self {
+        self.inner.cell_kind = CellKind::U32;
+        self
+    }
+    
+    /// Sets the cell type to signed integers of 8 bits (`u8`).
+    #[must_use]
+    pub fn with_i8(mut self) -> Self {
+        self.inner.cell_kind = CellKind::I8;
+        self
+    }
+    
+    /// Sets the cell type to signed integers of 16 bits (`u16`).
+    #[must_use]
+    pub fn with_i16(mut self) -> Self {
+        self.inner.cell_kind = CellKind::I16;
+        self
+    }
+    
+    /// Sets the cell type to signed integers of 32 bits (`u32`).
+    #[must_use]
+    pub fn with_i32(mut self) -> Self {
+        self.inner.cell_kind = CellKind::I32;
+        self
+    }
+
+    /// Sets the cell type to unsigned integers of 64 bits (`u64`).
+    #[must_use]
+    pub fn with_u64(mut self) -> Self {
+        self.inner.cell_kind = CellKind::U64;
+        self
+    }
+
+    /// Sets the cell type to signed integers of 64 bits (`i64`).
+    #[must_use]
+    pub fn with_i64(mut self) -> Self {
+        self.inner.cell_kind = CellKind::I64;
+        self
+    }
+
+    /// Sets the cell type to signed integers of arbitrary size ([`BigCell`]).
+    /// These cells never overflow, so the overflow behaviour does not apply to them.
+    #[must_use]
+    pub fn with_big_int(mut self) -> Self {
+        self.inner.cell_kind = CellKind::Big;
+        self
+    }
+
+    /// Sets a limit to the tape lenght of `lenght` cells.
+    #[must_use]
+    pub fn with_tape_leght(mut self, lenght: usize) -> Self {
+        self.inner.lenght_limit = Some(lenght);
+        self
+    }
+
+    /// Does not set a limit to the tape lenght. It will grow as much as needed.
+    #[must_use]
+    pub fn without_tape_lenght(mut self) -> Self {
+        self.inner.lenght_limit = None;
+        self
+    }
+
+    /// Makes the tape extend infinitely to the right of the starting cell only.
+    /// Going left of the starting cell aborts the execution of the program. This is the default.
+    #[must_use]
+    pub fn with_right_infinite_tape(mut self) -> Self {
+        self.inner.tape_mode = TapeMode::RightInfinite;
+        self
+    }
+
+    /// Makes the tape extend infinitely in both directions from the starting cell.
+    /// Cells left of the starting cell have negative indices in the dump.
+    #[must_use]
+    pub fn with_bidirectional_tape(mut self) -> Self {
+        self.inner.tape_mode = TapeMode::Bidirectional;
+        self
+    }
+
+    /// Makes the tape circular with `lenght` cells, going past an end of the tape leads to the other end.
+    /// Panics if `lenght` is 0.
+    #[must_use]
+    pub fn with_circular_tape(mut self, lenght: usize) -> Self {
+        assert!(lenght > 0, "a circular tape needs at least one cell");
+        self.inner.tape_mode = TapeMode::Circular(lenght);
+        self
+    }
+
+    /// Sets the overflow behaviour to wrapping. 
+    #[must_use]
+    pub fn with_wrapping_behaviour(mut self) -> Self {
+        self.inner.overflow_behaviour = OverflowBehaviour::Wrap;
+        self
+    }
+
+    /// Sets the overflow behaviour to saturating.  
+    #[must_use]   
+    pub fn with_saturating_behaviour(mut self) -> Self {
+        self.inner.overflow_behaviour = OverflowBehaviour::Saturate;
+        self
+    }
+
+    /// Sets the overflow behaviour to aborting. 
+    #[must_use]
+    pub fn with_aborting_behaviour(mut self) -> Self {
+        self.inner.overflow_behaviour = OverflowBehaviour::Abort;
+        self
+    }
+
+    /// Sets the input mode to treat the input as an integer number.
+    #[must_use]
+    pub fn with_input_as_number(mut self) -> Self {
+        self.inner.input_as_number = true;
+        self
+    }
+
+    /// Sets the input mode to treat the input as an character.
+    #[must_use]
+    pub fn with_input_as_character(mut self) -> Self {
+        self.inner.input_as_number = false;
+        self
+    }
+
+    /// Sets the output mode to treat the output as an integer number.
+    #[must_use]
+    pub fn with_output_as_number(mut self) -> Self {
+        self.inner.output_as_number = true;
+        self
+    }
+
+    /// Sets the output mode to treat the output as an character.
+    #[must_use]
+    pub fn with_output_as_character(mut self) -> Self {
+        self.inner.output_as_number = false;
+        self
+    }
+
+    /// Sets the input mode to bulk.
+    /// This means that an input, for example: "cats are rats"
+    /// would take the first character and give that to the tape and then store the rest in a buffer.
+    /// If a bf input is requested and the buffer is not empty it will take it from the buffer.
+    #[must_use]
+    pub fn with_bulk_input(mut self) -> Self {
+        self.inner.bulk_input = true;
+        self
+    }
+
+    /// Sets the input mode to single item.
+    #[must_use]
+    pub fn without_bulk_input(mut self) -> Self {
+        self.inner.bulk_input = false;
+        self
+    }
+
+    /// Reads the input as a stream without prompting the user.
+    /// In character mode each input takes a single byte, in number mode each takes a whitespace separated number.
+    /// This is meant for piping input into programs.
+    #[must_use]
+    pub fn with_streamed_input(mut self) -> Self {
+        self.inner.streamed_input = true;
+        self
+    }
+
+    /// Prompts the user for input when the program needs it.
+    /// The prompts are written to stderr, so they don't end up in the output of the program.
+    #[must_use]
+    pub fn with_interactive_input(mut self) -> Self {
+        self.inner.streamed_input = false;
+        self
+    }
+
+    /// Leaves the cell unchanged when trying to input after the end of the input.
+    #[must_use]
+    pub fn with_eof_unchanged(mut self) -> Self {
+        self.inner.eof_behaviour = EofBehaviour::Unchanged;
+        self
+    }
+
+    /// Sets the cell to 0 when trying to input after the end of the input.
+    #[must_use]
+    pub fn with_eof_as_zero(mut self) -> Self {
+        self.inner.eof_behaviour = EofBehaviour::Zero;
+        self
+    }
+
+    /// Sets the cell to -1 (or to the maximum value for unsigned cells) when trying to input after the end of the input.
+    #[must_use]
+    pub fn with_eof_as_max(mut self) -> Self {
+        self.inner.eof_behaviour = EofBehaviour::Max;
+        self
+    }
+
+    /// Aborts the execution when trying to input after the end of the input.
+    #[must_use]
+    pub fn with_eof_aborting(mut self) -> Self {
+        self.inner.eof_behaviour = EofBehaviour::Abort;
+        self
+    }
+
+    /// Aborts the execution once `limit` byte codes were executed.
+    #[must_use]
+    pub fn with_step_limit(mut self, limit: usize) -> Self {
+        self.inner.step_limit = Some(limit);
+        self
+    }
+
+    /// Does not limit the number of byte codes that can be executed.
+    #[must_use]
+    pub fn without_step_limit(mut self) -> Self {
+        self.inner.step_limit = None;
+        self
+    }
+
+    /// Aborts the execution once it has been running for longer than `timeout`.
+    /// The clock starts with the first executed byte code, the time spent waiting for input is not counted.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.inner.timeout = Some(timeout);
+        self
+    }
+
+    /// Does not limit the time the program can run for.
+    #[must_use]
+    pub fn without_timeout(mut self) -> Self {
+        self.inner.timeout = None;
+        self
+    }
+
+    /// Counts how many times each byte code is executed.
+    /// The counts can be gotten via [`InterpreterTrait::profile`].
+    #[must_use]
+    pub fn with_profiling(mut self) -> Self {
+        self.inner.profiling = true;
+        self
+    }
+
+    /// Does not count how many times each byte code is executed.
+    #[must_use]
+    pub fn without_profiling(mut self) -> Self {
+        self.inner.profiling = false;
+        self
+    }
+
+    /// Reads the input of the program from `input` instead of stdin.
+    /// Any reader can be used, such as a [`std::fs::File`], a [`SharedBuffer`] or a byte slice.
+    #[must_use]
+    pub fn with_input(mut self, input: impl Read + 'static) -> Self {
+        self.streams.input = InputStream::new(input);
+        self
+    }
+
+    /// Writes the output of the program to `output` instead of stdout.
+    /// Any writer can be used, such as a [`std::fs::File`] or a [`SharedBuffer`].
+    #[must_use]
+    pub fn with_output(mut self, output: impl Write + 'static) -> Self {
+        self.streams.output = OutputStream::new(output);
+        self
+    }
+
+    /// Reads from stdin and writes to stdout.
+    #[must_use]
+    pub fn with_stdio(mut self) -> Self {
+        self.streams.input = InputStream::default();
+        self.streams.output = OutputStream::default();
+        self
+    }
+
+    /// Keeps a copy of the output of the program.
+    /// The copy can be gotten via [`InterpreterTrait::captured_output`].
+    #[must_use]
+    pub fn with_output_capture(mut self) -> Self {
+        self.inner.capture_output = true;
+        self
+    }
+
+    /// Does not keep a copy of the output of the program.
+    #[must_use]
+    pub fn without_output_capture(mut self) -> Self {
+        self.inner.capture_output = false;
+        self
+    }
+
+    /// Writes every executed step to `output`, see [`TraceEntry`] for the format.
+    /// The trace can be read back using [`read_trace`].
+    #[must_use]
+    pub fn with_trace(mut self, output: impl Write + 'static) -> Self {
+        self.streams.trace = Some(TraceRecorder::new(Box::new(output)));
+        self
+    }
+
+    /// Does not record the executed steps.
+    #[must_use]
+    pub fn without_trace(mut self) -> Self {
+        self.streams.trace = None;
+        self
+    }
+
+    /// Uses the fast execution engine.
+    /// The program is compiled to higher level operations: runs of `+-<>` are folded into operations on offsets,
+    /// clear loops (`[-]`), multiplication loops (`[->++>+<<]`) and scan loops (`[>]`) are executed in one step.
+    ///
+    /// The fast engine only works with wrapping cells, without profiling and without tracing.
+    /// The standard engine is used if any of those is configured.
+    /// The step limit, the instruction pointer and `run_until_instruction` count fast operations instead of byte codes.
+    /// Also, pointer moves into negatives are only reported once a cell is accessed or the pointer settles.
+    /// Clear and multiplication loops on big cells going away from 0 never end, they repeat their last operation forever.
+    #[must_use]
+    pub fn with_fast_engine(mut self) -> Self {
+        self.inner.fast_engine = true;
+        self
+    }
+
+    /// Uses the standard execution engine, which executes the brainfuck operators one clump at a time.
+    #[must_use]
+    pub fn with_standard_engine(mut self) -> Self {
+        self.inner.fast_engine = false;
+        self
+    }
+
+    /// Returns whether the fast engine would be used if it was requested.
+    fn can_use_fast_engine(&self) -> bool {
+        self.inner.overflow_behaviour == OverflowBehaviour::Wrap
+            && !self.inner.profiling
+            && self.streams.trace.is_none()
+    }
+
+    /// Finishes the building process, resuming the execution from `snapshot`.
+    /// The configuration of the machine is taken from the snapshot (see [`Snapshot`]),
+    /// the budgets, profiling, tracing and io streams from the builder.
+    ///
+    /// The number of executed byte codes carries over from the snapshot, so the step limit includes the steps executed before it.
+    /// Errors if the snapshot was taken from another program, or if it used the fast engine and profiling or tracing are enabled.
+    pub fn resume(mut self, snapshot: &Snapshot) -> Result<Box<dyn InterpreterTrait>, SnapshotError> {
+        if snapshot.program_hash() != self.inner.program_hash {
+            return Err(SnapshotError::ProgramMismatch { snapshot: snapshot.program_hash(), program: self.inner.program_hash })
+        }
+
+        let config = snapshot.config();
+        self.inner.cell_kind = config.cell_kind.clone();
+        self.inner.lenght_limit = config.lenght_limit;
+        self.inner.tape_mode = config.tape_mode.clone();
+        self.inner.overflow_behaviour = config.overflow_behaviour.clone();
+        self.inner.eof_behaviour = config.eof_behaviour.clone();
+        self.inner.input_as_number = config.input_as_number;
+        self.inner.output_as_number = config.output_as_number;
+        self.inner.bulk_input = config.bulk_input;
+        self.inner.fast_engine = config.fast_engine;
+
+        if config.fast_engine && !self.can_use_fast_engine() {
+            return Err(SnapshotError::EngineMismatch)
+        }
+
+        let mut interpreter = self.finish();
+        interpreter.restore_snapshot(snapshot)?;
+
+        Ok(interpreter)
+    }
+
+    /// Finishes the building process.
+    #[must_use]
+    pub fn finish(mut self) -> Box<dyn InterpreterTrait> {
+        // the configuration keeps the engine which is actually used, so that snapshots know how to read the instruction pointer
+        self.inner.fast_engine &= self.can_use_fast_engine();
+        if self.inner.fast_engine {
+            (self.instructions, self.offsets) = fast::to_fast_bytecode(&self.instructions, &self.offsets);
+        }
+
+        match self.inner.cell_kind {
+            CellKind::U8 => Box::new(Interpreter::<u8>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::U16 => Box::new(Interpreter::<u16>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::U32 => Box::new(Interpreter::<u32>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::I8 => Box::new(Interpreter::<i8>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::I16 => Box::new(Interpreter::<i16>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::I32 => Box::new(Interpreter::<i32>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::U64 => Box::new(Interpreter::<u64>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::I64 => Box::new(Interpreter::<i64>::new(self.instructions, self.offsets, self.inner, self.streams)),
+            CellKind::Big => Box::new(Interpreter::<BigCell>::new(self.instructions, self.offsets, self.inner, self.streams)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct InterpreterConfig {
+    /// The hash of the program, used to check that snapshots are resumed by the right program.
+    program_hash: u64,
+    cell_kind: CellKind,
+    lenght_limit: Option<usize>,
+    tape_mode: TapeMode,
+    overflow_behaviour: OverflowBehaviour,
+    step_limit: Option<usize>,
+    timeout: Option<Duration>,
+    profiling: bool,
+    capture_output: bool,
+    fast_engine: bool,
+
+    bulk_input: bool,
+    streamed_input: bool,
+    eof_behaviour: EofBehaviour,
+    input_as_number: bool,
+    output_as_number: bool,
+}
+
+/// Clumped operation byte codes.
+/// We use i8 since it is the smallest supported cell type.
+/// This means that we can cast the value without fearing that it cannot
+/// fit within the cell's type whichever it is.
+/// The recurence value is should always be above zero, despite the type.
+#[derive(Debug, Clone, PartialEq)]
+enum ByteCode {
+    /// The '>' operator `self.0` times.
+    PointerAdd(i8),
+    /// The '<' operator `self.0` times.
+    PointerSub(i8),
+    /// The '+' operator `self.0` times.
+    Add(i8),
+    /// The '-' operator `self.0` times.
+    Sub(i8),
+    /// The '[' operator.
+    /// Contains the position of the bytecode of the matching ']' (`None` if there is none).
+    LeftBracket(Option<usize>),
+    /// The ']' operator.
+    /// Contains the position of the bytecode of the matching '[' (`None` if there is none).
+    RightBracket(Option<usize>),
+    /// The ',' operator.
+    In,
+    /// The '.' operator.
+    Out,
+
+    // --- fast engine only ---
+    /// Adds `amount` to the cell at `offset` from the tape pointer, negative amounts subtract.
+    /// `amount` is never `i8::MIN`, so that it can be negated.
+    AddAt {
+        offset: isize,
+        amount: i8,
+    },
+    /// Moves the tape pointer by `self.0` cells.
+    Move(isize),
+    /// Sets the cell to 0, a `[-]` or `[+]` loop, `self.0` is what the loop adds to the cell each iteration.
+    /// Big cells going away from 0 never reach it, then the loop never ends.
+    Clear(i8),
+    /// Adds the cell multiplied by `factor` to the cell at `offset`, does nothing if the cell is 0.
+    /// A multiplication loop is made of these followed by a `Clear`.
+    /// `factor` is never `i8::MIN`, so that it can be negated.
+    MulAdd {
+        offset: isize,
+        factor: i8,
+    },
+    /// Moves the tape pointer by `self.0` cells until it is on a zero cell, a `[>]` loop.
+    Scan(isize),
+}
+
+impl ByteCode {
+    /// Returns the brainfuck operator the byte code is made of.
+    fn operator(&self) -> char {
+        match self {
+            ByteCode::PointerAdd(_) => '>',
+            ByteCode::PointerSub(_) => '<',
+            ByteCode::Add(_) => '+',
+            ByteCode::Sub(_) => '-',
+            // the loops of the fast engine start with a `[`
+            ByteCode::LeftBracket(_) | ByteCode::Clear(_) | ByteCode::MulAdd { .. } | ByteCode::Scan(_) => '[',
+            ByteCode::RightBracket(_) => ']',
+            ByteCode::In => ',',
+            ByteCode::Out => '.',
+            ByteCode::AddAt { amount, .. } => if *amount < 0 { '-' } else { '+' },
+            ByteCode::Move(n) => if *n < 0 { '<' } else { '>' },
+        }
+    }
+}
+
+/// Transforms the slice of brainfuck into byte code.
+/// Also returns the offset in bytes of the first operator of each byte code in `bf`.
+fn brainfuck_to_bytecode(bf: &str) -> (Vec<ByteCode>, Vec<usize>) {
+    macro_rules! increment_bytecode_or_stash {
+        ($state:ident, $acc:ident, $bc:ident, $offset:ident) => {
+            if let Some(($bc(val), start)) = $state {
+                // we check if we overflow, if we do
+                // we simply push the current instruction and
+                // create another one to hold what would overflow the last
+                if let Some(nval) = val.checked_add(1) {
+                    $state = Some(($bc(nval), start));
+                } else {
+                    $acc.push(($bc(val), start));
+                    $state = Some(($bc(1), $offset));
+                }
+            } else if let Some(b) = $state {
+                $acc.push(b);
+                $state = Some(($bc(1), $offset));
+            } else {
+                $state = Some(($bc(1), $offset));
+            }
+        };
+    }
+
+    macro_rules! stash_bytecode {
+        ($state:ident, $acc:ident, $bc:expr, $offset:ident) => {
+            if let Some(b) = $state {
+                $state = None;
+                
+                $acc.push(b);
+                $acc.push(($bc, $offset));
+            } else {
+                $acc.push(($bc, $offset));
+            }
+        };
+    }
+
+    let (mut instructions, remaining) = bf.char_indices().filter(|(_, c)| {
+        *c == '+' || *c == '-'
+        || *c == '<' || *c == '>'
+        || *c == ',' || *c == '.'
+        || *c == '[' || *c == ']'
+    }
+    ).fold((Vec::new(), None),|(mut acc, mut state), (offset, ch)| {
+        // transforming the operator string into clumped byte codes to reduce redundent operations
+        match ch {
+            '+' => {
+                use ByteCode::Add as Add;
+                increment_bytecode_or_stash!(state, acc, Add, offset);
+            },
+            '-' => {
+                use ByteCode::Sub as Sub;
+                increment_bytecode_or_stash!(state, acc, Sub, offset);
+            },
+            '>' => {
+                use ByteCode::PointerAdd as PointerAdd;
+                increment_bytecode_or_stash!(state, acc, PointerAdd, offset);
+            },
+            '<' => {
+                use ByteCode::PointerSub as PointerSub;
+                increment_bytecode_or_stash!(state, acc, PointerSub, offset);
+            },
+            '[' => {
+                use ByteCode::LeftBracket as LeftBracket;
+                stash_bytecode!(state, acc, LeftBracket(None), offset);
+            },
+            ']' => {
+                use ByteCode::RightBracket as RightBracket;
+                stash_bytecode!(state, acc, RightBracket(None), offset);
+            },
+            ',' => {
+                use ByteCode::In as In;
+                stash_bytecode!(state, acc, In, offset);
+            },
+            '.' => {
+                use ByteCode::Out as Out;
+                stash_bytecode!(state, acc, Out, offset);
+            },
+            _ => unreachable!()
+        }
+
+        (acc, state)
+    });
+
+    if let Some(b) = remaining {
+        instructions.push(b);
+    }
+
+    instructions.into_iter().unzip()
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+enum CellKind {
+    #[default]
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    Big,
+}
+
+impl CellKind {
+    /// Every cell kind.
+    const ALL: [CellKind; 9] = [
+        CellKind::U8, CellKind::I8, CellKind::U16, CellKind::I16, CellKind::U32,
+        CellKind::I32, CellKind::U64, CellKind::I64, CellKind::Big,
+    ];
+
+    /// Returns the name of the rust type matching the cell kind.
+    fn name(&self) -> &'static str {
+        match self {
+            CellKind::U8 => "u8",
+            CellKind::I8 => "i8",
+            CellKind::U16 => "u16",
+            CellKind::I16 => "i16",
+            CellKind::U32 => "u32",
+            CellKind::I32 => "i32",
+            CellKind::U64 => "u64",
+            CellKind::I64 => "i64",
+            CellKind::Big => "bigint",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+enum OverflowBehaviour {
+    #[default]
+    Wrap,
+    Saturate,
+    Abort,
+}
+
+/// How the tape extends from the starting cell.
+#[derive(Debug, Clone, PartialEq, Default)]
+enum TapeMode {
+    /// Only extends to the right, going left of the starting cell is an error.
+    #[default]
+    RightInfinite,
+    /// Extends in both directions.
+    Bidirectional,
+    /// Has a fixed number of cells, going past an end of the tape leads to the other end.
+    Circular(usize),
+}
+
+/// What happens to the cell when the program inputs after the end of the input.
+#[derive(Debug, Clone, PartialEq, Default)]
+enum EofBehaviour {
+    #[default]
+    Unchanged,
+    Zero,
+    Max,
+    Abort,
+}
+
+trait NumOpsPlus: WrappingAdd + WrappingSub + WrappingMul + CheckedAdd + CheckedSub + SaturatingAdd + SaturatingSub
+    + Num + ConstZero + NumCast + Default + Debug + Clone + PartialOrd + FromStr + 'static {}
+impl<T> NumOpsPlus for T 
+where T: WrappingAdd + WrappingSub + WrappingMul + CheckedAdd + CheckedSub + SaturatingAdd + SaturatingSub
+    + Num + ConstZero + NumCast + Default + Debug + Clone + PartialOrd + FromStr + 'static {}
+
+/// An error that is relative to interpreting a brainfuck program.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum InterpreterError {
+    /// The tape size limitor is smaller that what the program tried to allocate.
+    /// Allocation is implicit, it happens when trying to write or read for a cell. (e.g.: every operation execept `>` and `<`)
+    #[error("the tape size limit was exceded, tried to expand to {tried:?}, but limit is {limit:?}")]
+    TapeLimitExceded {
+        /// The tape size limit.
+        limit: usize,
+        /// The cell index that was requested for reading/writing.
+        tried: usize,
+    },
+    /// The tape pointer is not within the range of valid values. (e.g `0..=usize::MAX`)
+    #[error("the tape pointer has gone into negatives")]
+    TapePointerOob,
+    /// The instruction pointer is not within the range of valid values. (e.g `0..=usize::MAX`)
+    /// It is most likely that this is because there was an unmatched `]` cause the instruction pointer to go into negatives.
+    #[error("the instruction pointer has gone into negatives")]
+    InstructionPointerOob,
+    /// The program over/under-flowed a cell.
+    /// This error only happens if the interpreter was configured to abort on overflow.
+    #[error("aborted due to overflow at cell {at:?}")]
+    AbortedDueToOverflow {
+        /// The index of the cell that over/under-flowed.
+        at: usize,
+    },
+    /// The program tried to reach the matching bracket of a bracket that does not have a match.
+    /// This error only occurs when the bracket tried to jump to its match, not when it is passed over
+    /// because the condition is not satisfied.
+    #[error("an unmatched braket tried to jump to its matching bracket")]
+    UnmatchedBracket,
+    /// The program executed as many byte codes as the step limit allows.
+    /// This error only happens if the interpreter was configured with a step limit.
+    #[error("the step limit of {limit} was reached after executing {steps} steps, at instruction {instruction_pointer}")]
+    StepLimitExceded {
+        /// The step limit.
+        limit: usize,
+        /// The number of byte codes that were executed.
+        steps: usize,
+        /// The index of the byte code which was about to be executed.
+        instruction_pointer: usize,
+    },
+    /// The program ran for longer than the timeout.
+    /// This error only happens if the interpreter was configured with a timeout.
+    #[error("timed out after {timeout:?}, executed {steps} steps, at instruction {instruction_pointer}")]
+    TimedOut {
+        /// The timeout.
+        timeout: Duration,
+        /// The number of byte codes that were executed.
+        steps: usize,
+        /// The index of the byte code which was about to be executed.
+        instruction_pointer: usize,
+    },
+    /// The program tried to input after the end of the input.
+    /// This error only happens if the interpreter was configured to abort on end of input.
+    #[error("the program tried to read past the end of the input")]
+    EndOfInput,
+    /// The input could not be converted into a cell value.
+    /// This error only happens with streamed input, since interactive input asks again.
+    #[error("the input {input:?} is not a valid cell value")]
+    InvalidInput {
+        /// The invalid input.
+        input: String,
+    },
+    /// The value does not fit in the cells of the interpreter.
+    #[error("the value {value} does not fit in a {cell_kind} cell")]
+    ValueOutOfRange {
+        /// The value which was tried to be set.
+        value: String,
+        /// The name of the type of the cells.
+        cell_kind: &'static str,
+    },
+    /// The output of the program could not be written.
+    #[error("failed to write the output: {reason}")]
+    OutputWriteFailed {
+        /// The reason of the failure.
+        reason: String,
+    },
+    /// The step could not be written to the trace output.
+    /// This error only happens if the interpreter was configured to record a trace.
+    #[error("failed to write to the trace: {reason}")]
+    TraceWriteFailed {
+        /// The reason of the failure.
+        reason: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{source::SourceFile, transpile};
+
+    use super::*;
+
+    #[test]
+    fn interpreter_multiply() {
+        let mut inter = InterpreterBuilder::new("++++++++++++>++++<[->>+<<]>[>[->+<<<+>>]>[-<+>]<<-]")
+            .with_u8()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u8>>().unwrap();
+        assert_eq!(tape[0], 48);
+    }
+
+    #[test]
+    fn interpreter_fibonacci() {
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+            .with_u8()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u8>>().unwrap();
+        assert_eq!(tape[1], 144);
+    }
+
+    #[test]
+    fn interpreter_cell_types() {
+        // u8
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+            .with_u8()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u8>>().unwrap();
+        assert_eq!(tape[1], 144_u8);
+
+        // i8 (should overflow)
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+            .with_i8()
+            .with_aborting_behaviour()
+            .finish();
+        inter.complete().unwrap_err();
+        
+        // u16
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+        .with_u16()
+        .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u16>>().unwrap();
+        assert_eq!(tape[1], 144_u16);
+
+        // i16
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+        .with_i16()
+        .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<i16>>().unwrap();
+        assert_eq!(tape[1], 144_i16);
+
+        // u32
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+        .with_u32()
+        .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u32>>().unwrap();
+        assert_eq!(tape[1], 144_u32);
+
+        // i32
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+        .with_i32()
+        .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<i32>>().unwrap();
+        assert_eq!(tape[1], 144_i32);
+    }
+
+    #[test]
+    fn interpreter_wide_cells() {
+        let run = |builder: InterpreterBuilder, program_input: &str| {
+            let mut inter = builder
+                .with_input_as_number()
+                .with_output_as_number()
+                .with_bulk_input()
+                .with_output_capture()
+                .finish();
+            assert!(inter.add_to_input_buffer(program_input));
+            inter.complete().unwrap();
+            inter.captured_output().unwrap().to_string()
+        };
+
+        // 64 bits cells wrap
+        assert_eq!(
+            run(InterpreterBuilder::new(",+.").with_i64(), &i64::MAX.to_string()),
+            format!("{} ", i64::MIN),
+        );
+        assert_eq!(run(InterpreterBuilder::new(",+.").with_u64(), &u64::MAX.to_string()), "0 ");
+
+        // big ints don't overflow, on both engines
+        let big = "123456789012345678901234567890";
+        assert_eq!(run(InterpreterBuilder::new(",+.").with_big_int(), big), "123456789012345678901234567891 ");
+        assert_eq!(
+            run(InterpreterBuilder::new(",[->++<]>.").with_big_int().with_fast_engine(), big),
+            "246913578024691357802469135780 ",
+        );
+        assert_eq!(
+            run(InterpreterBuilder::new("-.").with_big_int().with_aborting_behaviour(), "0"),
+            "-1 ",
+        );
+
+        // big ints never reach 0 going away from it, so these loops never end, on both engines
+        for program in ["-[-]", "+[+]", "-[->+<]"] {
+            for builder in [InterpreterBuilder::new(program), InterpreterBuilder::new(program).with_fast_engine()] {
+                let mut inter = builder
+                    .with_big_int()
+                    .with_step_limit(1000)
+                    .finish();
+                match inter.complete() {
+                    Err(InterpreterError::StepLimitExceded { .. }) => (), // good
+                    other => panic!("got other {other:?}"),
+                }
+            }
+        }
+
+        let mut inter = InterpreterBuilder::new("-->+")
+            .with_big_int()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<BigCell>>().unwrap();
+        assert_eq!(tape, &vec![BigCell((-2).into()), BigCell(1.into())]);
+        assert_eq!(inter.cell_i64(0), Some(-2));
+    }
+
+    #[test]
+    fn interpreter_behaviour() {
+        // -- wrapping
+        // overflow
+        let plus_256: String = ["+"; 257].concat();
+        let mut inter = InterpreterBuilder::new(&plus_256)
+            .with_u8()
+            .with_wrapping_behaviour()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u8>>().unwrap();
+        assert_eq!(tape[0], 1);
+
+        // underflow
+        let mut inter = InterpreterBuilder::new("---")
+            .with_u8()
+            .with_wrapping_behaviour()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u8>>().unwrap();
+        assert_eq!(tape[0], 253);
+
+        // -- abort
+        // overflow
+        let plus_256: String = ["+"; 256].concat();
+        let mut inter = InterpreterBuilder::new(&plus_256)
+            .with_u8()
+            .with_aborting_behaviour()
+            .finish();
+        inter.complete().unwrap_err();
+
+        // underflow
+        let mut inter = InterpreterBuilder::new("-")
+            .with_u8()
+            .with_aborting_behaviour()
+            .finish();
+        inter.complete().unwrap_err();
+
+        // -- saturating
+        // overflow
+        let plus_256: String = ["+"; 1024].concat();
+        let mut inter = InterpreterBuilder::new(&plus_256)
+            .with_u8()
+            .with_saturating_behaviour()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u8>>().unwrap();
+        assert_eq!(tape[0], u8::MAX);
+
+        // underflow
+        let mut inter = InterpreterBuilder::new("---")
+            .with_u8()
+            .with_saturating_behaviour()
+            .finish();
+        inter.complete().unwrap();
+        let tape = inter.tape().downcast_ref::<Vec<u8>>().unwrap();
+        assert_eq!(tape[0], 0);
+    }
+
+    #[test]
+    fn interpreter_limitor() {
+        let mut heavy_program = [">"; 1293].concat();
+        heavy_program.push('+');
+
+        // no limitor
+        let mut inter = InterpreterBuilder::new(&heavy_program)
+            .finish();
+        inter.complete().unwrap();
+
+        // with limitor, but big enough
+        let mut inter = InterpreterBuilder::new(&heavy_program)
+            .with_tape_leght(4096)
+            .finish();
+        inter.complete().unwrap();
+
+        // with small limitor
+        let mut inter = InterpreterBuilder::new(&heavy_program)
+            .with_tape_leght(256)
+            .finish();
+        match inter.complete() {
+            Err(InterpreterError::TapeLimitExceded { .. }) => (), // good
+            other => panic!("got other {other:?}"),
+        };
+    }
+
+    #[test]
+    fn interpreter_tape_modes() {
+        // going left of the starting cell
+        let program = "+<<++>>>+++<.";
+        let mut inter = InterpreterBuilder::new(program)
+            .with_output_capture()
+            .finish();
+        assert_eq!(inter.complete(), Err(InterpreterError::TapePointerOob));
+
+        let mut inter = InterpreterBuilder::new(program)
+            .with_bidirectional_tape()
+            .with_output_as_number()
+            .with_output_capture()
+            .finish();
+        inter.complete().unwrap();
+        assert_eq!(inter.captured_output().unwrap(), "1 ");
+        let origin = inter.tape_origin();
+        assert_eq!(origin, 2);
+        assert_eq!(inter.tape().downcast_ref::<Vec<u8>>().unwrap(), &vec![2, 0, 1, 3]);
+        assert_eq!(inter.tape_pointer(), origin);
+
+        // the limit is on the whole tape
+        let mut inter = InterpreterBuilder::new("<<<<+>>>>>+")
+            .with_bidirectional_tape()
+            .with_tape_leght(5)
+            .finish();
+        assert_eq!(inter.complete(), Err(InterpreterError::TapeLimitExceded { limit: 5, tried: 6 }));
+
+        // circular tape
+        let mut inter = InterpreterBuilder::new("<+<++>>>>+++")
+            .with_circular_tape(3)
+            .finish();
+        inter.complete().unwrap();
+        assert_eq!(inter.tape().downcast_ref::<Vec<u8>>().unwrap(), &vec![0, 2, 4]);
+        assert_eq!(inter.tape_pointer(), 2);
+
+        // the fast engine agrees
+        let run = |builder: InterpreterBuilder| {
+            let mut inter = builder.finish();
+            inter.complete().unwrap();
+            (inter.tape().downcast_ref::<Vec<u8>>().unwrap().clone(), inter.tape_pointer())
+        };
+        let program = "+<<<++>>>[-<<<<+>>>>]<<<<[-<+>]>>>>>>>+<[<]";
+        assert_eq!(
+            run(InterpreterBuilder::new(program).with_bidirectional_tape().with_standard_engine()),
+            run(InterpreterBuilder::new(program).with_bidirectional_tape().with_fast_engine()),
+        );
+        assert_eq!(
+            run(InterpreterBuilder::new(program).with_circular_tape(7).with_standard_engine()),
+            run(InterpreterBuilder::new(program).with_circular_tape(7).with_fast_engine()),
+        );
+    }
+
+    #[test]
+    fn interpreter_tape_only_takes_necessary() {
+        let mut program = [">"; 1293].concat();
+        program.push_str(&["<"; 1200].concat());
+
+        // takes nothing
+        let mut inter = InterpreterBuilder::new(&program)
+            .with_tape_leght(0)
+            .finish();
+        inter.complete().unwrap();
+
+        // allocates what is needed, and is big enough
+        program.push('+');
+        let mut inter = InterpreterBuilder::new(&program)
+            .with_tape_leght(256)
+            .finish();
+        inter.complete().unwrap();
+
+        // allocates what is needed, but is too small
+        let mut inter = InterpreterBuilder::new(&program)
+            .with_tape_leght(64)
+            .finish();
+        match inter.complete() {
+            Err(InterpreterError::TapeLimitExceded { .. }) => (), // good
+            other => panic!("got other {other:?}"),
+        };
+    }
+
+    #[test]
+    fn interpreter_captured_output_is_accurate() {
+        let source = include_str!("../../test-resources/custom-conditionals.basm").to_string();
+        let sf = SourceFile::from_raw_parts("conditionals.gay".into(), source);
+        let conditional = transpile(sf.leak())
+            .unwrap();
+        let mut inter = InterpreterBuilder::new(&conditional)
+            .with_output_as_character()
+            .with_output_capture()
+            .finish();
+        inter.complete().unwrap();
+        assert_eq!(inter.captured_output().unwrap(), "right");
+
+        let source = include_str!("../../test-resources/fib.basm").to_string();
+        let sf = SourceFile::from_raw_parts("fib.gay".into(), source);
+        let fib = transpile(sf.leak())
+            .unwrap();
+        let mut inter = InterpreterBuilder::new(&fib)
+            .with_output_as_number()
+            .with_output_capture()
+            .finish();
+        inter.complete().unwrap();
+        assert_eq!(inter.captured_output().unwrap(), "1 2 3 5 8 13 21 34 55 89 144 ");
+    }
+
+    #[test]
+    fn interpreter_budgets() {
+        // step limit
+        let mut inter = InterpreterBuilder::new("+[]")
+            .with_step_limit(100)
+            .finish();
+        match inter.complete() {
+            Err(InterpreterError::StepLimitExceded { limit: 100, steps: 100, instruction_pointer: 1 | 2 }) => (), // good
+            other => panic!("got other {other:?}"),
+        }
+
+        // big enough step limit
+        let mut inter = InterpreterBuilder::new(include_str!("../../test-resources/fib.bf"))
+            .with_step_limit(1_000_000)
+            .finish();
+        inter.complete().unwrap();
+
+        // timeout
+        let mut inter = InterpreterBuilder::new("+[]")
+            .with_timeout(Duration::from_millis(20))
+            .finish();
+        match inter.complete() {
+            Err(InterpreterError::TimedOut { steps, .. }) => assert!(steps > 0),
+            other => panic!("got other {other:?}"),
+        }
+    }
+
+    #[test]
+    fn interpreter_timeout_input() {
+        // waiting for the input does not count towards the timeout
+        struct SlowInput;
+        impl std::io::Read for SlowInput {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                std::thread::sleep(Duration::from_millis(100));
+                buf[0] = 255;
+                Ok(1)
+            }
+        }
+
+        let mut inter = InterpreterBuilder::new(",[>+++[-]<-]")
+            .with_input(SlowInput)
+            .with_streamed_input()
+            .with_timeout(Duration::from_millis(50))
+            .finish();
+        inter.complete().unwrap();
+    }
+
+    #[test]
+    fn interpreter_end_of_input() {
+        let config = |eof_behaviour| InterpreterConfig { eof_behaviour, ..Default::default() };
+
+        let mut inter = Interpreter::<u8>::new(Vec::new(), Vec::new(), config(EofBehaviour::Unchanged), Streams::default());
+        inter.tape = vec![7];
+        inter.end_of_input().unwrap();
+        assert_eq!(inter.tape, vec![7]);
+
+        let mut inter = Interpreter::<u8>::new(Vec::new(), Vec::new(), config(EofBehaviour::Zero), Streams::default());
+        inter.tape = vec![7];
+        inter.end_of_input().unwrap();
+        assert_eq!(inter.tape, vec![0]);
+
+        let mut inter = Interpreter::<u8>::new(Vec::new(), Vec::new(), config(EofBehaviour::Max), Streams::default());
+        inter.end_of_input().unwrap();
+        assert_eq!(inter.tape, vec![u8::MAX]);
+
+        let mut inter = Interpreter::<i16>::new(Vec::new(), Vec::new(), config(EofBehaviour::Max), Streams::default());
+        inter.end_of_input().unwrap();
+        assert_eq!(inter.tape, vec![-1]);
+
+        let mut inter = Interpreter::<u8>::new(Vec::new(), Vec::new(), config(EofBehaviour::Abort), Streams::default());
+        assert_eq!(inter.end_of_input(), Err(InterpreterError::EndOfInput));
+    }
+
+    #[test]
+    fn adding_to_buffer() {
+        let source = "+[,.]".to_string();
+
+        // numbers
+        let mut inter = InterpreterBuilder::new(&source)
+            .with_input_as_number()
+            .with_output_as_number()
+            .with_bulk_input()
+            .with_output_capture()
+            .finish();
+
+        assert!(!inter.add_to_input_buffer("732"));
+        assert!(!inter.add_to_input_buffer("openup"));
+        assert!(inter.add_to_input_buffer("3 1 2 0"));
+        inter.complete().unwrap();
+        assert_eq!(inter.captured_output().unwrap(), "3 1 2 0 ");
+
+        // characters
+        let mut inter = InterpreterBuilder::new(&source)
+            .with_bulk_input()
+            .with_output_capture()
+            .finish();
+
+        assert!(inter.add_to_input_buffer("732"));
+        assert!(inter.add_to_input_buffer("openup\0"));
+        inter.complete().unwrap();
+        assert_eq!(inter.captured_output().unwrap().trim(), "732openup\0");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,179 @@
+//! Execution counts of byte codes and loops, used to find the hot spots of a program.
+
+use std::{cmp::Reverse, ops::Range};
+
+use colored::Colorize as _;
+
+use crate::compiler::SourceMap;
+
+use super::{ByteCode, Interpreter};
+
+/// The execution counts of a program.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Profile {
+    /// The execution counts of each byte code, in the order of the program.
+    pub byte_codes: Vec<ByteCodeProfile>,
+    /// The execution counts of each matched bracket pair, in the order of their `[`.
+    pub loops: Vec<LoopProfile>,
+}
+
+/// The execution count of a single byte code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ByteCodeProfile {
+    /// The offset in bytes of the first operator of the byte code in the brainfuck source.
+    pub offset: usize,
+    /// The operator the byte code is made of.
+    pub operator: char,
+    /// The number of operators clumped in the byte code.
+    pub recurrence: usize,
+    /// The number of times the byte code was executed.
+    pub executions: u64,
+}
+
+/// The execution counts of a loop (a matched bracket pair).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopProfile {
+    /// The range in bytes of the loop in the brainfuck source, brackets included.
+    pub bf_range: Range<usize>,
+    /// The number of times the loop was reached.
+    pub entries: u64,
+    /// The number of times the body of the loop was run.
+    pub iterations: u64,
+    /// The number of byte codes executed within the loop, including the brackets and nested loops.
+    pub executed_operations: u64,
+}
+
+impl Profile {
+    /// Returns the total number of byte codes executed.
+    pub fn total_operations(&self) -> u64 {
+        self.byte_codes.iter().map(|b| b.executions).sum()
+    }
+
+    /// Returns the `count` loops within which the most byte codes were executed, the hottest first.
+    pub fn hottest_loops(&self, count: usize) -> Vec<&LoopProfile> {
+        let mut loops = self.loops.iter()
+            .filter(|l| l.entries != 0)
+            .collect::<Vec<_>>();
+        loops.sort_by_key(|l| Reverse(l.executed_operations));
+        loops.truncate(count);
+
+        loops
+    }
+
+    /// Prints the `count` hottest loops to stdout.
+    /// If a `source_map` is provided, also prints the basm instructions which generated the loops.
+    #[allow(clippy::cast_precision_loss)] // it's only for a percentage
+    pub fn print_hottest_loops(&self, count: usize, source_map: Option<&SourceMap>) {
+        let total = self.total_operations().max(1);
+
+        println!("{}", "-- HOTTEST LOOPS --".red().underline().bold());
+        println!("{} {}", "total executed operations:".black(), total);
+        for (i, l) in self.hottest_loops(count).into_iter().enumerate() {
+            let percentage = l.executed_operations as f64 / total as f64 * 100.0;
+            println!(
+                "{} bf {}..{}, {} ops ({percentage:.1}%), {} entries, {} iterations",
+                format!("#{}:", i+1).black(),
+                l.bf_range.start,
+                l.bf_range.end,
+                l.executed_operations,
+                l.entries,
+                l.iterations,
+            );
+
+            if let Some(entry) = source_map.and_then(|sm| sm.find_covering(l.bf_range.clone())) {
+                println!("    {} {}", "from".black(), entry.describe());
+            }
+        }
+        println!();
+    }
+}
+
+impl<T> Interpreter<T> {
+    /// Returns the execution counts of the byte codes and loops executed so far.
+    /// Returns `None` if the interpreter was not configured to profile.
+    pub(super) fn profile(&self) -> Option<Profile> {
+        let counts = self.execution_counts.as_ref()?;
+
+        let byte_codes = self.instructions.iter()
+            .zip(&self.offsets)
+            .zip(counts)
+            .map(|((bc, offset), executions)| {
+                let recurrence = match bc {
+                    ByteCode::PointerAdd(n) | ByteCode::PointerSub(n)
+                    | ByteCode::Add(n) | ByteCode::Sub(n) => *n as usize,
+                    _ => 1,
+                };
+
+                ByteCodeProfile {
+                    offset: *offset,
+                    operator: bc.operator(),
+                    recurrence,
+                    executions: *executions,
+                }
+            }).collect();
+
+        let loops = self.instructions.iter()
+            .enumerate()
+            .filter_map(|(i, bc)| if let ByteCode::LeftBracket(Some(j)) = bc {
+                Some((i, *j))
+            } else {
+                None
+            })
+            .map(|(i, j)| LoopProfile {
+                bf_range: self.offsets[i]..self.offsets[j]+1,
+                entries: counts[i],
+                // the closing bracket is executed once at the end of every iteration
+                iterations: counts[j],
+                executed_operations: counts[i..=j].iter().sum(),
+            }).collect();
+
+        Some(Profile {
+            byte_codes,
+            loops,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::InterpreterBuilder;
+
+    #[test]
+    fn profiling_counts() {
+        // not profiling
+        let mut inter = InterpreterBuilder::new("+++[-]")
+            .finish();
+        inter.complete().unwrap();
+        assert!(inter.profile().is_none());
+
+        //                                      0   4 6  9  12 15 18
+        let mut inter = InterpreterBuilder::new("+++ [>++[-]<-] [-]")
+            .with_profiling()
+            .finish();
+        inter.complete().unwrap();
+        let profile = inter.profile().unwrap();
+
+        assert_eq!(profile.byte_codes[0].offset, 0);
+        assert_eq!(profile.byte_codes[0].operator, '+');
+        assert_eq!(profile.byte_codes[0].recurrence, 3);
+        assert_eq!(profile.byte_codes[0].executions, 1);
+
+        assert_eq!(profile.loops.len(), 3);
+        // outer loop
+        assert_eq!(profile.loops[0].bf_range, 4..14);
+        assert_eq!(profile.loops[0].entries, 1);
+        assert_eq!(profile.loops[0].iterations, 3);
+        // inner loop
+        assert_eq!(profile.loops[1].bf_range, 8..11);
+        assert_eq!(profile.loops[1].entries, 3);
+        assert_eq!(profile.loops[1].iterations, 6);
+        // never entered since the cell is zeroed
+        assert_eq!(profile.loops[2].entries, 1);
+        assert_eq!(profile.loops[2].iterations, 0);
+
+        let hottest = profile.hottest_loops(2);
+        assert_eq!(hottest[0].bf_range, 4..14);
+        assert_eq!(hottest[1].bf_range, 8..11);
+        assert_eq!(profile.total_operations(), inter.report(None).executed_operations as u64);
+    }
+}
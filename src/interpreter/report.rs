@@ -0,0 +1,121 @@
+//! Machine-readable reports about the outcome of a run.
+
+use crate::json::JsonValue;
+
+use super::InterpreterError;
+
+/// A summary of the state of an interpreter once it stopped running.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    /// The decimal representation of every allocated cell of the tape.
+    /// Strings are used so that any cell kind can be represented.
+    pub tape: Vec<String>,
+    /// The position of the tape pointer, relative to the cell the program started on.
+    pub tape_pointer: isize,
+    /// The index in `tape` of the cell the program started on, cells left of it were allocated by a bidirectional tape.
+    pub tape_origin: usize,
+    /// The name of the cell type. (e.g: `u8`, `i32`)
+    pub cell_kind: String,
+    /// The number of byte codes that were executed.
+    pub executed_operations: usize,
+    /// The error which stopped the program, `None` if it ran to completion.
+    pub error: Option<InterpreterError>,
+}
+
+impl RunReport {
+    /// Returns `"completed"` if the program ran until the end or `"errored"` if it was stopped by an error.
+    pub fn exit_status(&self) -> &'static str {
+        if self.error.is_some() {
+            "errored"
+        } else {
+            "completed"
+        }
+    }
+
+    /// Converts the report into a JSON document.
+    pub fn to_json(&self) -> JsonValue {
+        let tape = self.tape.iter()
+            .map(|c| JsonValue::Number(c.clone()))
+            .collect();
+
+        let error = match &self.error {
+            Some(e) => JsonValue::object([
+                ("kind", JsonValue::string(error_kind(e))),
+                ("message", JsonValue::string(e.to_string())),
+            ]),
+            None => JsonValue::Null,
+        };
+
+        JsonValue::object([
+            ("exit_status", JsonValue::string(self.exit_status())),
+            ("cell_kind", JsonValue::string(&self.cell_kind)),
+            ("tape_pointer", JsonValue::number(self.tape_pointer)),
+            ("tape_origin", JsonValue::number(self.tape_origin)),
+            ("executed_operations", JsonValue::number(self.executed_operations)),
+            ("tape", JsonValue::Array(tape)),
+            ("error", error),
+        ])
+    }
+}
+
+/// Returns the name of the variant of the error.
+fn error_kind(error: &InterpreterError) -> &'static str {
+    match error {
+        InterpreterError::TapeLimitExceded { .. } => "TapeLimitExceded",
+        InterpreterError::TapePointerOob => "TapePointerOob",
+        InterpreterError::InstructionPointerOob => "InstructionPointerOob",
+        InterpreterError::AbortedDueToOverflow { .. } => "AbortedDueToOverflow",
+        InterpreterError::UnmatchedBracket => "UnmatchedBracket",
+        InterpreterError::StepLimitExceded { .. } => "StepLimitExceded",
+        InterpreterError::TimedOut { .. } => "TimedOut",
+        InterpreterError::EndOfInput => "EndOfInput",
+        InterpreterError::InvalidInput { .. } => "InvalidInput",
+        InterpreterError::ValueOutOfRange { .. } => "ValueOutOfRange",
+        InterpreterError::OutputWriteFailed { .. } => "OutputWriteFailed",
+        InterpreterError::TraceWriteFailed { .. } => "TraceWriteFailed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::InterpreterBuilder;
+
+    #[test]
+    fn report_json() {
+        let mut inter = InterpreterBuilder::new("++>+++<[->+<]>")
+            .with_u16()
+            .finish();
+        let res = inter.complete();
+        let report = inter.report(res.err());
+
+        assert_eq!(report.tape, vec!["0", "5"]);
+        assert_eq!(report.tape_pointer, 1);
+        assert_eq!(report.exit_status(), "completed");
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"exit_status":"completed","cell_kind":"u16","tape_pointer":1,"tape_origin":0,"executed_operations":16,"tape":[0,5],"error":null}"#
+        );
+
+        let mut inter = InterpreterBuilder::new("+<")
+            .finish();
+        let res = inter.complete();
+        let report = inter.report(res.err());
+
+        assert_eq!(report.exit_status(), "errored");
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"exit_status":"errored","cell_kind":"u8","tape_pointer":0,"tape_origin":0,"executed_operations":1,"tape":[1],"error":{"kind":"TapePointerOob","message":"the tape pointer has gone into negatives"}}"#
+        );
+
+        // cells left of the starting one on a bidirectional tape
+        let mut inter = InterpreterBuilder::new("+<<+>")
+            .with_bidirectional_tape()
+            .finish();
+        let res = inter.complete();
+        let report = inter.report(res.err());
+
+        assert_eq!(report.tape_pointer, -1);
+        assert_eq!(report.tape[report.tape_origin], "1");
+        assert_eq!(report.tape[report.tape_origin - 2], "1");
+    }
+}
@@ -0,0 +1,462 @@
+//! Saving the state of an interpreter, so that its execution can be resumed later.
+
+use std::{collections::VecDeque, fmt::Debug, time::Duration};
+
+use thiserror::Error;
+
+use crate::json::JsonValue;
+
+use super::{ByteCode, CellKind, EofBehaviour, Interpreter, InterpreterConfig, NumOpsPlus, OverflowBehaviour, TapeMode};
+
+/// The version of the snapshot format, snapshots of other versions are rejected.
+const SNAPSHOT_VERSION: u64 = 1;
+
+/// The full state of an interpreter, at some point of the execution of a program.
+/// It can be written to JSON with [`Snapshot::to_json`], read back with [`Snapshot::parse`]
+/// and resumed with [`InterpreterBuilder::resume`](super::InterpreterBuilder::resume).
+///
+/// The configuration of the machine (cell type, overflow and end of input behaviours, tape, input and output modes
+/// and engine) is part of the snapshot, the budgets, profiling, tracing and io streams are not.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// The hash of the program, see [`program_hash`].
+    program_hash: u64,
+    config: InterpreterConfig,
+    /// The decimal representation of every allocated cell of the tape.
+    tape: Vec<String>,
+    tape_pointer: usize,
+    tape_origin: usize,
+    instruction_pointer: usize,
+    executed_operations: usize,
+    /// The decimal representation of the values waiting in the input buffer.
+    input_buffer: Vec<String>,
+}
+
+/// An error caused by a snapshot which cannot be resumed.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SnapshotError {
+    /// The snapshot is not valid JSON, or is missing some of the state.
+    #[error("the snapshot is malformed: {reason}")]
+    Malformed {
+        /// What is wrong with the snapshot.
+        reason: String,
+    },
+    /// The snapshot was taken while running another program.
+    #[error("the snapshot was taken from another program (hash {snapshot:016x}, expected {program:016x})")]
+    ProgramMismatch {
+        /// The hash of the program of the snapshot.
+        snapshot: u64,
+        /// The hash of the program which tried to resume it.
+        program: u64,
+    },
+    /// The snapshot was taken from an interpreter configured differently.
+    /// The configuration of the snapshot needs to be applied before restoring it.
+    #[error("the snapshot was taken from an interpreter with {snapshot} cells, not {interpreter}")]
+    CellKindMismatch {
+        /// The name of the cell type of the snapshot.
+        snapshot: &'static str,
+        /// The name of the cell type of the interpreter.
+        interpreter: &'static str,
+    },
+    /// The snapshot was taken using the fast engine, which cannot be used with the current configuration.
+    /// Their byte codes differ, so the instruction pointer would not match.
+    #[error("the snapshot was taken using the fast engine, which cannot be used with profiling or tracing")]
+    EngineMismatch,
+    /// A value of the snapshot does not fit in its cells.
+    #[error("the value {value} of the snapshot does not fit in a {cell_kind} cell")]
+    InvalidValue {
+        /// The invalid value.
+        value: String,
+        /// The name of the type of the cells.
+        cell_kind: &'static str,
+    },
+}
+
+/// Returns the hash of the brainfuck operators of `bf`, ignoring any other character.
+/// Uses 64 bit FNV-1a, which is stable across platforms and versions.
+pub(super) fn program_hash(bf: &str) -> u64 {
+    bf.bytes()
+        .filter(|b| b"+-<>[],.".contains(b))
+        .fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+impl Snapshot {
+    /// Returns the hash of the program the snapshot was taken from.
+    pub fn program_hash(&self) -> u64 {
+        self.program_hash
+    }
+
+    /// Returns the decimal representation of every allocated cell of the tape.
+    pub fn tape(&self) -> &[String] {
+        &self.tape
+    }
+
+    /// Returns the position of the tape pointer.
+    pub fn tape_pointer(&self) -> usize {
+        self.tape_pointer
+    }
+
+    /// Returns the index of the next byte code to execute.
+    pub fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    /// Returns the number of byte codes executed before the snapshot was taken.
+    pub fn executed_operations(&self) -> usize {
+        self.executed_operations
+    }
+
+    /// Returns the configuration of the machine the snapshot was taken from.
+    pub(super) fn config(&self) -> &InterpreterConfig {
+        &self.config
+    }
+
+    /// Converts the snapshot into a JSON document.
+    pub fn to_json(&self) -> JsonValue {
+        let numbers = |values: &[String]| JsonValue::Array(values.iter().map(|v| JsonValue::Number(v.clone())).collect());
+        let config = &self.config;
+
+        let (tape_mode, circular_tape_lenght) = match config.tape_mode {
+            TapeMode::RightInfinite => ("right-infinite", JsonValue::Null),
+            TapeMode::Bidirectional => ("bidirectional", JsonValue::Null),
+            TapeMode::Circular(lenght) => ("circular", JsonValue::number(lenght)),
+        };
+
+        let config = JsonValue::object([
+            ("cell_kind", JsonValue::string(config.cell_kind.name())),
+            ("tape_limit", config.lenght_limit.map_or(JsonValue::Null, JsonValue::number)),
+            ("tape_mode", JsonValue::string(tape_mode)),
+            ("circular_tape_lenght", circular_tape_lenght),
+            ("overflow", JsonValue::string(overflow_name(&config.overflow_behaviour))),
+            ("eof", JsonValue::string(eof_name(&config.eof_behaviour))),
+            ("input_as_number", JsonValue::Bool(config.input_as_number)),
+            ("output_as_number", JsonValue::Bool(config.output_as_number)),
+            ("bulk_input", JsonValue::Bool(config.bulk_input)),
+            ("fast_engine", JsonValue::Bool(config.fast_engine)),
+        ]);
+
+        JsonValue::object([
+            ("version", JsonValue::number(SNAPSHOT_VERSION)),
+            // as a string, since JSON readers usually lose the precision of big numbers
+            ("program_hash", JsonValue::string(format!("{:016x}", self.program_hash))),
+            ("config", config),
+            ("tape_pointer", JsonValue::number(self.tape_pointer)),
+            ("tape_origin", JsonValue::number(self.tape_origin)),
+            ("instruction_pointer", JsonValue::number(self.instruction_pointer)),
+            ("executed_operations", JsonValue::number(self.executed_operations)),
+            ("tape", numbers(&self.tape)),
+            ("input_buffer", numbers(&self.input_buffer)),
+        ])
+    }
+
+    /// Parses a snapshot written by [`Snapshot::to_json`].
+    pub fn parse(src: &str) -> Result<Snapshot, SnapshotError> {
+        let json = JsonValue::parse(src).map_err(|e| malformed(e.to_string()))?;
+
+        let version = usize_field(&json, "version")?;
+        if version as u64 != SNAPSHOT_VERSION {
+            return Err(malformed(format!("unsupported version {version}, expected {SNAPSHOT_VERSION}")))
+        }
+
+        let program_hash = field(&json, "program_hash")?.as_str()
+            .and_then(|h| u64::from_str_radix(h, 16).ok())
+            .ok_or_else(|| malformed("`program_hash` is not an hexadecimal string"))?;
+
+        Ok(Snapshot {
+            program_hash,
+            config: parse_config(field(&json, "config")?)?,
+            tape: numbers_field(&json, "tape")?,
+            tape_pointer: usize_field(&json, "tape_pointer")?,
+            tape_origin: usize_field(&json, "tape_origin")?,
+            instruction_pointer: usize_field(&json, "instruction_pointer")?,
+            executed_operations: usize_field(&json, "executed_operations")?,
+            input_buffer: numbers_field(&json, "input_buffer")?,
+        })
+    }
+}
+
+fn malformed(reason: impl Into<String>) -> SnapshotError {
+    SnapshotError::Malformed { reason: reason.into() }
+}
+
+fn field<'a>(json: &'a JsonValue, key: &str) -> Result<&'a JsonValue, SnapshotError> {
+    json.get(key).ok_or_else(|| malformed(format!("missing `{key}`")))
+}
+
+fn usize_field(json: &JsonValue, key: &str) -> Result<usize, SnapshotError> {
+    field(json, key)?.as_number()
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| malformed(format!("`{key}` is not a positive integer")))
+}
+
+fn bool_field(json: &JsonValue, key: &str) -> Result<bool, SnapshotError> {
+    field(json, key)?.as_bool()
+        .ok_or_else(|| malformed(format!("`{key}` is not a boolean")))
+}
+
+fn str_field<'a>(json: &'a JsonValue, key: &str) -> Result<&'a str, SnapshotError> {
+    field(json, key)?.as_str()
+        .ok_or_else(|| malformed(format!("`{key}` is not a string")))
+}
+
+fn numbers_field(json: &JsonValue, key: &str) -> Result<Vec<String>, SnapshotError> {
+    field(json, key)?.as_array()
+        .and_then(|values| values.iter().map(|v| v.as_number().map(str::to_string)).collect())
+        .ok_or_else(|| malformed(format!("`{key}` is not an array of numbers")))
+}
+
+fn parse_config(json: &JsonValue) -> Result<InterpreterConfig, SnapshotError> {
+    let cell_kind = str_field(json, "cell_kind")?;
+    let cell_kind = CellKind::ALL.into_iter()
+        .find(|k| k.name() == cell_kind)
+        .ok_or_else(|| malformed(format!("unknown cell kind `{cell_kind}`")))?;
+
+    let lenght_limit = match field(json, "tape_limit")? {
+        JsonValue::Null => None,
+        _ => Some(usize_field(json, "tape_limit")?),
+    };
+
+    let tape_mode = match str_field(json, "tape_mode")? {
+        "right-infinite" => TapeMode::RightInfinite,
+        "bidirectional" => TapeMode::Bidirectional,
+        "circular" => match usize_field(json, "circular_tape_lenght")? {
+            0 => return Err(malformed("a circular tape needs at least one cell")),
+            lenght => TapeMode::Circular(lenght),
+        },
+        other => return Err(malformed(format!("unknown tape mode `{other}`"))),
+    };
+
+    let overflow = str_field(json, "overflow")?;
+    let overflow_behaviour = [OverflowBehaviour::Wrap, OverflowBehaviour::Saturate, OverflowBehaviour::Abort].into_iter()
+        .find(|o| overflow_name(o) == overflow)
+        .ok_or_else(|| malformed(format!("unknown overflow behaviour `{overflow}`")))?;
+
+    let eof = str_field(json, "eof")?;
+    let eof_behaviour = [EofBehaviour::Unchanged, EofBehaviour::Zero, EofBehaviour::Max, EofBehaviour::Abort].into_iter()
+        .find(|e| eof_name(e) == eof)
+        .ok_or_else(|| malformed(format!("unknown end of input behaviour `{eof}`")))?;
+
+    Ok(InterpreterConfig {
+        cell_kind,
+        lenght_limit,
+        tape_mode,
+        overflow_behaviour,
+        eof_behaviour,
+        input_as_number: bool_field(json, "input_as_number")?,
+        output_as_number: bool_field(json, "output_as_number")?,
+        bulk_input: bool_field(json, "bulk_input")?,
+        fast_engine: bool_field(json, "fast_engine")?,
+        ..InterpreterConfig::default()
+    })
+}
+
+fn overflow_name(overflow: &OverflowBehaviour) -> &'static str {
+    match overflow {
+        OverflowBehaviour::Wrap => "wrap",
+        OverflowBehaviour::Saturate => "saturate",
+        OverflowBehaviour::Abort => "abort",
+    }
+}
+
+fn eof_name(eof: &EofBehaviour) -> &'static str {
+    match eof {
+        EofBehaviour::Unchanged => "unchanged",
+        EofBehaviour::Zero => "zero",
+        EofBehaviour::Max => "max",
+        EofBehaviour::Abort => "abort",
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: NumOpsPlus + TryFrom<i8>> Interpreter<T>
+where <T as TryFrom<i8>>::Error: Debug {
+    /// Takes a snapshot of the current state.
+    pub(super) fn snapshot(&self) -> Snapshot {
+        let config = &self.config;
+
+        Snapshot {
+            program_hash: config.program_hash,
+            config: InterpreterConfig {
+                cell_kind: config.cell_kind.clone(),
+                lenght_limit: config.lenght_limit,
+                tape_mode: config.tape_mode.clone(),
+                overflow_behaviour: config.overflow_behaviour.clone(),
+                eof_behaviour: config.eof_behaviour.clone(),
+                input_as_number: config.input_as_number,
+                output_as_number: config.output_as_number,
+                bulk_input: config.bulk_input,
+                fast_engine: config.fast_engine,
+                ..InterpreterConfig::default()
+            },
+            tape: self.tape.iter().map(|c| format!("{c:?}")).collect(),
+            tape_pointer: self.tape_pointer,
+            tape_origin: self.tape_origin,
+            instruction_pointer: self.instruction_pointer,
+            executed_operations: self.executed_operations,
+            input_buffer: self.input_buffer.iter().map(|c| format!("{c:?}")).collect(),
+        }
+    }
+
+    /// Restores the state of `snapshot`, the configuration of the interpreter is kept.
+    /// Errors if the snapshot was taken from another program, from another cell type or from another engine.
+    pub(super) fn restore_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        if snapshot.program_hash != self.config.program_hash {
+            return Err(SnapshotError::ProgramMismatch { snapshot: snapshot.program_hash, program: self.config.program_hash })
+        }
+
+        if snapshot.config.cell_kind != self.config.cell_kind {
+            return Err(SnapshotError::CellKindMismatch {
+                snapshot: snapshot.config.cell_kind.name(),
+                interpreter: self.config.cell_kind.name(),
+            })
+        }
+
+        if snapshot.config.fast_engine != self.config.fast_engine {
+            return Err(SnapshotError::EngineMismatch)
+        }
+
+        if snapshot.instruction_pointer > self.instructions.len() {
+            return Err(malformed("the instruction pointer is past the end of the program"))
+        }
+        if snapshot.tape_origin > snapshot.tape.len() {
+            return Err(malformed("the tape origin is past the end of the tape"))
+        }
+        // the tape is only allocated up to the cells accessed, the pointer may have moved past them
+        let in_tape = match self.config.tape_mode {
+            TapeMode::Circular(lenght) => snapshot.tape_pointer < lenght,
+            _ => snapshot.tape_pointer <= snapshot.tape.len().saturating_add(self.reach_without_access()),
+        };
+        if !in_tape {
+            return Err(malformed("the tape pointer is past the end of the tape"))
+        }
+
+        let cell_kind = self.config.cell_kind.name();
+        let parse = |value: &String| value.parse::<T>()
+            .map_err(|_| SnapshotError::InvalidValue { value: value.clone(), cell_kind });
+        let tape = snapshot.tape.iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+        let input_buffer = snapshot.input_buffer.iter().map(parse).collect::<Result<VecDeque<_>, _>>()?;
+
+        self.tape = tape;
+        self.input_buffer = input_buffer;
+        self.tape_pointer = snapshot.tape_pointer;
+        self.tape_origin = snapshot.tape_origin;
+        self.instruction_pointer = snapshot.instruction_pointer;
+        self.executed_operations = snapshot.executed_operations;
+        self.started_at = None;
+        self.input_wait = Duration::ZERO;
+
+        Ok(())
+    }
+
+    /// Returns how far right of the cells accessed the program can move the tape pointer.
+    /// Loops access the cell they check, so it is at most all the moves to the right of the program.
+    fn reach_without_access(&self) -> usize {
+        self.instructions.iter()
+            .map(|bc| match bc {
+                ByteCode::PointerAdd(n) => usize::try_from(*n).unwrap_or(0),
+                ByteCode::Move(n) => usize::try_from(*n).unwrap_or(0),
+                _ => 0,
+            })
+            .fold(0, usize::saturating_add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::{InterpreterBuilder, InterpreterError, SharedBuffer};
+
+    use super::*;
+
+    #[test]
+    fn snapshot_round_trip() {
+        let program = include_str!("../../test-resources/fib.bf");
+        let build = |output: &SharedBuffer| InterpreterBuilder::new(program)
+            .with_u16()
+            .with_bidirectional_tape()
+            .with_output(output.clone());
+
+        let full_output = SharedBuffer::new();
+        let mut full = build(&full_output).finish();
+        full.complete().unwrap();
+
+        // stops part way, and resumes from a snapshot written to JSON
+        let output = SharedBuffer::new();
+        let mut first = build(&output).with_step_limit(500).finish();
+        assert!(matches!(first.complete(), Err(InterpreterError::StepLimitExceded { .. })));
+        let json = first.snapshot().to_json().to_string();
+
+        let snapshot = Snapshot::parse(&json).unwrap();
+        assert_eq!(snapshot, first.snapshot());
+        assert_eq!(snapshot.executed_operations(), 500);
+
+        // the configuration comes from the snapshot
+        let mut resumed = InterpreterBuilder::new(program)
+            .with_output(output.clone())
+            .resume(&snapshot)
+            .unwrap();
+        resumed.complete().unwrap();
+
+        assert_eq!(output.contents(), full_output.contents());
+        assert_eq!(resumed.report(None), full.report(None));
+    }
+
+    #[test]
+    fn snapshot_input_buffer() {
+        let mut first = InterpreterBuilder::new(",>,>,")
+            .with_i32()
+            .with_input_as_number()
+            .with_bulk_input()
+            .with_input(SharedBuffer::from_bytes("-7 300 12\n"))
+            .with_step_limit(2)
+            .finish();
+        assert!(first.complete().is_err());
+
+        // the values which were buffered are not read from the input again
+        let mut resumed = InterpreterBuilder::new(",>,>,")
+            .resume(&first.snapshot())
+            .unwrap();
+        resumed.complete().unwrap();
+        assert_eq!(resumed.report(None).tape, vec!["-7", "300", "12"]);
+    }
+
+    #[test]
+    fn snapshot_rejection() {
+        let mut inter = InterpreterBuilder::new("+++[>+<-]").with_step_limit(4).finish();
+        let _ = inter.complete();
+        let snapshot = inter.snapshot();
+
+        // text is not part of the program
+        assert!(InterpreterBuilder::new("+ + + [ loop > + < - ]").resume(&snapshot).is_ok());
+        assert!(matches!(
+            InterpreterBuilder::new("+++[>++<-]").resume(&snapshot),
+            Err(SnapshotError::ProgramMismatch { .. })
+        ));
+
+        let mut other = InterpreterBuilder::new("+++[>+<-]").with_i16().finish();
+        assert!(matches!(other.restore_snapshot(&snapshot), Err(SnapshotError::CellKindMismatch { snapshot: "u8", interpreter: "i16" })));
+
+        assert!(matches!(Snapshot::parse("{}"), Err(SnapshotError::Malformed { .. })));
+        assert!(matches!(Snapshot::parse("not json"), Err(SnapshotError::Malformed { .. })));
+        let json = snapshot.to_json().to_string().replace("\"tape\":[3,1]", "\"tape\":[3,256]");
+        assert!(matches!(
+            InterpreterBuilder::new("+++[>+<-]").resume(&Snapshot::parse(&json).unwrap()),
+            Err(SnapshotError::InvalidValue { cell_kind: "u8", .. })
+        ));
+
+        // the tape pointer can only be past the tape by as much as the program moves right
+        let json = snapshot.to_json().to_string().replace("\"tape_pointer\":1,", "\"tape_pointer\":3,");
+        assert!(InterpreterBuilder::new("+++[>+<-]").resume(&Snapshot::parse(&json).unwrap()).is_ok());
+        let json = snapshot.to_json().to_string().replace("\"tape_pointer\":1,", "\"tape_pointer\":1000000000000,");
+        assert!(matches!(
+            InterpreterBuilder::new("+++[>+<-]").resume(&Snapshot::parse(&json).unwrap()),
+            Err(SnapshotError::Malformed { .. })
+        ));
+        let mut circular = InterpreterBuilder::new("+++[>+<-]").with_circular_tape(2).with_step_limit(4).finish();
+        let _ = circular.complete();
+        let json = circular.snapshot().to_json().to_string().replace("\"tape_pointer\":1,", "\"tape_pointer\":2,");
+        assert!(matches!(
+            InterpreterBuilder::new("+++[>+<-]").resume(&Snapshot::parse(&json).unwrap()),
+            Err(SnapshotError::Malformed { .. })
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+//! Typed access to the state of the interpreter, independent of the type of its cells.
+
+use std::{fmt::Debug, time::Duration};
+
+use super::{Interpreter, InterpreterError, NumOpsPlus};
+
+#[allow(private_bounds)]
+impl<T: NumOpsPlus + TryFrom<i8>> Interpreter<T>
+where <T as TryFrom<i8>>::Error: Debug {
+    /// Returns the value of the cell at `index` as a `i64`.
+    /// Cells which were not allocated yet are 0.
+    /// Returns `None` if the value cannot be represented as a `i64`.
+    pub(super) fn cell_i64(&self, index: usize) -> Option<i64> {
+        match self.tape.get(index) {
+            Some(cell) => cell.to_i64(),
+            None => Some(0),
+        }
+    }
+
+    /// Returns the value of the cell at `index` as a `u64`.
+    /// Cells which were not allocated yet are 0.
+    /// Returns `None` if the value cannot be represented as a `u64`. (e.g: it is negative)
+    pub(super) fn cell_u64(&self, index: usize) -> Option<u64> {
+        match self.tape.get(index) {
+            Some(cell) => cell.to_u64(),
+            None => Some(0),
+        }
+    }
+
+    /// Sets the value of the cell at `index`, allocating the tape up to it if needed.
+    /// `value_str` gives the textual representation of the value for error reporting.
+    fn set_cell(&mut self, index: usize, value: Option<T>, value_str: impl FnOnce() -> String) -> Result<(), InterpreterError> {
+        let Some(value) = value else {
+            return Err(InterpreterError::ValueOutOfRange {
+                value: value_str(),
+                cell_kind: self.config.cell_kind.name(),
+            })
+        };
+
+        *self.get_mut_cell_at_or_insert_default(index)? = value;
+
+        Ok(())
+    }
+
+    /// Sets the value of the cell at `index` from a `i64`, allocating the tape up to it if needed.
+    pub(super) fn set_cell_i64(&mut self, index: usize, value: i64) -> Result<(), InterpreterError> {
+        self.set_cell(index, T::from(value), || value.to_string())
+    }
+
+    /// Sets the value of the cell at `index` from a `u64`, allocating the tape up to it if needed.
+    pub(super) fn set_cell_u64(&mut self, index: usize, value: u64) -> Result<(), InterpreterError> {
+        self.set_cell(index, T::from(value), || value.to_string())
+    }
+
+    /// Puts the interpreter back in the state it was built in.
+    /// The configuration and the input and output streams are kept.
+    pub(super) fn reset(&mut self) {
+        self.tape.clear();
+        self.tape_pointer = 0;
+        self.tape_origin = 0;
+        self.instruction_pointer = 0;
+        self.executed_operations = 0;
+        self.started_at = None;
+        self.input_wait = Duration::ZERO;
+        self.input_buffer.clear();
+
+        if let Some(counts) = &mut self.execution_counts {
+            counts.fill(0);
+        }
+        if let Some(captured) = &mut self.captured_output {
+            captured.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::{InterpreterBuilder, InterpreterError, SharedBuffer};
+
+    #[test]
+    fn typed_state_access() {
+        let mut inter = InterpreterBuilder::new("[-]>-")
+            .with_i8()
+            .finish();
+
+        inter.set_cell_i64(0, 100).unwrap();
+        inter.set_cell_i64(2, -5).unwrap();
+        assert_eq!(inter.tape_len(), 3);
+        assert_eq!(inter.cell_i64(2), Some(-5));
+        assert_eq!(inter.cell_u64(2), None);
+        assert_eq!(inter.cell_i64(50), Some(0));
+        assert_eq!(
+            inter.set_cell_i64(0, 200),
+            Err(InterpreterError::ValueOutOfRange { value: "200".to_string(), cell_kind: "i8" })
+        );
+
+        inter.complete().unwrap();
+        assert_eq!(inter.cell_i64(0), Some(0));
+        assert_eq!(inter.cell_i64(1), Some(-1));
+        assert_eq!(inter.tape_pointer(), 1);
+
+        inter.reset();
+        assert_eq!(inter.tape_len(), 0);
+        assert_eq!(inter.tape_pointer(), 0);
+        assert_eq!(inter.instruction_pointer(), 0);
+
+        inter.set_tape_pointer(2).unwrap();
+        inter.complete().unwrap();
+        assert_eq!(inter.cell_i64(3), Some(-1));
+
+        // the tape pointer stays within circular tapes and tape limits
+        let mut inter = InterpreterBuilder::new("+").with_circular_tape(3).finish();
+        assert_eq!(inter.set_tape_pointer(3), Err(InterpreterError::TapeLimitExceded { limit: 3, tried: 4 }));
+        assert_eq!(inter.tape_pointer(), 0);
+        inter.set_tape_pointer(2).unwrap();
+        inter.complete().unwrap();
+        assert_eq!(inter.cell_i64(2), Some(1));
+        let mut inter = InterpreterBuilder::new("+").with_tape_leght(5).finish();
+        assert_eq!(inter.set_tape_pointer(5), Err(InterpreterError::TapeLimitExceded { limit: 5, tried: 6 }));
+        assert_eq!(inter.set_tape_pointer(4), Ok(()));
+    }
+
+    #[test]
+    fn running_until() {
+        let output = SharedBuffer::new();
+        // byte codes:                          +++ [ . - ]
+        let mut inter = InterpreterBuilder::new("+++[.-]")
+            .with_output(output.clone())
+            .with_output_as_number()
+            .finish();
+
+        assert_eq!(inter.run_until_instruction(1), Ok(true));
+        assert_eq!(inter.instruction_pointer(), 1);
+        assert_eq!(inter.cell_u64(0), Some(3));
+
+        assert_eq!(inter.run_until_output(), Ok(true));
+        assert_eq!(output.contents_lossy(), "3 ");
+        assert_eq!(inter.run_until_output(), Ok(true));
+        assert_eq!(output.contents_lossy(), "3 2 ");
+
+        // the loop goes back to the `.` and not the `[`
+        assert_eq!(inter.run_until_instruction(1), Ok(false));
+        assert_eq!(output.contents_lossy(), "3 2 1 ");
+        assert_eq!(inter.run_until_output(), Ok(false));
+    }
+}
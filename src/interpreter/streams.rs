@@ -0,0 +1,216 @@
+//! The input and output streams of the interpreter.
+//!
+//! By default, the interpreter reads from stdin and writes to stdout.
+//! Any [`Read`] or [`Write`] implementor (files, [`SharedBuffer`], byte slices, ...)
+//! can be used instead via [`InterpreterBuilder::with_input`](super::InterpreterBuilder::with_input)
+//! and [`InterpreterBuilder::with_output`](super::InterpreterBuilder::with_output).
+
+use std::{cell::RefCell, fmt::{self, Debug}, io::{self, BufRead, BufReader, Read, Write}, rc::Rc};
+
+use super::{trace::TraceRecorder, InterpreterError};
+
+/// An in-memory buffer which can be given to the interpreter while still being accessible from the outside.
+/// Clones of a [`SharedBuffer`] refer to the same buffer.
+///
+/// When read from, bytes are consumed from the start of the buffer.
+#[derive(Debug, Clone, Default)]
+pub struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+    /// Creates a new empty [`SharedBuffer`].
+    pub fn new() -> SharedBuffer {
+        SharedBuffer::default()
+    }
+
+    /// Creates a [`SharedBuffer`] containing `bytes`.
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> SharedBuffer {
+        SharedBuffer(Rc::new(RefCell::new(bytes.into())))
+    }
+
+    /// Returns a copy of the content of the buffer.
+    pub fn contents(&self) -> Vec<u8> {
+        self.0.borrow().clone()
+    }
+
+    /// Returns the content of the buffer as a string, invalid UTF-8 is replaced by `�`.
+    pub fn contents_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.0.borrow()).to_string()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for SharedBuffer {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut inner = self.0.borrow_mut();
+        let len = buf.len().min(inner.len());
+        buf[..len].copy_from_slice(&inner[..len]);
+        inner.drain(..len);
+
+        Ok(len)
+    }
+}
+
+/// The streams the interpreter communicates through.
+/// Clones share the same streams, see [`InputStream`] and [`OutputStream`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(super) struct Streams {
+    pub(super) input: InputStream,
+    pub(super) output: OutputStream,
+    pub(super) trace: Option<TraceRecorder>,
+}
+
+/// Where the interpreter takes its input from.
+/// Clones of an [`InputStream`] read from the same reader, and are equal to each other.
+#[derive(Clone)]
+pub(super) struct InputStream(Rc<RefCell<dyn BufRead>>);
+
+impl InputStream {
+    pub(super) fn new(reader: impl Read + 'static) -> InputStream {
+        InputStream(Rc::new(RefCell::new(BufReader::new(reader))))
+    }
+
+    /// Reads a line, without the trailing newline.
+    /// Returns `None` at the end of the input (or if it could not be read).
+    pub(super) fn read_line(&mut self) -> Option<String> {
+        let mut buf = String::new();
+        match self.0.borrow_mut().read_line(&mut buf) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => (),
+        }
+
+        // remove the extra newlines that comes with entering a line
+        if buf.ends_with('\n') {
+            let _ = buf.pop();
+        }
+
+        Some(buf)
+    }
+
+    /// Reads a single byte.
+    /// Returns `None` at the end of the input (or if it could not be read).
+    pub(super) fn read_byte(&mut self) -> Option<u8> {
+        let mut byte = [0];
+        loop {
+            match self.0.borrow_mut().read(&mut byte) {
+                Ok(0) => return None,
+                Ok(_) => return Some(byte[0]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+impl Default for InputStream {
+    fn default() -> Self {
+        InputStream::new(io::stdin())
+    }
+}
+
+impl PartialEq for InputStream {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for InputStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputStream").finish_non_exhaustive()
+    }
+}
+
+/// Where the interpreter writes its output to.
+/// Clones of an [`OutputStream`] write to the same writer, and are equal to each other.
+#[derive(Clone)]
+pub(super) struct OutputStream(Rc<RefCell<dyn Write>>);
+
+impl OutputStream {
+    pub(super) fn new(writer: impl Write + 'static) -> OutputStream {
+        OutputStream(Rc::new(RefCell::new(writer)))
+    }
+
+    /// Writes and flushes `s`.
+    pub(super) fn write_str(&mut self, s: &str) -> Result<(), InterpreterError> {
+        let mut writer = self.0.borrow_mut();
+        writer.write_all(s.as_bytes())
+            .and_then(|()| writer.flush())
+            .map_err(|e| InterpreterError::OutputWriteFailed { reason: e.to_string() })
+    }
+}
+
+impl Default for OutputStream {
+    fn default() -> Self {
+        OutputStream::new(io::stdout())
+    }
+}
+
+impl PartialEq for OutputStream {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for OutputStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutputStream").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::InterpreterBuilder;
+
+    use super::*;
+
+    #[test]
+    fn interpreter_custom_streams() {
+        let output = SharedBuffer::new();
+        let mut inter = InterpreterBuilder::new(",[.,]")
+            .with_input(SharedBuffer::from_bytes("cats"))
+            .with_output(output.clone())
+            .with_streamed_input()
+            .with_eof_as_zero()
+            .with_output_capture()
+            .finish();
+        inter.complete().unwrap();
+
+        assert_eq!(output.contents_lossy(), "cats");
+        assert_eq!(inter.captured_output(), Some("cats"));
+
+        // interactive input reads lines, and prompts on stderr rather than in the output
+        let output = SharedBuffer::new();
+        let mut inter = InterpreterBuilder::new(",>,<.>.")
+            .with_input("4 2\n".as_bytes())
+            .with_output(output.clone())
+            .with_input_as_number()
+            .with_output_as_number()
+            .with_bulk_input()
+            .finish();
+        inter.complete().unwrap();
+
+        assert_eq!(output.contents_lossy(), "4 2 ");
+        assert_eq!(inter.captured_output(), None);
+
+        // clones share the streams
+        let output = SharedBuffer::new();
+        let builder = InterpreterBuilder::new(",.")
+            .with_input(SharedBuffer::from_bytes("ab"))
+            .with_output(output.clone())
+            .with_streamed_input();
+        assert_eq!(builder.clone(), builder);
+        builder.clone().finish().complete().unwrap();
+        builder.finish().complete().unwrap();
+
+        assert_eq!(output.contents_lossy(), "ab");
+    }
+}
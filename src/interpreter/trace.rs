@@ -0,0 +1,298 @@
+//! Recording of every step taken by the interpreter into a line-based trace.
+//!
+//! Each executed byte code is written as one line of space separated fields:
+//! `step instruction_pointer bf_offset operator tape_pointer before after`,
+//! where `before` and `after` are the values of the cell under the tape pointer around the execution.
+//! The tape pointer is relative to the cell the program started on, so it is negative left of it on a bidirectional tape.
+//! Lines starting with `#` are comments.
+
+use std::{cell::RefCell, fmt::{self, Debug, Display}, io::{self, BufRead, BufWriter, Write}, ops::Range, rc::Rc};
+
+use thiserror::Error;
+
+use super::{Interpreter, InterpreterError, NumOpsPlus};
+
+/// The comment line at the start of every trace, describing the fields.
+pub const TRACE_HEADER: &str = "# step instruction_pointer bf_offset operator tape_pointer before after";
+
+/// A single step of a trace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    /// The number of byte codes executed before this one.
+    pub step: usize,
+    /// The index of the executed byte code.
+    pub instruction_pointer: usize,
+    /// The offset in bytes of the byte code in the brainfuck source.
+    pub bf_offset: usize,
+    /// The operator of the byte code.
+    pub operator: char,
+    /// The position of the tape pointer before the execution, relative to the cell the program started on.
+    pub tape_pointer: isize,
+    /// The decimal value of the cell under the tape pointer before the execution.
+    pub before: String,
+    /// The decimal value of the same cell after the execution.
+    pub after: String,
+}
+
+impl TraceEntry {
+    /// Parses a line of a trace.
+    /// Returns `None` if the line is malformed.
+    pub fn parse(line: &str) -> Option<TraceEntry> {
+        let mut fields = line.split_whitespace();
+
+        let entry = TraceEntry {
+            step: fields.next()?.parse().ok()?,
+            instruction_pointer: fields.next()?.parse().ok()?,
+            bf_offset: fields.next()?.parse().ok()?,
+            operator: fields.next()?.parse().ok()?,
+            tape_pointer: fields.next()?.parse().ok()?,
+            before: fields.next()?.to_string(),
+            after: fields.next()?.to_string(),
+        };
+
+        if fields.next().is_some() {
+            return None
+        }
+
+        Some(entry)
+    }
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            self.step,
+            self.instruction_pointer,
+            self.bf_offset,
+            self.operator,
+            self.tape_pointer,
+            self.before,
+            self.after,
+        )
+    }
+}
+
+/// Filter over the entries of a trace.
+/// An entry is kept if it matches all of the set criteria.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TraceFilter {
+    /// Keeps only the steps which operated on a cell within this range.
+    pub cells: Option<Range<isize>>,
+    /// Keeps only the steps which executed a byte code within this range.
+    pub instructions: Option<Range<usize>>,
+}
+
+impl TraceFilter {
+    /// Returns `true` if the entry should be kept.
+    pub fn matches(&self, entry: &TraceEntry) -> bool {
+        let cell_matches = self.cells.as_ref()
+            .is_none_or(|r| r.contains(&entry.tape_pointer));
+        let instruction_matches = self.instructions.as_ref()
+            .is_none_or(|r| r.contains(&entry.instruction_pointer));
+
+        cell_matches && instruction_matches
+    }
+}
+
+/// An error caught while reading a trace.
+#[derive(Debug, Error)]
+pub enum TraceReadError {
+    /// The trace could not be read.
+    #[error("failed to read the trace: {0}")]
+    Io(#[from] io::Error),
+    /// A line of the trace is not a valid entry.
+    #[error("line {line} of the trace is malformed")]
+    Malformed {
+        /// The line number of the malformed line, starting at 1.
+        line: usize,
+    },
+}
+
+/// Reads the entries of a trace matching the `filter`, one line at a time.
+/// The iterator yields an error for each line which could not be read or parsed.
+pub fn read_trace(reader: impl BufRead, filter: TraceFilter) -> impl Iterator<Item = Result<TraceEntry, TraceReadError>> {
+    reader.lines().enumerate().filter_map(move |(i, line)| {
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => return Some(Err(e.into())),
+        };
+        if line.starts_with('#') || line.trim().is_empty() {
+            return None
+        }
+
+        match TraceEntry::parse(&line) {
+            Some(entry) if filter.matches(&entry) => Some(Ok(entry)),
+            Some(_) => None,
+            None => Some(Err(TraceReadError::Malformed { line: i+1 })),
+        }
+    })
+}
+
+/// Where the steps of the interpreter get written to when tracing.
+/// Clones of a [`TraceRecorder`] write to the same trace, and are equal to each other.
+#[derive(Clone)]
+pub(super) struct TraceRecorder(Rc<RefCell<TraceOutput>>);
+
+struct TraceOutput {
+    output: BufWriter<Box<dyn Write>>,
+    wrote_header: bool,
+}
+
+impl TraceRecorder {
+    pub(super) fn new(output: Box<dyn Write>) -> TraceRecorder {
+        TraceRecorder(Rc::new(RefCell::new(TraceOutput {
+            output: BufWriter::new(output),
+            wrote_header: false,
+        })))
+    }
+
+    fn write_entry(&mut self, entry: &TraceEntry) -> io::Result<()> {
+        let trace = &mut *self.0.borrow_mut();
+        if !trace.wrote_header {
+            writeln!(trace.output, "{TRACE_HEADER}")?;
+            trace.wrote_header = true;
+        }
+
+        writeln!(trace.output, "{entry}")
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.borrow_mut().output.flush()
+    }
+}
+
+impl PartialEq for TraceRecorder {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for TraceRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceRecorder")
+            .field("wrote_header", &self.0.borrow().wrote_header)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(private_bounds)]
+impl<T: NumOpsPlus> Interpreter<T> {
+    /// Returns the value of the cell under the tape pointer, if the interpreter is tracing.
+    /// Does not allocate the cell.
+    pub(super) fn trace_cell_value(&self) -> Option<T> {
+        self.trace.as_ref()?;
+
+        Some(self.tape.get(self.tape_pointer).cloned().unwrap_or_default())
+    }
+
+    /// Writes the step which executed the byte code at `instruction_pointer` to the trace.
+    /// `tape_pointer` is the logical index of the cell under the tape pointer before the execution,
+    /// and `before` the value of that cell.
+    pub(super) fn record_step(&mut self, instruction_pointer: usize, tape_pointer: isize, before: &T) -> Result<(), InterpreterError> {
+        // the step may have grown the tape to the left, moving the cell
+        let after = self.tape_origin.checked_add_signed(tape_pointer)
+            .and_then(|index| self.tape.get(index))
+            .cloned()
+            .unwrap_or_default();
+        let Some(trace) = &mut self.trace else {
+            return Ok(())
+        };
+
+        let operator = self.instructions[instruction_pointer].operator();
+
+        let entry = TraceEntry {
+            step: self.executed_operations,
+            instruction_pointer,
+            bf_offset: self.offsets[instruction_pointer],
+            operator,
+            tape_pointer,
+            before: format!("{before:?}"),
+            after: format!("{after:?}"),
+        };
+
+        trace.write_entry(&entry)
+            .map_err(|e| InterpreterError::TraceWriteFailed { reason: e.to_string() })
+    }
+
+    /// Writes the buffered steps to the trace output.
+    pub(super) fn flush_trace(&mut self) -> Result<(), InterpreterError> {
+        let Some(trace) = &mut self.trace else {
+            return Ok(())
+        };
+
+        trace.flush()
+            .map_err(|e| InterpreterError::TraceWriteFailed { reason: e.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::{InterpreterBuilder, SharedBuffer};
+
+    use super::*;
+
+    #[test]
+    fn trace_recording() {
+        let buffer = SharedBuffer::default();
+        let mut inter = InterpreterBuilder::new("++>+<[->+<]")
+            .with_trace(buffer.clone())
+            .finish();
+        inter.complete().unwrap();
+
+        let trace = buffer.contents_lossy();
+        let mut lines = trace.lines();
+        assert_eq!(lines.next(), Some(TRACE_HEADER));
+        assert_eq!(lines.next(), Some("0 0 0 + 0 0 2"));
+        assert_eq!(lines.next(), Some("1 1 2 > 0 2 2"));
+        assert_eq!(lines.next(), Some("2 2 3 + 1 0 1"));
+
+        let all = read_trace(trace.as_bytes(), TraceFilter::default()).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(all.len(), inter.report(None).executed_operations);
+
+        // only the steps operating on the second cell
+        let filter = TraceFilter { cells: Some(1..2), ..Default::default() };
+        let second_cell = read_trace(trace.as_bytes(), filter).collect::<Result<Vec<_>, _>>().unwrap();
+        assert!(second_cell.iter().all(|e| e.tape_pointer == 1));
+        assert_eq!(second_cell.last().unwrap().after, "3");
+
+        // only the `-` of the loop
+        let filter = TraceFilter { instructions: Some(5..6), ..Default::default() };
+        let decrements = read_trace(trace.as_bytes(), filter.clone()).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(decrements.len(), 2);
+        assert_eq!(decrements[1].before, "1");
+        assert_eq!(decrements[1].after, "0");
+
+        let mut malformed = read_trace("0 0 0 +".as_bytes(), filter);
+        assert!(matches!(malformed.next(), Some(Err(TraceReadError::Malformed { line: 1 }))));
+    }
+
+    #[test]
+    fn trace_bidirectional() {
+        let buffer = SharedBuffer::default();
+        let mut inter = InterpreterBuilder::new("+<-<--")
+            .with_bidirectional_tape()
+            .with_i8()
+            .with_trace(buffer.clone())
+            .finish();
+        inter.complete().unwrap();
+
+        // the tape grows to the left on each `<`, moving the cells already on it
+        let trace = buffer.contents_lossy();
+        let entries = read_trace(trace.as_bytes(), TraceFilter::default()).collect::<Result<Vec<_>, _>>().unwrap();
+        let steps = entries.iter()
+            .map(|e| (e.operator, e.tape_pointer, e.before.as_str(), e.after.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(steps, [
+            ('+', 0, "0", "1"),
+            ('<', 0, "1", "1"),
+            ('-', -1, "0", "-1"),
+            ('<', -1, "-1", "-1"),
+            ('-', -2, "0", "-2"),
+        ]);
+
+        let filter = TraceFilter { cells: Some(-2..0), ..Default::default() };
+        assert_eq!(read_trace(trace.as_bytes(), filter).count(), 3);
+    }
+}
@@ -0,0 +1,319 @@
+//! A minimal JSON document model.
+//! Used to write machine-readable reports and read back snapshots without pulling in a serialisation crate.
+
+use std::{fmt::{self, Display, Write as _}, iter::Peekable, str::CharIndices};
+
+use thiserror::Error;
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    /// The `null` literal.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A number, stored in its textual (decimal) representation.
+    /// This allows numbers of any size (like big integer cells) to be represented.
+    Number(String),
+    /// A string, escaped when written.
+    String(String),
+    /// An ordered list of values.
+    Array(Vec<JsonValue>),
+    /// An object, the order of the fields is kept when written.
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Creates a [`JsonValue::Number`] from anything that displays as a number.
+    pub fn number(n: impl Display) -> JsonValue {
+        JsonValue::Number(n.to_string())
+    }
+
+    /// Creates a [`JsonValue::String`].
+    pub fn string(s: impl Into<String>) -> JsonValue {
+        JsonValue::String(s.into())
+    }
+
+    /// Creates a [`JsonValue::Object`] from `(key, value)` pairs.
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, JsonValue)>) -> JsonValue {
+        JsonValue::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    /// Parses a JSON document.
+    pub fn parse(src: &str) -> Result<JsonValue, JsonParseError> {
+        let mut parser = Parser { chars: src.char_indices().peekable() };
+        let value = parser.value()?;
+
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(value),
+            Some((at, _)) => Err(JsonParseError { at, expected: "the end of the document" }),
+        }
+    }
+
+    /// Returns the value of the field `key`, if this is an object containing it.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        let JsonValue::Object(fields) = self else { return None };
+        fields.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Returns the string, if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        if let JsonValue::String(s) = self { Some(s) } else { None }
+    }
+
+    /// Returns the textual representation of the number, if this is a number.
+    pub fn as_number(&self) -> Option<&str> {
+        if let JsonValue::Number(n) = self { Some(n) } else { None }
+    }
+
+    /// Returns the boolean, if this is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        if let JsonValue::Bool(b) = self { Some(*b) } else { None }
+    }
+
+    /// Returns the values, if this is an array.
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        if let JsonValue::Array(values) = self { Some(values) } else { None }
+    }
+}
+
+/// An error encountered while parsing a JSON document.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("malformed JSON at byte {at}, expected {expected}")]
+pub struct JsonParseError {
+    /// The offset in bytes of the unexpected character (or the lenght of the document if it ended early).
+    pub at: usize,
+    /// What was expected instead.
+    pub expected: &'static str,
+}
+
+/// A recursive descent parser over the characters of a document.
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_ascii_whitespace()).is_some() {}
+    }
+
+    /// Returns an error at the current position.
+    fn error(&mut self, expected: &'static str) -> JsonParseError {
+        let at = self.chars.peek().map_or(usize::MAX, |(i, _)| *i);
+        JsonParseError { at, expected }
+    }
+
+    /// Consumes `literal`, which is expected to be next.
+    fn expect_literal(&mut self, literal: &'static str) -> Result<(), JsonParseError> {
+        for expected in literal.chars() {
+            if self.chars.next_if(|(_, c)| *c == expected).is_none() {
+                return Err(self.error(literal))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonParseError> {
+        self.skip_whitespace();
+        match self.chars.peek().map(|(_, c)| *c) {
+            Some('n') => self.expect_literal("null").map(|()| JsonValue::Null),
+            Some('t') => self.expect_literal("true").map(|()| JsonValue::Bool(true)),
+            Some('f') => self.expect_literal("false").map(|()| JsonValue::Bool(false)),
+            Some('"') => self.string().map(JsonValue::String),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(c) if c == '-' || c.is_ascii_digit() => Ok(self.number()),
+            _ => Err(self.error("a value")),
+        }
+    }
+
+    fn number(&mut self) -> JsonValue {
+        let mut number = String::new();
+        while let Some((_, c)) = self.chars.next_if(|(_, c)| matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+            number.push(c);
+        }
+
+        JsonValue::Number(number)
+    }
+
+    fn string(&mut self) -> Result<String, JsonParseError> {
+        self.expect_literal("\"")?;
+
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(string),
+                Some((_, '\\')) => {
+                    let escaped = match self.chars.next() {
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, '/')) => '/',
+                        Some((_, 'n')) => '\n',
+                        Some((_, 'r')) => '\r',
+                        Some((_, 't')) => '\t',
+                        Some((_, 'b')) => '\u{8}',
+                        Some((_, 'f')) => '\u{c}',
+                        Some((_, 'u')) => {
+                            let mut code = 0;
+                            for _ in 0..4 {
+                                let digit = self.chars.next()
+                                    .and_then(|(_, c)| c.to_digit(16))
+                                    .ok_or_else(|| self.error("an hexadecimal digit"))?;
+                                code = code * 16 + digit;
+                            }
+                            // surrogate pairs are not supported, since we never write them
+                            char::from_u32(code).unwrap_or('\u{fffd}')
+                        },
+                        _ => return Err(self.error("an escape sequence")),
+                    };
+                    string.push(escaped);
+                },
+                Some((_, c)) => string.push(c),
+                None => return Err(self.error("the end of the string")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonParseError> {
+        self.expect_literal("[")?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(JsonValue::Array(values))
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => (),
+                Some((_, ']')) => return Ok(JsonValue::Array(values)),
+                _ => return Err(self.error("`,` or `]`")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonParseError> {
+        self.expect_literal("{")?;
+        let mut fields = Vec::new();
+
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(JsonValue::Object(fields))
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect_literal(":")?;
+            fields.push((key, self.value()?));
+
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => (),
+                Some((_, '}')) => return Ok(JsonValue::Object(fields)),
+                _ => return Err(self.error("`,` or `}`")),
+            }
+        }
+    }
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Bool(b) => write!(f, "{b}"),
+            JsonValue::Number(n) => f.write_str(n),
+            JsonValue::String(s) => write_escaped(f, s),
+            JsonValue::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            },
+            JsonValue::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(',')?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            },
+        }
+    }
+}
+
+/// Writes `s` as a quoted and escaped JSON string.
+fn write_escaped(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for ch in s.chars() {
+        match ch {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_writing() {
+        let value = JsonValue::object([
+            ("tape", JsonValue::Array(vec![JsonValue::number(0), JsonValue::number(144)])),
+            ("error", JsonValue::Null),
+            ("done", JsonValue::Bool(true)),
+            ("text", JsonValue::string("a \"quoted\"\n\u{1}")),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            r#"{"tape":[0,144],"error":null,"done":true,"text":"a \"quoted\"\n\u0001"}"#
+        );
+    }
+
+    #[test]
+    fn json_parsing() {
+        let value = JsonValue::object([
+            ("tape", JsonValue::Array(vec![JsonValue::number(-3), JsonValue::number("123456789012345678901234567890")])),
+            ("empty", JsonValue::Array(Vec::new())),
+            ("error", JsonValue::Null),
+            ("done", JsonValue::Bool(false)),
+            ("text", JsonValue::string("a \"quoted\"\n\u{1}é")),
+            ("nested", JsonValue::object([])),
+        ]);
+
+        // parsing what we write gives back the same value
+        assert_eq!(JsonValue::parse(&value.to_string()), Ok(value.clone()));
+        assert_eq!(JsonValue::parse(" { \"a\" : [ 1 , true ] } "), Ok(JsonValue::object([
+            ("a", JsonValue::Array(vec![JsonValue::number(1), JsonValue::Bool(true)])),
+        ])));
+
+        assert_eq!(value.get("done").and_then(JsonValue::as_bool), Some(false));
+        assert_eq!(value.get("tape").and_then(JsonValue::as_array).map(<[_]>::len), Some(2));
+        assert_eq!(value.get("missing"), None);
+
+        assert_eq!(JsonValue::parse("[1,]"), Err(JsonParseError { at: 3, expected: "a value" }));
+        assert_eq!(JsonValue::parse("{} {}"), Err(JsonParseError { at: 3, expected: "the end of the document" }));
+        assert!(JsonValue::parse("\"unterminated").is_err());
+    }
+}
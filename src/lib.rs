@@ -27,16 +27,34 @@ pub use lexer::lex_file;
 pub mod source;
 use source::SourceFile;
 pub mod utils;
+pub mod json;
 pub mod parser;
 pub mod compiler;
 pub mod interpreter;
+pub mod backend;
 pub mod clap_cli;
 pub use clap_cli::CliCommand;
 mod optimiser;
-pub use optimiser::optimise;
+pub use optimiser::{optimise, verify_optimisation, Divergence, DivergenceKind, DEFAULT_VERIFICATION_INPUTS, VERIFICATION_STEP_LIMIT};
+pub use optimiser::{OptimisationLevel, Pass, Pipeline, UnknownPassError, MAX_FIXED_POINT_ITERATIONS};
+pub use optimiser::{BfStats, OperationChange, OptimisationReport, PassReport};
+pub use optimiser::fuzz;
 
 /// Transpiles bfu source code into bf.
 pub fn transpile<'a>(sf: &'static SourceFile) -> Result<String, Vec<Box<dyn CompilerError + 'a>>> {
+    transpile_with_source_map(sf).map(|(program, _)| program)
+}
+
+/// Transpiles bfu source code into bf, also returns the [`SourceMap`](compiler::SourceMap)
+/// linking the bf back to the basm instructions which generated it.
+pub fn transpile_with_source_map<'a>(sf: &'static SourceFile) -> Result<(String, compiler::SourceMap), Vec<Box<dyn CompilerError + 'a>>> {
+    transpile_with_context(sf, compiler::MainContext::new())
+        .map(|(program, mut context)| (program, context.take_source_map()))
+}
+
+/// Transpiles bfu source code into bf, starting from the `context` (e.g. one made for a [`TargetCell`](compiler::TargetCell)).
+/// Also returns the context once the program was transpiled.
+pub fn transpile_with_context<'a>(sf: &'static SourceFile, context: compiler::MainContext) -> Result<(String, compiler::MainContext), Vec<Box<dyn CompilerError + 'a>>> {
     let tokens = match lexer::lex_file(sf) {
         Ok(tokens) => tokens,
         Err((_, errors)) => {
@@ -52,7 +70,7 @@ pub fn transpile<'a>(sf: &'static SourceFile) -> Result<String, Vec<Box<dyn Comp
         Err(e) => return Err(vec![Box::new(e)]),
     };
 
-    let program = match compiler::compile(&program) {
+    let program = match compiler::compile_with_context(&program, context) {
         Ok(p) => p,
         Err(e) => return Err(vec![Box::new(e)])
     };
@@ -110,10 +128,11 @@ mod tests {
 
         let mut inter = InterpreterBuilder::new(&bf_prog)
             .with_bulk_input()
+            .with_output_capture()
             .finish();
         assert!(inter.add_to_input_buffer(hello_bf));
         inter.complete().unwrap();
-        assert_eq!(inter.captured_output(), "Hello World!\n");
+        assert_eq!(inter.captured_output().unwrap(), "Hello World!\n");
 
         // fibonacci program
         let mut fib_bf = include_str!("./../test-resources/fib.bf").to_string();
@@ -122,9 +141,10 @@ mod tests {
         let mut inter = InterpreterBuilder::new(&bf_prog)
             .with_output_as_number()
             .with_bulk_input()
+            .with_output_capture()
             .finish();
         assert!(inter.add_to_input_buffer(&fib_bf));
         inter.complete().unwrap();
-        assert_eq!(inter.captured_output(), "1 2 3 5 8 13 21 34 55 89 144 ");
+        assert_eq!(inter.captured_output().unwrap(), "1 2 3 5 8 13 21 34 55 89 144 ");
     }
 }
\ No newline at end of file
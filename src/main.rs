@@ -1,6 +1,6 @@
-use std::{env, fs, io::Write, path::{self, PathBuf}};
+use std::{env, fs, io::{BufReader, Write}, path::{self, PathBuf}};
 
-use basm::{clap_cli::CompileArgs, source::SourceFile, CliCommand, CompilerError};
+use basm::{clap_cli::{CompileArgs, ReportFormat, RunArgs, TraceViewArgs}, compiler::MainContext, interpreter::{read_trace, TRACE_HEADER}, source::SourceFile, CliCommand, CompilerError};
 use clap::Parser;
 use colored::Colorize as _;
 
@@ -9,30 +9,53 @@ const MALFORMED_INPUT: &str = "the input path is malformed";
 const INACCESSIBLE_INPUT: &str = "failed to access input";
 const INACCESSIBLE_OUTPUT: &str = "failed to access output";
 const UNWRITEABLE_OUTPUT: &str = "failed to write to output file";
+/// Number of loops shown by `run --profile`.
+const PROFILED_LOOPS: usize = 10;
+
+/// The commands processing a program.
+enum ProgramCommand {
+    Compile(CompileArgs),
+    Run(RunArgs),
+}
 
 fn main() {
-    let cli = CliCommand::parse();
-    
+    match CliCommand::parse() {
+        CliCommand::Compile(args) => process_program(ProgramCommand::Compile(args)),
+        CliCommand::Run(args) => process_program(ProgramCommand::Run(args)),
+        // viewing traces does not involve any program
+        CliCommand::TraceView(args) => view_trace(&args),
+    }
+}
+
+/// Compiles (or reads) the program, then writes it to file or runs it depending on the command.
+fn process_program(cli: ProgramCommand) {
     let file_path = match &cli {
-        CliCommand::Compile(args) => PathBuf::from(&args.file_path),
-        CliCommand::Run(args) => PathBuf::from(&args.file_path),
+        ProgramCommand::Compile(args) => PathBuf::from(&args.file_path),
+        ProgramCommand::Run(args) => PathBuf::from(&args.file_path),
     };
 
     let abs_path = path::absolute(&file_path)
         .unwrap_or_else(|_| error_out(MALFORMED_INPUT));
 
     let is_basm_file = match &cli {
-        CliCommand::Compile(_) => true,
-        CliCommand::Run(args) => !args.raw,
+        ProgramCommand::Compile(_) => true,
+        ProgramCommand::Run(args) => !args.raw,
     };
 
     // transpiling (or not)
+    let mut source_map = None;
     let mut program = if is_basm_file {
         let sf = SourceFile::from_file(&abs_path)
             .unwrap_or_else(|_| error_out(INACCESSIBLE_INPUT))
             .leak();
 
-        let program = match basm::transpile(sf) {
+        let context = match &cli {
+            ProgramCommand::Compile(args) => args.compiler_context()
+                .unwrap_or_else(|e| error_out(&e.to_string())),
+            ProgramCommand::Run(_) => MainContext::new(),
+        };
+
+        let program = match basm::transpile_with_context(sf, context) {
             Err(errors) => {
                 eprintln!("\n------------------ [ ERRORS ] ------------------");
                 for e in errors {
@@ -40,7 +63,22 @@ fn main() {
                 };
                 std::process::exit(1)
             },
-            Ok(p) => p,
+            Ok((p, mut context)) => {
+                if let ProgramCommand::Compile(CompileArgs { memory_map: true, .. }) = &cli {
+                    context.memory_map().print();
+                }
+
+                // only the cells instructions move to are checked against the tape limit
+                if let (Some(tape_limit), false) = (context.tape_limit(), context.memory_map().is_complete()) {
+                    eprintln!(
+                        "{} RAW instructions move the tape pointer, the cells they address are not checked against the tape limit of {tape_limit} cells",
+                        "Warning:".yellow().bold(),
+                    );
+                }
+
+                source_map = Some(context.take_source_map());
+                p
+            },
         };
 
         program
@@ -49,19 +87,36 @@ fn main() {
             .unwrap_or_else(|_| error_out(INACCESSIBLE_INPUT))
     };
 
-    let optimise = match &cli {
-        CliCommand::Compile(args) => !args.unoptimized,
-        CliCommand::Run(args) => !args.unoptimized,
+    let pipeline = match &cli {
+        ProgramCommand::Compile(args) => args.optimisation_pipeline(),
+        ProgramCommand::Run(args) => args.optimisation_pipeline(),
     };
 
-    if optimise {
-        program = basm::optimise(&program);
+    if let Some(pipeline) = pipeline {
+        let unoptimised = program;
+        program = match &cli {
+            ProgramCommand::Compile(args) if args.opt_report => {
+                let (optimised, report) = pipeline.run_with_report(&unoptimised);
+                report.print(args.opt_report_diff);
+                optimised
+            },
+            _ => pipeline.run(&unoptimised),
+        };
+
+        if let ProgramCommand::Compile(args) = &cli {
+            if args.verify_optimisation {
+                verify_optimisation(&unoptimised, &program, &args.verify_input);
+            }
+        }
+
+        // the source map only matches the unoptimised program
+        source_map = None;
     }
 
     // show (if necessary)
     let show = match &cli {
-        CliCommand::Compile(args) => args.show,
-        CliCommand::Run(args) => args.show,
+        ProgramCommand::Compile(args) => args.show,
+        ProgramCommand::Run(args) => args.show,
     };
 
     if show {
@@ -69,7 +124,7 @@ fn main() {
     }
 
     // writing to output file (if necessary)
-    if let CliCommand::Compile(CompileArgs { out, .. }) = &cli {
+    if let ProgramCommand::Compile(args @ CompileArgs { out, target, .. }) = &cli {
         let out_path = out.clone().unwrap_or_else(|| {
             // get the name of the current operated on file
             let file_name = file_path.file_name()
@@ -82,21 +137,28 @@ fn main() {
             // mash the both of them together
             out_path.push(file_name);
 
-            // and change basm -> bf
-            out_path.set_extension("bf");
+            // and change the extension to the one of the target (basm -> bf, c, s or wat)
+            out_path.set_extension(target.extension());
 
             out_path.to_string_lossy().to_string()
         });
 
+        let output = match target.translate(program, &args.machine_options()) {
+            Ok(o) => o,
+            Err(e) => error_out(&e.to_string()),
+        };
+
         let mut output_file = fs::File::create(&out_path)
         .unwrap_or_else(|_| error_out(INACCESSIBLE_OUTPUT));
 
-        output_file.write_all(program.as_bytes())
+        output_file.write_all(output.as_bytes())
         .unwrap_or_else(|_| error_out(UNWRITEABLE_OUTPUT));
+
+        return
     }
 
     // interpreting (if necessary)
-    let run_args = if let CliCommand::Run(args) = cli {
+    let run_args = if let ProgramCommand::Run(args) = cli {
         // okay
         args
     } else {
@@ -107,17 +169,61 @@ fn main() {
         Ok(i) => i,
         Err(e) => error_out(&e.to_string())
     };
-    match interpreter.complete() {
-        Ok(()) => (),
-        Err(e) => {
-            let msg = format!("{}: {}", "Intepreter Error".red().bold(), e.to_string());
-            eprintln!("{}", msg)
-        },
+    let result = interpreter.complete();
+    if let Err(e) = &result {
+        let msg = format!("{}: {}", "Intepreter Error".red().bold(), e.to_string());
+        eprintln!("{}", msg)
     }
 
     if run_args.dump {
         interpreter.print_dump();
     }
+
+    if let Some(snapshot_path) = &run_args.snapshot_on_exit {
+        fs::write(snapshot_path, interpreter.snapshot().to_json().to_string())
+            .unwrap_or_else(|_| error_out(UNWRITEABLE_OUTPUT));
+    }
+
+    if let Some(profile) = interpreter.profile() {
+        profile.print_hottest_loops(PROFILED_LOOPS, source_map.as_ref());
+    }
+
+    if let (Some(format), Some(report_path)) = (run_args.report, &run_args.report_out) {
+        let report = interpreter.report(result.err());
+        let report_str = match format {
+            ReportFormat::Json => report.to_json().to_string(),
+        };
+
+        fs::write(report_path, report_str)
+            .unwrap_or_else(|_| error_out(UNWRITEABLE_OUTPUT));
+    }
+}
+
+/// Runs the unoptimised and optimised programs on the inputs (or the default ones), errors out if they diverge.
+fn verify_optimisation(unoptimised: &str, optimised: &str, inputs: &[String]) {
+    let inputs = if inputs.is_empty() {
+        basm::DEFAULT_VERIFICATION_INPUTS.to_vec()
+    } else {
+        inputs.iter().map(String::as_str).collect()
+    };
+
+    if let Err(divergence) = basm::verify_optimisation(unoptimised, optimised, &inputs) {
+        error_out(&divergence.to_string());
+    }
+}
+
+/// Prints the entries of the trace file matching the filters of `args`.
+fn view_trace(args: &TraceViewArgs) {
+    let file = fs::File::open(&args.file_path)
+        .unwrap_or_else(|_| error_out(INACCESSIBLE_INPUT));
+
+    println!("{TRACE_HEADER}");
+    for entry in read_trace(BufReader::new(file), args.filter()) {
+        match entry {
+            Ok(e) => println!("{e}"),
+            Err(e) => error_out(&e.to_string()),
+        }
+    }
 }
 
 fn error_out(reason: &str) -> ! {
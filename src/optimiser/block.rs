@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use super::Operation;
 
@@ -12,14 +12,15 @@ pub struct Block<'a> {
 impl<'a> Block<'a> {
     /// Takes in the source of a matched bracket block.
     /// The slice should start and end with '[' and ']' respectively.
-    pub fn new(src: &str) -> Block {
+    /// If `strict` is `true`, the moves reaching further on the tape are kept (see [`super::parse`]).
+    pub fn new(src: &str, strict: bool) -> Block {
         debug_assert!(src.starts_with('['));
         debug_assert!(src.ends_with(']'));
         
         // remove the brackets
         let content = &src[1..src.len()-1];
 
-        let (operations, end_point) = super::parse_operations(content);
+        let (operations, end_point) = super::parse(content, strict);
 
         Block {
             operations,
@@ -32,6 +33,51 @@ impl<'a> Block<'a> {
         self.dynamic_endpoint.is_some()
     }
 
+    /// Returns where the block leaves the tape pointer at the end of an iteration, relative to its start.
+    pub fn endpoint(&self) -> isize {
+        self.dynamic_endpoint.unwrap_or(0)
+    }
+
+    /// Returns how far the block moves the tape pointer on each iteration, if it is always the same.
+    /// It is `Some(0)` for blocks which are not dynamic, and `None` if the block contains dynamic blocks,
+    /// since the tape pointer then depends on the tape.
+    /// Aka `[->>]` drifts by 2, while `[>[>]]` drifts by an unknown amount.
+    /// The operations of a block with a known drift are all relative to the start of the iteration.
+    pub fn drift(&self) -> Option<isize> {
+        let dynamic_sub_block = self.operations.iter()
+            .any(|op| matches!(op, Operation::Block { block, .. } if block.is_dynamic()));
+
+        (!dynamic_sub_block).then_some(self.endpoint())
+    }
+
+    /// Returns `true` if the block zeroes its cell.
+    /// Aka if it is `[-]` or `[+]`, or anything which reduces to it, like `[--+]`.
+    /// Blocks containing text are not clear loops since the text would be lost.
+    pub fn is_clear_loop(&self) -> bool {
+        !self.is_dynamic() && matches!(self.operations[..], [Operation::Offset { cell: 0, recurrence: 1 | -1 }])
+    }
+
+    /// Returns the cells and factors of a multiplication loop, relative to the block, if the block is one.
+    /// Multiplication loops only contain offsets, do not move the tape pointer, and decrement their cell by one.
+    /// Aka `[->+>--<<]` multiplies the cell by 1 into the next cell and by -2 into the one after.
+    pub fn multiplication_factors(&self) -> Option<Vec<(isize, i32)>> {
+        if self.is_dynamic() {
+            return None;
+        }
+
+        let mut factors = BTreeMap::new();
+        for op in &self.operations {
+            let Operation::Offset { cell, recurrence } = op else { return None };
+            *factors.entry(*cell).or_insert(0) += recurrence;
+        }
+
+        if factors.remove(&0) != Some(-1) {
+            return None;
+        }
+
+        Some(factors.into_iter().filter(|(_, factor)| *factor != 0).collect())
+    }
+
     pub fn fences_cell(&self, idx: isize) -> bool {
         // we can't trust this section to not use this cell if it is dynamic
         if self.is_dynamic() {
@@ -57,22 +103,8 @@ impl<'a> Block<'a> {
     }
 
     pub fn to_brainfuck(&self) -> String {
-        let mut buf = super::operations_to_brainfuck(&self.operations);
-        
         // we want to end on the dynamic endpoint, or at the start if we are not dynamic
-        // find the last position we got put on
-        let last_position = self.operations.iter()
-            .rfind(|op| op.cell_position().is_some())
-            .map(|op| op.cell_position().unwrap())
-            .unwrap_or(0);
-        
-        let offset = self.dynamic_endpoint.unwrap_or(0);
-        let difference = offset - last_position;
-            let movement_ch = if difference.is_positive() { '>' } else { '<' };
-            
-            for _ in 0..(difference.abs()) {
-                buf.push(movement_ch);
-        }
+        let mut buf = super::operations_to_brainfuck_ending_at(&self.operations, self.endpoint());
 
         // we are a bracket block after all
         buf.insert(0, '[');
@@ -89,13 +121,15 @@ impl<'a> Block<'a> {
 
 #[cfg(test)] 
 mod tests {
+    use std::assert_matches::assert_matches;
+
     use crate::optimiser::parse_operations;
 
     use super::*;
 
     #[test]
     fn block_fences() {                     // 0   3 2
-        let block = Block::new("[>>>,<[-]<<]");
+        let block = Block::new("[>>>,<[-]<<]", false);
         assert!(!block.is_dynamic());
         assert!(block.fences_cell(0));
         assert!(!block.fences_cell(1));
@@ -111,9 +145,60 @@ mod tests {
         assert!(block.fences_cell(5));
     }
 
+    #[test]
+    fn block_is_clear_loop() {
+        assert!(Block::new("[-]", false).is_clear_loop());
+        assert!(Block::new("[+]", false).is_clear_loop());
+        assert!(Block::new("[--+]", false).is_clear_loop());
+
+        // not zero because dynamic
+        assert!(!Block::new("[->]", false).is_clear_loop());
+        // not zero because not on cell 0
+        assert!(!Block::new("[<->]", false).is_clear_loop());
+        // text would be lost
+        assert!(!Block::new("[ i love cats - :3c]", false).is_clear_loop());
+
+        // clear loops are parsed as zeroes
+        assert_eq!(parse_operations(">[+]").0, vec![Operation::Zero { cell: 1 }]);
+        assert_matches!(parse_operations("[< omg - figha > ]").0[..], [Operation::Block { .. }]);
+    }
+
+    #[test]
+    fn block_drift() {
+        assert_eq!(Block::new("[->+<]", false).drift(), Some(0));
+        assert_eq!(Block::new("[>]", false).drift(), Some(1));
+        assert_eq!(Block::new("[-<<[-]>.<]", false).drift(), Some(-2));
+        // sub blocks which are not dynamic keep the drift known
+        assert_eq!(Block::new("[->>[-<+>]]", false).drift(), Some(2));
+
+        // the drift depends on the tape
+        assert_eq!(Block::new("[>[>]]", false).drift(), None);
+        assert_eq!(Block::new("[>[<]>]", false).drift(), None);
+        assert_eq!(Block::new("[[>[>]<]]", false).drift(), None);
+    }
+
+    #[test]
+    fn block_multiplication_factors() {
+        assert_eq!(Block::new("[->+>>--<<<]", false).multiplication_factors(), Some(vec![(1, 1), (3, -2)]));
+        assert_eq!(Block::new("[<+>>+<-]", false).multiplication_factors(), Some(vec![(-1, 1), (1, 1)]));
+        // the offsets don't need to be grouped
+        assert_eq!(Block::new("[>+<->+<]", false).multiplication_factors(), Some(vec![(1, 2)]));
+
+        assert_eq!(Block::new("[>+<]", false).multiplication_factors(), None);
+        assert_eq!(Block::new("[+>+<]", false).multiplication_factors(), None);
+        assert_eq!(Block::new("[-->+<]", false).multiplication_factors(), None);
+        assert_eq!(Block::new("[->+]", false).multiplication_factors(), None);
+        assert_eq!(Block::new("[->.<]", false).multiplication_factors(), None);
+        assert_eq!(Block::new("[->+<[-]]", false).multiplication_factors(), None);
+        assert_eq!(Block::new("[- copy >+<]", false).multiplication_factors(), None);
+
+        // multiplication loops are parsed as such
+        assert_eq!(parse_operations(">[->+<]").0, vec![Operation::Multiply { cell: 1, factors: vec![(1, 1)] }]);
+    }
+
     #[test]
     fn block_modifies() {                   // 0   3  2
-        let block = Block::new("[>>>+<[-]<<]");
+        let block = Block::new("[>>>+<[-]<<]", false);
         assert!(!block.is_dynamic());
         assert!(!block.modified_cells().contains(&0));
         assert!(!block.modified_cells().contains(&1));
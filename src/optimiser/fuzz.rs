@@ -0,0 +1,411 @@
+//! Fuzzing of the optimiser, generating random programs and checking that optimising them does not change their behaviour.
+//! Everything is derived from a seed, so that failures can be reproduced.
+
+use std::fmt::Write as _;
+
+use crate::{interpreter::InterpreterError, source::SourceFile, transpile};
+
+use super::{verify::verify_with_step_limits, Divergence, DivergenceKind, OptimisationLevel};
+
+/// The inputs given to the generated programs.
+pub const FUZZ_INPUTS: [&str; 2] = ["", "Az\n"];
+
+/// The number of byte codes a generated program can execute per input before the verification stops.
+/// Random loops often never end, so this is kept low.
+pub const FUZZ_STEP_LIMIT: usize = 20_000;
+
+/// The step limit used to check again the program which may need more steps when the programs diverge.
+pub const FUZZ_RECHECK_STEP_LIMIT: usize = 2_000_000;
+
+/// Programs which once broke the optimiser or its verification, checked by the tests along with the generated ones.
+pub const FUZZ_CORPUS: [&str; 4] = [
+    // the clear loop `[+]` becomes `[-]`, so the optimised program completes much faster
+    "-[+>>><<<<<[+]+>>--]",
+    // the output was moved before a loop which never ends
+    "+>+<[]>.",
+    ">>><<<----+++++--[-<+>>+<]>>>[--].",
+    // the optimised loop outputs much faster, so the optimised program outputs more before reaching its step limit
+    "-[[-++<>].+]",
+];
+
+/// The number of cells the generated programs move around in.
+const TAPE_WINDOW: usize = 8;
+/// The maximum loop nesting of the generated programs.
+const MAX_DEPTH: usize = 3;
+/// The maximum number of operations (or instructions) of a generated program, nested ones included.
+const MAX_OPERATIONS: usize = 40;
+
+/// A deterministic pseudo random number generator (`SplitMix64`).
+#[derive(Debug, Clone, PartialEq)]
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Returns `true` one time out of `n`.
+    fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+}
+
+/// Generates random brainfuck and basm programs from a seed.
+///
+/// Loops are balanced, and the pointer moves within a few cells, except in loops drifting along the tape (like `[>]` or `[->>+<]`).
+/// The programs read input and output, and may never end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramGenerator {
+    rng: Rng,
+}
+
+impl ProgramGenerator {
+    /// Creates a generator, generators created with the same seed generate the same programs.
+    pub fn new(seed: u64) -> ProgramGenerator {
+        ProgramGenerator { rng: Rng(seed) }
+    }
+
+    /// Generates a brainfuck program.
+    pub fn bf(&mut self) -> String {
+        let mut buf = String::new();
+        let mut budget = MAX_OPERATIONS;
+        self.bf_section(&mut buf, &mut 0, 0, &mut budget);
+
+        buf
+    }
+
+    /// Writes a section of brainfuck into `buf`, `pointer` is where the generator thinks the tape pointer is.
+    fn bf_section(&mut self, buf: &mut String, pointer: &mut usize, depth: usize, budget: &mut usize) {
+        while *budget > 0 && !(depth > 0 && self.rng.one_in(6)) {
+            *budget -= 1;
+
+            match self.rng.below(10) {
+                0..=2 => {
+                    let operator = if self.rng.one_in(2) { '+' } else { '-' };
+                    buf.extend(std::iter::repeat_n(operator, 1 + self.rng.below(4)));
+                },
+                3 | 4 => {
+                    let target = self.rng.below(TAPE_WINDOW);
+                    move_pointer(buf, pointer, target);
+                },
+                5 => buf.push(','),
+                6 => buf.push('.'),
+                7 | 8 if depth < MAX_DEPTH => {
+                    let start = *pointer;
+                    buf.push('[');
+                    // loops decrementing their cell are more likely to end
+                    if self.rng.one_in(2) {
+                        buf.push('-');
+                    }
+                    self.bf_section(buf, pointer, depth + 1, budget);
+                    // some loops end their iterations a few cells away, and drift along the tape
+                    let drift = if self.rng.one_in(4) { 1 + self.rng.below(2) } else { 0 };
+                    let end = if self.rng.one_in(2) && start + drift < TAPE_WINDOW {
+                        start + drift
+                    } else {
+                        start.saturating_sub(drift)
+                    };
+                    move_pointer(buf, pointer, end);
+                    buf.push(']');
+                },
+                _ => match self.rng.below(3) {
+                    0 => buf.push_str("[>]"),
+                    1 => buf.push_str("[<]"),
+                    _ => buf.push_str(" fuzz "),
+                },
+            }
+        }
+    }
+
+    /// Generates a basm program, made of the instructions which do not take scopes and `WHNE`.
+    pub fn basm(&mut self) -> String {
+        let mut buf = String::from("[main] [\n");
+        let mut budget = MAX_OPERATIONS / 2;
+        self.basm_scope(&mut buf, 1, &mut budget);
+        buf.push_str("]\n");
+
+        buf
+    }
+
+    /// Writes the instructions of a scope into `buf`.
+    fn basm_scope(&mut self, buf: &mut String, depth: usize, budget: &mut usize) {
+        let indent = "    ".repeat(depth);
+        while *budget > 0 && !(depth > 1 && self.rng.one_in(4)) {
+            *budget -= 1;
+
+            let [a, b, c] = self.distinct_cells();
+            let value = 1 + self.rng.below(20);
+            let instruction = match self.rng.below(11) {
+                0 => format!("INCR {a} {value};"),
+                1 => format!("DECR {a} {value};"),
+                2 => format!("ZERO {a};"),
+                3 => format!("ADDP {a} {b};"),
+                4 => format!("SUBP {a} {b};"),
+                5 => format!("COPY {a} {b} {c};"),
+                6 => format!("OUT {a};"),
+                7 => format!("IN {a};"),
+                8 => format!("LSTR {a} \"{}\";", ["a", "Hi", "\\n"][self.rng.below(3)]),
+                9 => format!("PSTR {a} \"{}\";", ["!", "ok"][self.rng.below(2)]),
+                _ if depth < MAX_DEPTH => {
+                    let _ = writeln!(buf, "{indent}WHNE {a} {} [", self.rng.below(3));
+                    // decrementing the condition makes the loop more likely to end
+                    let _ = writeln!(buf, "{indent}    DECR {a} 1;");
+                    self.basm_scope(buf, depth + 1, budget);
+                    let _ = writeln!(buf, "{indent}];");
+                    continue
+                },
+                _ => format!("ZERO {a};"),
+            };
+
+            let _ = writeln!(buf, "{indent}{instruction}");
+        }
+    }
+
+    /// Returns three different cells within the tape window.
+    fn distinct_cells(&mut self) -> [usize; 3] {
+        let a = self.rng.below(TAPE_WINDOW);
+        let b = (a + 1 + self.rng.below(TAPE_WINDOW - 1)) % TAPE_WINDOW;
+        let start = self.rng.below(TAPE_WINDOW);
+        let c = (start..start + TAPE_WINDOW)
+            .map(|c| c % TAPE_WINDOW)
+            .find(|c| *c != a && *c != b)
+            .expect("the tape window has more than two cells");
+
+        [a, b, c]
+    }
+}
+
+/// Writes the moves from `pointer` to `target` into `buf`.
+fn move_pointer(buf: &mut String, pointer: &mut usize, target: usize) {
+    let operator = if target > *pointer { '>' } else { '<' };
+    buf.extend(std::iter::repeat_n(operator, target.abs_diff(*pointer)));
+    *pointer = target;
+}
+
+/// A generated program whose behaviour changed when it was optimised.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzFailure {
+    /// The seed of the generator.
+    pub seed: u64,
+    /// The index of the program in the programs generated from the seed.
+    pub index: usize,
+    /// The generated brainfuck (transpiled if it was a basm program).
+    pub program: String,
+    /// The shortest brainfuck found which still diverges when optimised.
+    pub reproducer: String,
+    /// The divergence of the reproducer.
+    pub divergence: Divergence,
+}
+
+/// Generates `count` programs from `seed`, alternating between brainfuck and basm,
+/// and checks that optimising them at the strict and default levels does not change their behaviour with [`FUZZ_INPUTS`].
+/// Returns the first failure, with its program shrunk into a minimal reproducer.
+///
+/// Generated basm programs are transpiled from leaked source files, so this should not be run indefinitely.
+pub fn fuzz_optimiser(seed: u64, count: usize) -> Result<(), Box<FuzzFailure>> {
+    let mut generator = ProgramGenerator::new(seed);
+
+    for index in 0..count {
+        let program = if index % 2 == 0 {
+            generator.bf()
+        } else {
+            let sf = SourceFile::from_raw_parts("fuzz.basm".into(), generator.basm()).leak();
+            transpile(sf).unwrap_or_else(|_| panic!("generated basm programs compile, failed on {index} of seed {seed}"))
+        };
+
+        if check(&program).is_ok() {
+            continue
+        }
+
+        let reproducer = shrink(&program, |candidate| check(candidate).is_err());
+        let divergence = *check(&reproducer).expect_err("the reproducer diverges");
+        return Err(Box::new(FuzzFailure { seed, index, program, reproducer, divergence }))
+    }
+
+    Ok(())
+}
+
+/// Checks that optimising `bf` with the strict and the default levels does not change its behaviour.
+fn check(bf: &str) -> Result<(), Box<Divergence>> {
+    for level in [OptimisationLevel::O1, OptimisationLevel::default()] {
+        let optimised = level.pipeline().expect("the level optimises").run(bf);
+        check_optimised(bf, &optimised)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that `optimised` behaves like `bf`.
+/// Optimised loops can be many times faster or slower (e.g. `[+]` becomes `[-]`),
+/// so while the programs diverge, they are checked again with more steps for the program which may need them.
+fn check_optimised(bf: &str, optimised: &str) -> Result<(), Box<Divergence>> {
+    let (mut unoptimised_step_limit, mut optimised_step_limit) = (2 * FUZZ_STEP_LIMIT, FUZZ_STEP_LIMIT);
+    loop {
+        let result = verify_with_step_limits(bf, optimised, &FUZZ_INPUTS, unoptimised_step_limit, optimised_step_limit);
+        let Err(divergence) = &result else { return result };
+
+        match divergence.kind {
+            DivergenceKind::Outcome { unoptimised: None, optimised: Some(InterpreterError::StepLimitExceded { .. }) }
+                if optimised_step_limit < FUZZ_RECHECK_STEP_LIMIT => optimised_step_limit = FUZZ_RECHECK_STEP_LIMIT,
+            _ if unoptimised_step_limit < FUZZ_RECHECK_STEP_LIMIT => unoptimised_step_limit = FUZZ_RECHECK_STEP_LIMIT,
+            _ => return result,
+        }
+    }
+}
+
+/// Shrinks the brainfuck `program` into a shorter one for which `fails` is still `true`.
+/// Comments are removed, then parts of the program and loop brackets are removed for as long as it keeps failing.
+/// The brackets of the shrunk program stay balanced if they were.
+pub fn shrink(program: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut current = program.to_string();
+    let operators = program.chars().filter(|c| "+-<>[],.".contains(*c)).collect::<String>();
+    if operators != current && fails(&operators) {
+        current = operators;
+    }
+
+    loop {
+        let before = current.len();
+
+        // removes chunks, from half the program down to single characters.
+        // Works on chars since comments may contain multi-byte characters
+        let mut chars = current.chars().collect::<Vec<_>>();
+        let mut size = chars.len() / 2;
+        while size > 0 {
+            let mut start = 0;
+            while start + size <= chars.len() {
+                let candidate = chars[..start].iter().chain(&chars[start + size..]).collect::<String>();
+                if is_balanced(&candidate) == is_balanced(&current) && fails(&candidate) {
+                    chars.drain(start..start + size);
+                    current = candidate;
+                } else {
+                    start += size;
+                }
+            }
+            size /= 2;
+        }
+
+        // unwraps loops
+        let mut start = 0;
+        while let Some(open) = current[start..].find('[').map(|i| i + start) {
+            let candidate = matching_bracket(&current, open)
+                .map(|close| format!("{}{}{}", &current[..open], &current[open + 1..close], &current[close + 1..]));
+            match candidate {
+                Some(candidate) if fails(&candidate) => current = candidate,
+                _ => start = open + 1,
+            }
+        }
+
+        if current.len() == before {
+            return current
+        }
+    }
+}
+
+/// Returns `true` if every bracket of `bf` has a match.
+fn is_balanced(bf: &str) -> bool {
+    let mut depth = 0usize;
+    for c in bf.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => (),
+        }
+    }
+
+    depth == 0
+}
+
+/// Returns the index of the `]` matching the `[` at `open`.
+fn matching_bracket(bf: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in bf[open..].char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i)
+                }
+            },
+            _ => (),
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn program_generation() {
+        // generation is deterministic
+        assert_eq!(ProgramGenerator::new(7).bf(), ProgramGenerator::new(7).bf());
+        assert_eq!(ProgramGenerator::new(7).basm(), ProgramGenerator::new(7).basm());
+        assert_ne!(ProgramGenerator::new(7).bf(), ProgramGenerator::new(8).bf());
+
+        let mut generator = ProgramGenerator::new(42);
+        for _ in 0..50 {
+            assert!(is_balanced(&generator.bf()));
+
+            let sf = SourceFile::from_raw_parts("fuzz.basm".into(), generator.basm()).leak();
+            assert!(transpile(sf).is_ok());
+        }
+    }
+
+    #[test]
+    fn shrinking() {
+        // fails while there are two outputs after an input
+        let fails = |bf: &str| bf.find(',').is_some_and(|i| bf[i..].matches('.').count() >= 2);
+        assert_eq!(shrink("+>, hi [->+<]. >>[.-]<", fails), ",..");
+
+        // brackets stay balanced
+        let fails = |bf: &str| bf.contains('-');
+        assert_eq!(shrink("[+[>-<]+]", fails), "-");
+        let fails = |bf: &str| bf.contains("[-]");
+        assert_eq!(shrink("++[>[-]<]", fails), "[-]");
+
+        // comments with multi-byte characters are kept when removing them stops the failure
+        let fails = |bf: &str| bf.contains('é') && bf.contains('+');
+        assert_eq!(shrink("+ été ±", fails), "+é");
+    }
+
+    #[test]
+    fn optimiser_corpus() {
+        for program in FUZZ_CORPUS {
+            if let Err(divergence) = check(program) {
+                panic!("{program}\n{divergence}");
+            }
+        }
+    }
+
+    #[test]
+    fn optimiser_fuzzing() {
+        if let Err(failure) = fuzz_optimiser(0x00ba_5eba11, 200) {
+            panic!("{failure:#?}\n{}", failure.divergence);
+        }
+    }
+
+    #[test]
+    #[ignore = "compute intensive"]
+    fn optimiser_fuzzing_long() {
+        for seed in 0..50 {
+            if let Err(failure) = fuzz_optimiser(seed, 1000) {
+                panic!("{failure:#?}\n{}", failure.divergence);
+            }
+        }
+    }
+}
@@ -11,30 +11,27 @@ use std::{collections::HashSet, ops::Range};
 mod block;
 use block::Block;
 mod optimisations;
+mod pipeline;
+pub use pipeline::{OptimisationLevel, Pass, Pipeline, UnknownPassError, MAX_FIXED_POINT_ITERATIONS};
+mod report;
+pub use report::{BfStats, OperationChange, OptimisationReport, PassReport};
+mod verify;
+pub use verify::{verify_optimisation, Divergence, DivergenceKind, DEFAULT_VERIFICATION_INPUTS, VERIFICATION_STEP_LIMIT};
+pub mod fuzz;
 
 
 /// Takes in a brainfuck program and removes redundant brainfuck operators by bulking them in `Operation`s.
 /// May break some behviour, like moving the pointer at the end of the program.
+/// This is the pipeline of the default [`OptimisationLevel`].
 pub fn optimise(bf: &str) -> String {
-    // Parses the operations
-    let mut operations = parse_operations(&bf).0;
-
-    optimisations::reorder_operations(&mut operations);
-
-    optimisations::merge_offsets(&mut operations);
-
-    // running it twice shaves off a few characters
-    optimisations::reorder_operations(&mut operations);
-
-    optimisations::remove_offsets_before_zeroing(&mut operations);
-
-    operations_to_brainfuck(&operations)
+    OptimisationLevel::default().pipeline()
+        .expect("the default level optimises")
+        .run(bf)
 }
 
-// TODO: Zero operation would enable more optimisations.
 /// Brainfuck operations on cells. Operations are collections of operators that achieve one thing.
 #[derive(Debug, Clone, PartialEq)]
-enum Operation<'a> {
+pub(crate) enum Operation<'a> {
     Block {
         cell: isize,
         block: Block<'a>,
@@ -43,6 +40,21 @@ enum Operation<'a> {
         cell: isize,
         recurrence: i32,
     },
+    /// A clear loop, `[-]` or `[+]`.
+    Zero {
+        cell: isize,
+    },
+    /// A clear loop followed by an offset, which sets the cell to `value`.
+    Set {
+        cell: isize,
+        value: i32,
+    },
+    /// A balanced loop which decrements its cell by one and adds multiples of it to other cells, like `[->++>-<<]`.
+    /// `factors` are the cells relative to `cell`, sorted, with what they are multiplied by.
+    Multiply {
+        cell: isize,
+        factors: Vec<(isize, i32)>,
+    },
     InOut {
         cell: isize,
         operator: char,
@@ -54,6 +66,11 @@ enum Operation<'a> {
     Text {
         src: &'a str,
     },
+    /// The tape pointer goes to the cell without doing anything.
+    /// Only strict parsing keeps these, so that the pointer goes as far on the tape as the original program.
+    Visit {
+        cell: isize,
+    },
 
 }
 
@@ -70,9 +87,13 @@ impl<'a> Operation<'a> {
             Self::Block { cell, block } => block.fences_cell(idx-cell),
             Self::InOut { cell, .. } => idx == *cell,
             Self::Offset { .. } => false,
+            // zeroing does not care about the value, but it can't be reordered with the offsets of its cell
+            Self::Zero { cell } | Self::Set { cell, .. } => idx == *cell,
+            // the other cells are only offset
+            Self::Multiply { cell, .. } => idx == *cell,
             // NOTE: THIS MAY CAUSE LOOSE BRACKETS TO GET AFFECTED TO PAIRS WHICH THEY WEREN'T A PART OF PRIOR (MAYBE IDK)
             Self::LooseBracket { cell, .. } => idx == *cell,
-            Self::Text { .. } => false,
+            Self::Text { .. } | Self::Visit { .. } => false,
         }
     }
 
@@ -89,38 +110,77 @@ impl<'a> Operation<'a> {
             } else {
                 vec![*cell]
             },
-            Self::Offset { cell, .. } => vec![*cell],
+            Self::Offset { cell, .. } | Self::Zero { cell } | Self::Set { cell, .. } => vec![*cell],
+            Self::Multiply { cell, factors } => {
+                factors.iter()
+                    .map(|(c, _)| c+cell)
+                    .chain([*cell])
+                    .collect()
+            },
             Self::LooseBracket { .. } => vec![], //vec![*cell],
-            Self::Text { .. } => vec![],
+            Self::Text { .. } | Self::Visit { .. } => vec![],
         };
 
         // TODO: because i am lazy, but in the best of worlds we don't pass by vec
         HashSet::from_iter(vec)
     }
 
+    /// Returns `true` if the operation reads input or writes output, or contains operations which do.
+    fn does_io(&self) -> bool {
+        match self {
+            Self::InOut { .. } => true,
+            Self::Block { block, .. } => block.operations.iter().any(Operation::does_io),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the operation is a loop which may never end, aka a [`Operation::Block`].
+    fn is_loop(&self) -> bool {
+        matches!(self, Self::Block { .. })
+    }
+
     fn cell_position(&self) -> Option<isize> {
         match self {
             Self::Block { cell, .. } => Some(*cell),
             Self::InOut { cell, .. } => Some(*cell),
             Self::Offset { cell, .. } => Some(*cell),
+            Self::Zero { cell } => Some(*cell),
+            Self::Multiply { cell, .. } => Some(*cell),
+            Self::Set { cell, .. } => Some(*cell),
             Self::LooseBracket { cell, .. } => Some(*cell),
+            Self::Visit { cell } => Some(*cell),
             Self::Text { .. } => None,
         }
     }
 
     fn can_swap(&self, other: &Self) -> bool {
-        // we don't want to 
         match (self, other) {
             // we don't reorganise text because that would be unintuitive
             (Operation::Text { .. }, _) => return false,
             (_, Operation::Text { .. }) => return false,
-            // we don't reorganise io
-            (Operation::InOut { .. }, Operation::InOut { .. }) => return false,
+            // visits need to stay between the same operations
+            (Operation::Visit { .. }, _) | (_, Operation::Visit { .. }) => return false,
             _ => (),
         }
 
-        // checks if there is a dynmanic block just in case
+        // we don't reorganise io, even when it is within blocks
+        if self.does_io() && other.does_io() {
+            return false;
+        }
+
+        // a loop may never end, or fail, so the io and the other loops stay on the side of it they were on
+        // (clear and multiplication loops always end)
+        let (self_loops, other_loops) = (self.is_loop(), other.is_loop());
+        if (self_loops && (other_loops || other.does_io())) || (other_loops && self.does_io()) {
+            return false;
+        }
+
+        // the operations after a dynamic block are relative to where it exits, and the operations before it to where it starts,
+        // so nothing can cross it, even when its drift is known (see `Block::drift`): where it exits depends on
+        // its number of iterations, which is not known. Operations are still reordered and merged on each side of it.
         // (fencing and use may not work if the dynamic does not modify anything)
+        // TODO: operations on cells a block with a known drift never reaches could cross it,
+        // if the other side of the block could address them (see `prerealease-todo.md`)
         if let Operation::Block { block, .. } = self {
             if block.is_dynamic() {
                 return false;
@@ -157,10 +217,17 @@ impl<'a> Operation<'a> {
 /// Dynamic means that it does not end where it started, so if it were to be executed it would
 /// offset all other operations.
 /// This function does not provide any optimisations in itself.
-fn parse_operations(src: &str) -> (Vec<Operation>, Option<isize>) {
+pub(crate) fn parse_operations(src: &str) -> (Vec<Operation>, Option<isize>) {
+    parse(src, false)
+}
+
+/// Same as [`parse_operations`], but if `strict` is `true`, moves reaching further on the tape than before
+/// are kept as [`Operation::Visit`]s.
+pub(crate) fn parse(src: &str, strict: bool) -> (Vec<Operation<'_>>, Option<isize>) {
     // -- Encoding the operations on the cells --
     let mut operations = Vec::new();
     let mut relative_cell_position = 0; // NOTE: this may be invalid when dynamic is involved
+    let mut furthest_cells = FurthestCells::default();
     let mut sub_section_bracket_depth = 0;
     let mut sub_section_start = None;
     let mut last_op_is_text = false;
@@ -172,8 +239,18 @@ fn parse_operations(src: &str) -> (Vec<Operation>, Option<isize>) {
                 (_, '[') => sub_section_bracket_depth += 1,
                 (1, ']') => {
                     let sub_string = &src[sub_section_start.unwrap()..=idx];
-                    let sub_section = Block::new(sub_string);
-                    operations.push(Operation::Block { cell: relative_cell_position, block: sub_section });
+                    let sub_section = Block::new(sub_string, strict);
+                    // we can't know where we are anymore
+                    if sub_section.is_dynamic() {
+                        furthest_cells = FurthestCells::at(relative_cell_position);
+                    }
+                    if sub_section.is_clear_loop() {
+                        operations.push(Operation::Zero { cell: relative_cell_position });
+                    } else if let Some(factors) = sub_section.multiplication_factors() {
+                        operations.push(Operation::Multiply { cell: relative_cell_position, factors });
+                    } else {
+                        operations.push(Operation::Block { cell: relative_cell_position, block: sub_section });
+                    }
 
                     sub_section_bracket_depth = 0;
                     sub_section_start = None;
@@ -184,26 +261,29 @@ fn parse_operations(src: &str) -> (Vec<Operation>, Option<isize>) {
             continue;
         }
 
+        // operations other than moves happen where we are, so we visit the cell before them
+        if strict && matches!(op, '+' | '-' | '.' | ',' | '[' | ']') {
+            if let Some(cell) = furthest_cells.just_reached.take() {
+                operations.push(Operation::Visit { cell });
+            }
+        }
+
         match op {
-            '>' => { relative_cell_position += 1; last_op_is_text = false; },
-            '<' => { relative_cell_position -= 1; last_op_is_text = false; },
-            '+' => if let Some(Operation::Offset { cell, recurrence: ref mut recurence }) = operations.last_mut() {
-                if relative_cell_position != *cell {
-                    operations.push(Operation::Offset { cell: relative_cell_position, recurrence: 1 });
-                } else {
-                    *recurence += 1;
+            '>' | '<' => {
+                let previous_position = relative_cell_position;
+                relative_cell_position += if op == '>' { 1 } else { -1 };
+                last_op_is_text = false;
+
+                if let Some(cell) = furthest_cells.moved(previous_position, relative_cell_position).filter(|_| strict) {
+                    operations.push(Operation::Visit { cell });
                 }
-            } else {
-                operations.push(Operation::Offset { cell: relative_cell_position, recurrence: 1 })
             },
-            '-' => if let Some(Operation::Offset { cell, recurrence: ref mut recurence }) = operations.last_mut() {
-                if relative_cell_position != *cell {
-                    operations.push(Operation::Offset { cell: relative_cell_position, recurrence: -1 });
-                } else {
-                    *recurence -= 1;
-                } 
-            } else {
-                operations.push(Operation::Offset { cell: relative_cell_position, recurrence: -1 })
+            '+' | '-' => {
+                let offset = if op == '+' { 1 } else { -1 };
+                match operations.last_mut() {
+                    Some(Operation::Offset { cell, recurrence }) if *cell == relative_cell_position => *recurrence += offset,
+                    _ => operations.push(Operation::Offset { cell: relative_cell_position, recurrence: offset }),
+                }
             },
             ',' => operations.push(Operation::InOut { operator: ',', cell: relative_cell_position }),
             '.' => operations.push(Operation::InOut { operator: '.', cell: relative_cell_position }),
@@ -237,6 +317,10 @@ fn parse_operations(src: &str) -> (Vec<Operation>, Option<isize>) {
     // we should be done with that
     debug_assert_eq!(sub_section_bracket_depth, 0);
 
+    if let Some(cell) = furthest_cells.just_reached.filter(|_| strict) {
+        operations.push(Operation::Visit { cell });
+    }
+
     // removes empty operations
     let operations = operations.into_iter().filter(|op| {
         match op {
@@ -262,6 +346,33 @@ fn parse_operations(src: &str) -> (Vec<Operation>, Option<isize>) {
     (operations, dynamic_endpoint)
 }
 
+/// The furthest cells the tape pointer reached in a section, used by strict parsing to keep the moves reaching them.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FurthestCells {
+    lowest: isize,
+    highest: isize,
+    /// The furthest cell, if the tape pointer just reached it and did not leave yet.
+    just_reached: Option<isize>,
+}
+
+impl FurthestCells {
+    fn at(cell: isize) -> FurthestCells {
+        FurthestCells { lowest: cell, highest: cell, just_reached: None }
+    }
+
+    /// Records a move of the tape pointer, returns the furthest cell if the pointer leaves it.
+    fn moved(&mut self, from: isize, to: isize) -> Option<isize> {
+        if to < self.lowest || to > self.highest {
+            self.lowest = self.lowest.min(to);
+            self.highest = self.highest.max(to);
+            self.just_reached = Some(to);
+            return None
+        }
+
+        self.just_reached.take_if(|cell| *cell == from)
+    }
+}
+
 /// Turns back the operations into text format.
 fn operations_to_brainfuck(ops: &[Operation]) -> String {
     let mut tape_pointer = 0;
@@ -284,18 +395,13 @@ fn operations_to_brainfuck(ops: &[Operation]) -> String {
         let op_str = match op {
             Operation::Block { block, .. } => block.to_brainfuck(),
             Operation::InOut { operator, .. } => operator.to_string(),
-            Operation::Offset { recurrence: recurence, .. } => {
-                let mut offset_buf = String::new();
-                let offset_ch = if recurence.is_positive() { '+' } else { '-' };
-            
-                for _ in 0..(recurence.abs()) {
-                    offset_buf.push(offset_ch);
-                }
-
-                offset_buf
-            },
+            Operation::Offset { recurrence, .. } => offset_to_brainfuck(*recurrence),
+            Operation::Zero { .. } => "[-]".to_string(),
+            Operation::Set { value, .. } => format!("[-]{}", offset_to_brainfuck(*value)),
+            Operation::Multiply { factors, .. } => multiplication_to_brainfuck(factors),
             Operation::LooseBracket { operator, .. } => operator.to_string(),
             Operation::Text { src } => { buf.push_str(&src); continue; },
+            Operation::Visit { .. } => continue,
         };
 
         buf.push_str(&op_str);
@@ -304,6 +410,48 @@ fn operations_to_brainfuck(ops: &[Operation]) -> String {
     buf
 }
 
+/// Same as [`operations_to_brainfuck`], but moves the tape pointer to `endpoint` at the end.
+fn operations_to_brainfuck_ending_at(ops: &[Operation], endpoint: isize) -> String {
+    let mut buf = operations_to_brainfuck(ops);
+
+    // find the last position we got put on
+    let last_position = ops.iter().rev().find_map(Operation::cell_position).unwrap_or(0);
+
+    let difference = endpoint - last_position;
+    let movement_ch = if difference.is_positive() { '>' } else { '<' };
+    for _ in 0..(difference.abs()) {
+        buf.push(movement_ch);
+    }
+
+    buf
+}
+
+/// Returns the `+` or `-` operators which offset a cell by `recurrence`.
+fn offset_to_brainfuck(recurrence: i32) -> String {
+    let offset_ch = if recurrence.is_positive() { '+' } else { '-' };
+    (0..recurrence.abs()).map(|_| offset_ch).collect()
+}
+
+/// Returns the shortest loop multiplying the current cell by `factors`.
+/// It visits the cells on the right first and then the ones on the left, which is always the shortest path.
+fn multiplication_to_brainfuck(factors: &[(isize, i32)]) -> String {
+    let mut buf = String::from("[-");
+    let mut position = 0;
+    let right = factors.iter().filter(|(cell, _)| cell.is_positive());
+    let left = factors.iter().rev().filter(|(cell, _)| cell.is_negative());
+    for (cell, factor) in right.chain(left) {
+        let movement_ch = if *cell > position { '>' } else { '<' };
+        buf.extend((0..cell.abs_diff(position)).map(|_| movement_ch));
+        buf.push_str(&offset_to_brainfuck(*factor));
+        position = *cell;
+    }
+
+    // we return to the loop cell, going through the remaining cells
+    buf.extend((0..position.unsigned_abs()).map(|_| if position.is_positive() { '<' } else { '>' }));
+    buf.push(']');
+    buf
+}
+
 /// Returns the range of valid positions the operator at `idx` can be inserted at.
 /// If the index is invalid returns an empty `0..0` range.
 fn operation_validity_range<'a, 'b>(ops: &'a [Operation<'b>], idx: usize) -> Range<usize> {
@@ -364,12 +512,12 @@ mod tests {
         let (ops, end_point) = parse_operations(">>,[---++]++--<<");
         assert!(end_point.is_none());
         assert_matches!(ops[0], Operation::InOut { cell: 2, operator: ',' });
-        assert_matches!(ops[1], Operation::Block { cell: 2, .. });
+        assert_matches!(ops[1], Operation::Zero { cell: 2 });
 
         let (ops, end_point) = parse_operations(">>,[--+]++--<<");
         assert!(end_point.is_none());
         assert_matches!(ops[0], Operation::InOut { cell: 2, operator: ',' });
-        assert_matches!(ops[1], Operation::Block { cell: 2, .. });
+        assert_matches!(ops[1], Operation::Zero { cell: 2 });
 
         let (ops, end_point) = parse_operations("did \n you know +>.[<atmic bomb[++-]");
         assert!(end_point.is_none());
@@ -378,7 +526,7 @@ mod tests {
         assert_matches!(ops[2], Operation::InOut { cell: 1, operator: '.' });
         assert_matches!(ops[3], Operation::LooseBracket { cell: 1, operator: '[' });
         assert_matches!(ops[4], Operation::Text { src: "atmic bomb" });
-        assert_matches!(ops[5], Operation::Block { cell: 0, .. });
+        assert_matches!(ops[5], Operation::Zero { cell: 0 });
     }
 
     #[test]
@@ -409,6 +557,21 @@ mod tests {
         assert!(!ops[0].can_swap(&ops[2]));
         assert!(!ops[1].can_swap(&ops[2]));
         assert!(!ops[2].can_swap(&ops[1]));
+
+        // io within blocks is not reorganised either
+        let (ops, _) = parse_operations(">.<[>>.<<]");
+        assert!(!ops[0].can_swap(&ops[1]));
+        assert!(!ops[1].can_swap(&ops[0]));
+
+        // io and loops don't cross loops, which may never end
+        let (ops, _) = parse_operations("+>+<[]>.");
+        assert!(!ops[2].can_swap(&ops[3]));
+        assert!(!ops[3].can_swap(&ops[2]));
+        assert!(ops[1].can_swap(&ops[2]));
+        let (ops, _) = parse_operations("[<+>>+<]>>>[--]");
+        assert!(!ops[0].can_swap(&ops[1]));
+        let (ops, _) = parse_operations("[-<+>]>.");
+        assert!(ops[0].can_swap(&ops[1]));
     }
 
     #[test]
@@ -423,14 +586,14 @@ mod tests {
 
         // behaviour is the same
         let program = include_str!("../../test-resources/fib.bf");
-        let mut inter =  InterpreterBuilder::new(&program).finish();
+        let mut inter =  InterpreterBuilder::new(&program).with_output_capture().finish();
         inter.complete().unwrap();
-        let original_output = inter.captured_output();
+        let original_output = inter.captured_output().unwrap();
 
         let parsed_and_rebuilt_program = operations_to_brainfuck(&parse_operations(&program).0);
-        let mut inter =  InterpreterBuilder::new(&parsed_and_rebuilt_program).finish();
+        let mut inter =  InterpreterBuilder::new(&parsed_and_rebuilt_program).with_output_capture().finish();
         inter.complete().unwrap();
-        let new_output = inter.captured_output();
+        let new_output = inter.captured_output().unwrap();
 
         assert_eq!(original_output, new_output);
     }
@@ -458,5 +621,19 @@ mod tests {
         let (ops, _) = parse_operations("+++>[>]<--");
         assert!(!ops[0].can_swap(&ops[1]));
         assert_eq!(operation_validity_range(&ops, 0), 0..1);
+
+        // even when the drift of the block is known, where it exits is not
+        let (ops, _) = parse_operations("+>>[->>+<]<<-");
+        assert!(!ops[0].can_swap(&ops[1]));
+        assert!(!ops[2].can_swap(&ops[1]));
+        assert_eq!(operation_validity_range(&ops, 2), 2..3);
+
+        // the block never reaches cell 0, but once across it the `+` operates on the cell 2 left of where it exits
+        let (ops, _) = parse_operations("+>>+[>]");
+        assert!(!ops[0].can_swap(&ops[2]));
+        let crossed = operations_to_brainfuck(&[ops[1].clone(), ops[2].clone(), ops[0].clone()]);
+        assert_eq!(crossed, ">>+[>]<<+");
+        let divergence = verify_optimisation("+>>+[>]", &crossed, &[""]).unwrap_err();
+        assert_eq!(divergence.kind, DivergenceKind::Tape { cell: 0, unoptimised: "1".to_string(), optimised: "0".to_string() });
     }
 }
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use crate::optimiser::{self, Operation};
+
+/// Merges multiplication loops which move a cell through a temporary cell, when the temporary cell starts at 0.
+/// Ex: `>[-]<[->+<]>[->+<]` becomes `>[-]<[->>+<<]`.
+pub fn merge_multiplications(ops: &mut Vec<Operation>) {
+    while let Some((idx, jdx, merged)) = find_merge(ops) {
+        ops[idx] = merged;
+        ops.remove(jdx);
+    }
+
+    // do it recursively
+    ops.iter_mut()
+        .filter_map(|op| if let Operation::Block { block, .. } = op {
+            Some(block)
+        } else {
+            None
+        })
+        .for_each(|block| block.apply_optimisation(merge_multiplications));
+}
+
+/// Finds a multiplication which can be merged into the multiplication filling its cell.
+/// Returns the index of the filling multiplication, the index of the one merged into it, and the merged operation.
+fn find_merge<'a>(ops: &[Operation<'a>]) -> Option<(usize, usize, Operation<'a>)> {
+    for (jdx, op) in ops.iter().enumerate() {
+        let Operation::Multiply { cell: temporary, factors: second_factors } = op else { continue };
+
+        // the operation just before the range is the one preventing the multiplication from moving further back
+        let range = optimiser::operation_validity_range(ops, jdx);
+        let Some(idx) = range.start.checked_sub(1) else { continue };
+        let Operation::Multiply { cell: source, factors: first_factors } = &ops[idx] else { continue };
+
+        let Some(&(_, factor)) = first_factors.iter().find(|(c, _)| source + c == *temporary) else { continue };
+        // the source is zeroed before the second loop adds to it again
+        if second_factors.iter().any(|(c, _)| temporary + c == *source) {
+            continue;
+        }
+
+        if !starts_at_zero(&ops[..idx], *temporary) {
+            continue;
+        }
+
+        let mut factors = first_factors.iter()
+            .filter(|(c, _)| source + c != *temporary)
+            .copied()
+            .collect::<BTreeMap<_, _>>();
+        let mut overflows = false;
+        for (c, second_factor) in second_factors {
+            let added = factor.checked_mul(*second_factor);
+            let merged_factor = factors.entry(temporary + c - source).or_insert(0);
+            match added.and_then(|added| merged_factor.checked_add(added)) {
+                Some(value) => *merged_factor = value,
+                None => overflows = true,
+            }
+        }
+        if overflows {
+            continue;
+        }
+
+        let factors = factors.into_iter().filter(|(_, factor)| *factor != 0).collect::<Vec<_>>();
+        let merged = if factors.is_empty() {
+            Operation::Zero { cell: *source }
+        } else {
+            Operation::Multiply { cell: *source, factors }
+        };
+
+        return Some((idx, jdx, merged))
+    }
+
+    None
+}
+
+/// Returns `true` if `cell` is known to be 0 at the end of `ops`, because the last operation writing it zeroes it.
+fn starts_at_zero(ops: &[Operation], cell: isize) -> bool {
+    for op in ops.iter().rev() {
+        match op {
+            Operation::Zero { cell: c } | Operation::Multiply { cell: c, .. } | Operation::Block { cell: c, .. } if *c == cell => return true,
+            op if op.fences_cell(cell) || op.modified_cells().contains(&cell) => return false,
+            _ => (),
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimiser::{operations_to_brainfuck, parse_operations};
+
+    use super::*;
+
+    #[test]
+    fn merging_multiplications() {
+        let mut ops = parse_operations(">[-]<[->++<]>[->+++<]").0;
+        merge_multiplications(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">[-]<[->>++++++<<]");
+
+        // other operations are in the way
+        let mut ops = parse_operations(",>[.]<[->+>+<<]>>.<[->>+<<]").0;
+        merge_multiplications(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",>[.]<[->>+>+<<<]>>.");
+
+        // the temporary cell is not known to start at 0
+        let mut ops = parse_operations(",[->+<]>[->+<]").0;
+        merge_multiplications(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[->+<]>[->+<]");
+
+        // the source is filled again
+        let mut ops = parse_operations(">[-]<[->+<]>[-<+>>+<]").0;
+        merge_multiplications(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">[-]<[->+<]>[->+<<+>]");
+
+        // something reads the temporary cell in between
+        let mut ops = parse_operations(">[-]<[->+<]>.[->+<]").0;
+        merge_multiplications(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">[-]<[->+<]>.[->+<]");
+
+        // the factors cancel out
+        let mut ops = parse_operations(">[-]<[->+>+<<]>[->-<]").0;
+        merge_multiplications(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">[-]<[-]");
+    }
+}
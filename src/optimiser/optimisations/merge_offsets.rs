@@ -42,7 +42,7 @@ pub fn merge_offsets<'a, 'b>(ops: &'a mut Vec<Operation<'b>>) {
                 true
             } else { false });
 
-        let other_offset = if let Some((other_offset, 0)) = other_offset_opt {
+        let other_offset = if let Some((other_offset, _)) = other_offset_opt {
             other_offset
         } else {
             continue;
@@ -57,11 +57,14 @@ pub fn merge_offsets<'a, 'b>(ops: &'a mut Vec<Operation<'b>>) {
         *recurence = 0;
     }
 
-    // Cleanup, we remove all the offsets we set to 0
+    merge_offsets_into_zeroes(ops, &offsets_indexes);
+
+    // Cleanup, we remove all the offsets we set to 0, and sets to 0 are simply zeroes
     let tmp_ops = mem::take(ops);
-    *ops = tmp_ops.into_iter().filter(|op| match op {
-        Operation::Offset { recurrence: 0, ..} => false,
-        _ => true
+    *ops = tmp_ops.into_iter().filter_map(|op| match op {
+        Operation::Offset { recurrence: 0, ..} => None,
+        Operation::Set { cell, value: 0 } => Some(Operation::Zero { cell }),
+        op => Some(op),
     }).collect();
 
     // do this recursively for all blocks
@@ -74,6 +77,31 @@ pub fn merge_offsets<'a, 'b>(ops: &'a mut Vec<Operation<'b>>) {
         .for_each(|block| block.apply_optimisation(merge_offsets));
 }
 
+/// Merges the offsets which can be moved right after a zero or a set of their cell into it.
+/// Ex: `[-]>+<++` becomes `[-]++>+<`, with the `[-]++` being a single set.
+fn merge_offsets_into_zeroes(ops: &mut [Operation], offsets_indexes: &[usize]) {
+    for offset_index in offsets_indexes {
+        let Operation::Offset { cell: self_cell, recurrence: self_recurrence } = ops[*offset_index] else { continue };
+        if self_recurrence == 0 {
+            continue;
+        }
+
+        // the operation just before the range is the one preventing the offset from moving further back
+        let range = optimiser::operation_validity_range(ops, *offset_index);
+        let Some(previous_index) = range.start.checked_sub(1) else { continue };
+        match &mut ops[previous_index] {
+            Operation::Zero { cell } if *cell == self_cell => {
+                ops[previous_index] = Operation::Set { cell: self_cell, value: self_recurrence };
+            },
+            Operation::Set { cell, value } if *cell == self_cell => *value += self_recurrence,
+            _ => continue,
+        }
+
+        let Operation::Offset { recurrence, .. } = &mut ops[*offset_index] else { unreachable!() };
+        *recurrence = 0;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,5 +138,23 @@ mod tests {
         merge_offsets(&mut ops);
         let string = optimiser::operations_to_brainfuck(&ops);
         assert_eq!(string, "++++>>[-][-<<+>>]");
+
+        // offsets after zeroes become sets
+        let (mut ops, _) = optimiser::parse_operations("[-]>+<++>[-]<-");
+        merge_offsets(&mut ops);
+        assert_eq!(ops[0], Operation::Set { cell: 0, value: 1 });
+        let string = optimiser::operations_to_brainfuck(&ops);
+        assert_eq!(string, "[-]+>+[-]");
+
+        let (mut ops, _) = optimiser::parse_operations("[-]+.-");
+        merge_offsets(&mut ops);
+        let string = optimiser::operations_to_brainfuck(&ops);
+        assert_eq!(string, "[-]+.-");
+
+        let (mut ops, _) = optimiser::parse_operations("[-]+>+<-");
+        merge_offsets(&mut ops);
+        assert_eq!(ops[0], Operation::Zero { cell: 0 });
+        let string = optimiser::operations_to_brainfuck(&ops);
+        assert_eq!(string, "[-]>+");
     }
 }
@@ -5,4 +5,8 @@ pub use merge_offsets::merge_offsets;
 mod reorder_operations;
 pub use reorder_operations::reorder_operations;
 mod remove_offsets_before_zeroing;
-pub use remove_offsets_before_zeroing::remove_offsets_before_zeroing;
\ No newline at end of file
+pub use remove_offsets_before_zeroing::remove_offsets_before_zeroing;
+mod propagate_known_values;
+pub use propagate_known_values::propagate_known_values;
+mod merge_multiplications;
+pub use merge_multiplications::merge_multiplications;
\ No newline at end of file
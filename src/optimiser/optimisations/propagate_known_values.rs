@@ -0,0 +1,335 @@
+use std::{collections::{HashMap, HashSet}, mem};
+
+use crate::optimiser::Operation;
+
+/// Tracks the values of cells through the program, since the tape starts all zero and zeroes leave cells at known values.
+/// Uses them to remove loops that can never execute, zeroes and sets of cells which already hold the value,
+/// and to turn sets into offsets when the offset is shorter.
+///
+/// Values are tracked as exact sums of offsets, so only a value of 0 is known to be 0 whatever the size of the cells.
+/// Blocks with a known drift, like `[>]`, leave the cells ahead of everything they and the operations before them touched untouched.
+/// Dead writes, which are overwritten before being read, are then removed by [`remove_offsets_before_zeroing`](super::remove_offsets_before_zeroing).
+pub fn propagate_known_values(ops: &mut Vec<Operation>) {
+    let mut known = KnownValues::with_default(Some(0));
+    propagate(ops, &mut known);
+}
+
+/// The values of the cells relative to the start of a section, if they are known.
+#[derive(Debug, Clone, PartialEq)]
+struct KnownValues {
+    /// The cells which differ from `default`, `None` if the value is unknown.
+    cells: HashMap<isize, Option<i32>>,
+    /// The value of all the other cells.
+    default: Option<i32>,
+    /// The first cell holding the default value, the cells before it are unknown. `None` if there is no such bound.
+    first_default: Option<isize>,
+    /// The last cell holding the default value, the cells after it are unknown. `None` if there is no such bound.
+    last_default: Option<isize>,
+}
+
+impl KnownValues {
+    fn with_default(default: Option<i32>) -> KnownValues {
+        KnownValues { cells: HashMap::new(), default, first_default: None, last_default: None }
+    }
+
+    fn get(&self, cell: isize) -> Option<i32> {
+        if let Some(value) = self.cells.get(&cell) {
+            return *value
+        }
+
+        let before_first = self.first_default.is_some_and(|first| cell < first);
+        let after_last = self.last_default.is_some_and(|last| cell > last);
+        if before_first || after_last {
+            None
+        } else {
+            self.default
+        }
+    }
+
+    fn set(&mut self, cell: isize, value: Option<i32>) {
+        self.cells.insert(cell, value);
+    }
+
+    fn forget_all(&mut self) {
+        *self = KnownValues::with_default(None);
+    }
+
+    /// The same values, relative to `cell`.
+    fn relative_to(&self, cell: isize) -> KnownValues {
+        KnownValues {
+            cells: self.cells.iter().map(|(c, value)| (c - cell, *value)).collect(),
+            default: self.default,
+            first_default: self.first_default.map(|c| c - cell),
+            last_default: self.last_default.map(|c| c - cell),
+        }
+    }
+
+    /// Returns how far the cells which may not hold the default value reach in the direction of the drift
+    /// of a block at `cell` modifying the `modified` cells of each iteration, relative to `cell` and counted along the drift.
+    /// The cells reached at the end of the block, relative to where it exits, and the cells reached in an iteration,
+    /// relative to where it starts, are all within that reach, since the block only moves along the drift.
+    /// Returns `None` if no cell is known to hold the default value after the block, since it may run for ever.
+    fn drift_reach(&self, cell: isize, drift: isize, modified: &HashSet<isize>) -> Option<isize> {
+        let direction = drift.signum();
+        let (behind, ahead) = if direction > 0 {
+            (self.first_default, self.last_default)
+        } else {
+            (self.last_default, self.first_default)
+        };
+        if ahead.is_some() {
+            return None
+        }
+
+        let reach = self.cells.keys().copied()
+            .chain(behind.map(|c| c - direction))
+            .map(|c| (c - cell) * direction)
+            // a previous iteration started one drift behind
+            .chain(modified.iter().map(|c| c * direction - drift.abs()))
+            .fold(0, isize::max);
+
+        Some(reach)
+    }
+
+    /// The values past the reach of a block drifting by `drift`, relative to `cell` (see [`KnownValues::drift_reach`]).
+    fn past_drift_reach(&self, cell: isize, drift: isize, reach: Option<isize>) -> KnownValues {
+        let mut known = KnownValues::with_default(None);
+        if let Some(reach) = reach {
+            known.default = self.default;
+            let bound = Some(cell + drift.signum() * (reach + 1));
+            if drift > 0 {
+                known.first_default = bound;
+            } else {
+                known.last_default = bound;
+            }
+        }
+
+        known
+    }
+}
+
+/// Propagates `known` through the operations, `known` is left with the values at the end of the section.
+fn propagate(ops: &mut Vec<Operation>, known: &mut KnownValues) {
+    let tmp_ops = mem::take(ops);
+    for mut op in tmp_ops {
+        match &mut op {
+            Operation::Offset { cell, recurrence } => {
+                let value = known.get(*cell).and_then(|v| v.checked_add(*recurrence));
+                known.set(*cell, value);
+            },
+            Operation::Zero { cell } => {
+                let cell = *cell;
+                match known.get(cell) {
+                    Some(0) => continue,
+                    // `-` is shorter than `[-]`
+                    Some(value @ -2..=2) => op = Operation::Offset { cell, recurrence: -value },
+                    _ => (),
+                }
+                known.set(cell, Some(0));
+            },
+            Operation::Set { cell, value } => {
+                let (cell, value) = (*cell, *value);
+                if let Some(recurrence) = known.get(cell).and_then(|v| value.checked_sub(v)) {
+                    if recurrence == 0 {
+                        continue;
+                    }
+
+                    // the set is `[-]` followed by the value
+                    if recurrence.unsigned_abs() < value.unsigned_abs() + 3 {
+                        op = Operation::Offset { cell, recurrence };
+                    }
+                }
+                known.set(cell, Some(value));
+            },
+            Operation::Multiply { cell, factors } => {
+                let head = known.get(*cell);
+                // the loop is never entered
+                if head == Some(0) {
+                    continue;
+                }
+
+                // the loop runs `head` times, even when the cells wrap
+                for (c, factor) in factors.iter() {
+                    let value = known.get(c + *cell)
+                        .zip(head.and_then(|head| head.checked_mul(*factor)))
+                        .and_then(|(value, added)| value.checked_add(added));
+                    known.set(c + *cell, value);
+                }
+                known.set(*cell, Some(0));
+            },
+            Operation::InOut { cell, operator: ',' } => known.set(*cell, None),
+            Operation::InOut { .. } | Operation::Text { .. } | Operation::Visit { .. } => (),
+            Operation::Block { cell, block } => {
+                // the loop is never entered
+                if known.get(*cell) == Some(0) {
+                    continue;
+                }
+
+                // relative to the block, or to the start of the iteration if the block drifts
+                let modified_cells = block.modified_cells();
+                let drift = block.drift();
+                let reach = drift.and_then(|drift| known.drift_reach(*cell, drift, &modified_cells));
+                let mut inner_known = match drift {
+                    // cells the block does not modify keep their value in every iteration
+                    Some(0) => {
+                        let mut inner_known = known.relative_to(*cell);
+                        for c in &modified_cells {
+                            inner_known.set(*c, None);
+                        }
+                        inner_known
+                    },
+                    // only the cells past the reach of the block are known in every iteration
+                    Some(drift) => known.past_drift_reach(0, drift, reach),
+                    None => KnownValues::with_default(None),
+                };
+                inner_known.set(0, None);
+                block.apply_optimisation(|ops| propagate(ops, &mut inner_known));
+
+                match drift {
+                    Some(0) => for c in modified_cells {
+                        known.set(c + *cell, None);
+                    },
+                    // the operations after the block are relative to where it exits
+                    Some(drift) => *known = known.past_drift_reach(*cell, drift, reach),
+                    None => known.forget_all(),
+                }
+                // we only leave the loop once its cell is 0
+                known.set(*cell, Some(0));
+
+                // removing dead loops may have left a clear loop
+                if block.is_clear_loop() {
+                    op = Operation::Zero { cell: *cell };
+                }
+            },
+            // we can't know where we come from after a loose bracket
+            Operation::LooseBracket { .. } => known.forget_all(),
+        }
+
+        ops.push(op);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimiser::{operations_to_brainfuck, parse_operations};
+
+    use super::*;
+
+    #[test]
+    fn dead_loops_are_removed() {
+        // the tape starts all zero
+        let mut ops = parse_operations("[->+<]>[-]+[-<+>]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">+[-<+>]");
+
+        // loops end on a zero cell
+        let mut ops = parse_operations(",[->+<][.]>.").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[->+<]>.");
+
+        let mut ops = parse_operations(">,[->>+<<]>>[<].").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">,[->>+<<]>>[<].");
+
+        let mut ops = parse_operations(",[>][.]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>]");
+
+        // within blocks, with the values known from outside
+        let mut ops = parse_operations(",[>[.]<-]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(ops[1], Operation::Zero { cell: 0 });
+
+        let mut ops = parse_operations(",[>[.]<[>+<-]]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>[.]<[->+<]]");
+
+        // unknown values are kept
+        let mut ops = parse_operations(",[.]>>,<[.]>[.]<<<[.]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[.]>>,[.]");
+
+        let mut ops = parse_operations("+[.]>++++[.]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), "+[.]>++++[.]");
+
+        let mut ops = parse_operations("]>[.]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), "]>[.]");
+    }
+
+    #[test]
+    fn multiplications_are_tracked() {
+        let mut ops = parse_operations("[->+<]+++[->++>+<<]>>>[.]<<<[.]>[.]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), "+++[->++>+<<]>[.]");
+
+        let mut ops = parse_operations(",[->+<]>[.]<[.]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[->+<]>[.]");
+    }
+
+    #[test]
+    fn drifting_blocks_are_tracked() {
+        // scans leave the cells ahead of them untouched
+        let mut ops = parse_operations(",[>]>[-]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>]");
+
+        let mut ops = parse_operations(">>>,[<]<[.]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">>>,[<]");
+
+        // the cells modified by the block and before it may be reached
+        let mut ops = parse_operations(",[->>+<]>[-]>[-]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[->>+<]>[-]");
+
+        let mut ops = parse_operations(">>>>+<<<<,[>]>[-]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">>>>+<<<<,[>]>[-]");
+
+        // within the block, the cells ahead of it are untouched in every iteration
+        let mut ops = parse_operations(",[>[-]]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>]");
+
+        let mut ops = parse_operations(",[>>[-]+<]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>>+<]");
+
+        let mut ops = parse_operations(",[>>[-]+<<<]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>>[-]+<<<]");
+
+        // unknown drifts forget everything
+        let mut ops = parse_operations(",[>[>]]>[-]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>[>]]>[-]");
+
+        // the block could drift into unknown cells
+        let mut ops = parse_operations(",[>]>[-],[<]<<[-]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ",[>]>,[<]<<[-]");
+    }
+
+    #[test]
+    fn known_values_are_folded() {
+        // zeroes of cells which are already zero
+        let mut ops = parse_operations("[-]>+<[-],[-]").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), ">+<,[-]");
+
+        // small values are zeroed with offsets
+        let mut ops = parse_operations("++.[-].").0;
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), "++.--.");
+
+        // sets of known cells become offsets when it is shorter
+        let mut ops = parse_operations("+++++.").0;
+        ops.push(Operation::Set { cell: 0, value: 6 });
+        ops.push(Operation::Set { cell: 0, value: 6 });
+        ops.push(Operation::Set { cell: 0, value: -6 });
+        propagate_known_values(&mut ops);
+        assert_eq!(operations_to_brainfuck(&ops), "+++++.+[-]------");
+    }
+}
@@ -1,9 +1,17 @@
 use crate::optimiser::{self, Operation};
 
-// TODO: This does not take into account the forced offset of dynamic blocks
-// (assumes that the pointer always ends at 0)
+// TODO: This does not move operations across dynamic blocks with a known drift, like flyers (see `Operation::can_swap`)
 /// Reorders operations as much as fencing allows to minimise the amount of tape pointer movement.
+/// Operations are never moved across dynamic blocks, even the ones with a known drift, like `[>]`,
+/// only within them and on each side of them.
 pub fn reorder_operations(ops: &mut Vec<Operation>) {
+    reorder_section(ops, 0);
+}
+
+/// Reorders the operations of a section which ends with the tape pointer at `endpoint`.
+/// Dynamic blocks end each iteration at their endpoint, and the operations after them are relative to where they exit,
+/// so they don't need more care than that.
+fn reorder_section(ops: &mut Vec<Operation>, endpoint: isize) {
     let mut ops_tracker = OperationProcessTracker::new(ops);
 
     // moves around all operations once (the once part is handled by OperationProcessTracker) to minimise distance
@@ -17,7 +25,7 @@ pub fn reorder_operations(ops: &mut Vec<Operation>) {
 
         // we fold to find the pair `(index, lost_distance)` with the lowest lost_distance.
         let (best_index, _) = validity_range.fold((unprocessed_index, usize::MAX), |(best_index, best_distance), i| {
-            let current_distance = calculate_lost_distance(&operation, ops_tracker.operations(), i);
+            let current_distance = calculate_lost_distance(&operation, ops_tracker.operations(), i, endpoint);
             if current_distance < best_distance {
                 (i, current_distance)
             } else {
@@ -36,14 +44,17 @@ pub fn reorder_operations(ops: &mut Vec<Operation>) {
         } else {
             None
         })
-        .for_each(|block| block.apply_optimisation(reorder_operations));
+        .for_each(|block| {
+            let endpoint = block.endpoint();
+            block.apply_optimisation(|ops| reorder_section(ops, endpoint));
+        });
 }
 
 /// Calculates the extra distance that would need to be traveled to reach the operation if it were to be inserted at `idx`.
 /// Ex: positions with positions `[0, 5, 4]` adding an operation with position 2 at 1 would have a lost distance of 0,
 /// but inserting at 2 would have a lost distance of 5 because we would need to move `-3` and `+2` rather than the `-1` prior.
-/// This assumes that the operations end and start at 0.
-fn calculate_lost_distance(op: &Operation, ops: &[Operation], idx: usize) -> usize {
+/// This assumes that the operations start at 0, and end at `endpoint`.
+fn calculate_lost_distance(op: &Operation, ops: &[Operation], idx: usize, endpoint: isize) -> usize {
     // finds the cell position of the first item before it that has a cell position
     // or default to 0
     let position_before = ops[..idx].iter()
@@ -51,11 +62,11 @@ fn calculate_lost_distance(op: &Operation, ops: &[Operation], idx: usize) -> usi
         .map(|op| op.cell_position().unwrap())
         .unwrap_or(0);
 
-    // same but for after
+    // same but for after, or default to where we end
     let position_after = ops[idx..].iter()
         .find(|op| op.cell_position().is_some())
         .map(|op| op.cell_position().unwrap())
-        .unwrap_or(0);
+        .unwrap_or(endpoint);
 
     let distance_before_insert = position_before.abs_diff(position_after);
 
@@ -153,5 +164,17 @@ mod tests {
         reorder_operations(&mut ops);
         let string = optimiser::operations_to_brainfuck(&ops);
         assert_eq!(string, "[-]+>++");
+
+        // dynamic blocks end their iterations at their endpoint
+        let (mut ops, _) = optimiser::parse_operations("[>>-<<+>]");
+        reorder_operations(&mut ops);
+        let string = optimiser::operations_to_brainfuck(&ops);
+        assert_eq!(string, "[+>>-<]");
+
+        // operations after dynamic blocks are relative to where they exit
+        let (mut ops, _) = optimiser::parse_operations("[>]>>-<<+>+");
+        reorder_operations(&mut ops);
+        let string = optimiser::operations_to_brainfuck(&ops);
+        assert_eq!(string, "[>]+>+>-");
     }
 }
\ No newline at end of file
@@ -0,0 +1,287 @@
+//! Configurable sequences of optimisation passes.
+
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+use super::{optimisations, parse, operations_to_brainfuck_ending_at, report::{BfStats, OptimisationReport, PassReport}, Operation};
+
+/// The maximum number of times a pipeline runs its passes when running them to a fixed point.
+pub const MAX_FIXED_POINT_ITERATIONS: usize = 32;
+
+/// An optimisation pass, which rewrites the operations of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pass {
+    /// Reorders operations to minimise the amount of tape pointer movement.
+    Reorder,
+    /// Merges offsets of the same cell, and offsets following a zero into it.
+    MergeOffsets,
+    /// Merges multiplication loops which move a cell through a temporary cell.
+    MergeMultiplications,
+    /// Removes loops which never run and writes of values cells already hold, knowing the tape starts all zero.
+    KnownValues,
+    /// Removes writes to cells which are zeroed before being read.
+    DeadWrites,
+}
+
+impl Pass {
+    /// All the passes.
+    pub const ALL: [Pass; 5] = [Pass::Reorder, Pass::MergeOffsets, Pass::MergeMultiplications, Pass::KnownValues, Pass::DeadWrites];
+
+    /// The name of the pass, as used by `--passes`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Pass::Reorder => "reorder",
+            Pass::MergeOffsets => "merge-offsets",
+            Pass::MergeMultiplications => "merge-multiplications",
+            Pass::KnownValues => "known-values",
+            Pass::DeadWrites => "dead-writes",
+        }
+    }
+
+    pub(crate) fn apply(self, ops: &mut Vec<Operation>) {
+        match self {
+            Pass::Reorder => optimisations::reorder_operations(ops),
+            Pass::MergeOffsets => optimisations::merge_offsets(ops),
+            Pass::MergeMultiplications => optimisations::merge_multiplications(ops),
+            Pass::KnownValues => optimisations::propagate_known_values(ops),
+            Pass::DeadWrites => optimisations::remove_offsets_before_zeroing(ops),
+        }
+    }
+}
+
+impl fmt::Display for Pass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Pass {
+    type Err = UnknownPassError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Pass::ALL.into_iter()
+            .find(|pass| pass.name() == s)
+            .ok_or_else(|| UnknownPassError { name: s.to_string() })
+    }
+}
+
+/// The name of a pass does not match any pass.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("unknown optimisation pass `{name}`, expected one of {}", Pass::ALL.map(Pass::name).join(", "))]
+pub struct UnknownPassError {
+    /// The name which did not match
+    pub name: String,
+}
+
+/// How much a program gets optimised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OptimisationLevel {
+    /// No optimisation, the program is left untouched.
+    O0,
+    /// All passes run once, without the documented changes of behaviour:
+    /// the tape pointer still ends where it would have and still goes as far on the tape.
+    O1,
+    /// All passes run once, the tape pointer may end elsewhere and moves not leading to operations are removed.
+    #[default]
+    O2,
+    /// Like [`O2`](OptimisationLevel::O2), but passes run until they stop changing the program.
+    O3,
+}
+
+impl OptimisationLevel {
+    /// The pipeline of the level, `None` for [`O0`](OptimisationLevel::O0).
+    pub fn pipeline(self) -> Option<Pipeline> {
+        // running the reordering twice shaves off a few characters,
+        // and removing dead loops may allow more merges
+        let passes = [
+            Pass::Reorder,
+            Pass::MergeOffsets,
+            Pass::MergeMultiplications,
+            Pass::Reorder,
+            Pass::KnownValues,
+            Pass::MergeOffsets,
+            Pass::DeadWrites,
+        ];
+
+        match self {
+            OptimisationLevel::O0 => None,
+            OptimisationLevel::O1 => Some(Pipeline::new(passes).with_strictness()),
+            OptimisationLevel::O2 => Some(Pipeline::new(passes)),
+            OptimisationLevel::O3 => Some(Pipeline::new(passes).with_fixed_point()),
+        }
+    }
+}
+
+/// A sequence of passes optimising brainfuck programs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pipeline {
+    passes: Vec<Pass>,
+    fixed_point: bool,
+    strict: bool,
+}
+
+impl Pipeline {
+    /// Creates a pipeline running the `passes` once, in order.
+    pub fn new(passes: impl IntoIterator<Item = Pass>) -> Pipeline {
+        Pipeline {
+            passes: passes.into_iter().collect(),
+            fixed_point: false,
+            strict: false,
+        }
+    }
+
+    /// Runs the passes again until they stop changing the program (at most [`MAX_FIXED_POINT_ITERATIONS`] times).
+    #[must_use]
+    pub fn with_fixed_point(mut self) -> Self {
+        self.fixed_point = true;
+        self
+    }
+
+    /// Keeps the behaviour of the program strictly the same, the tape pointer ends where it would have
+    /// and moves reaching further on the tape than before are kept, so that the program fails at the same place.
+    #[must_use]
+    pub fn with_strictness(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Replaces the passes of the pipeline.
+    #[must_use]
+    pub fn with_passes(mut self, passes: impl IntoIterator<Item = Pass>) -> Self {
+        self.passes = passes.into_iter().collect();
+        self
+    }
+
+    /// The passes run by the pipeline, in order.
+    pub fn passes(&self) -> &[Pass] {
+        &self.passes
+    }
+
+    /// Returns `true` if the pipeline keeps the behaviour of programs strictly the same.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Optimises the brainfuck program.
+    pub fn run(&self, bf: &str) -> String {
+        self.optimise(bf, None)
+    }
+
+    /// Optimises the brainfuck program, and reports what each pass did to it.
+    pub fn run_with_report(&self, bf: &str) -> (String, OptimisationReport) {
+        let mut report = OptimisationReport { input: BfStats::of(bf), passes: Vec::new() };
+        let optimised = self.optimise(bf, Some(&mut |pass_report| report.passes.push(pass_report)));
+
+        (optimised, report)
+    }
+
+    /// Optimises the brainfuck program, giving the report of each pass run to `on_pass` if there is one.
+    fn optimise(&self, bf: &str, mut on_pass: Option<&mut dyn FnMut(PassReport)>) -> String {
+        let (mut operations, endpoint) = parse(bf, self.strict);
+        let endpoint = endpoint.unwrap_or(0);
+
+        for iteration in 0..MAX_FIXED_POINT_ITERATIONS {
+            let previous = self.fixed_point.then(|| operations.clone());
+            for pass in &self.passes {
+                let Some(on_pass) = on_pass.as_mut() else {
+                    pass.apply(&mut operations);
+                    continue
+                };
+
+                let before = operations.clone();
+                pass.apply(&mut operations);
+                on_pass(PassReport::new(
+                    *pass,
+                    iteration,
+                    (&self.emit(&before, endpoint), &before),
+                    (&self.emit(&operations, endpoint), &operations),
+                ));
+            }
+
+            if previous.is_none_or(|previous| previous == operations) {
+                break
+            }
+        }
+
+        self.emit(&operations, endpoint)
+    }
+
+    /// Turns the operations back into brainfuck, strict pipelines end where the program ended.
+    fn emit(&self, operations: &[Operation], endpoint: isize) -> String {
+        if self.strict {
+            operations_to_brainfuck_ending_at(operations, endpoint)
+        } else {
+            super::operations_to_brainfuck(operations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{optimise, optimiser::verify_optimisation};
+
+    use super::*;
+
+    #[test]
+    fn pass_names() {
+        for pass in Pass::ALL {
+            assert_eq!(pass.name().parse(), Ok(pass));
+        }
+
+        assert_eq!(
+            "reorder-operations".parse::<Pass>().unwrap_err().to_string(),
+            "unknown optimisation pass `reorder-operations`, expected one of \
+            reorder, merge-offsets, merge-multiplications, known-values, dead-writes",
+        );
+    }
+
+    #[test]
+    fn optimisation_levels() {
+        assert_eq!(OptimisationLevel::O0.pipeline(), None);
+
+        let program = include_str!("../../test-resources/fib.bf");
+        assert_eq!(OptimisationLevel::default().pipeline().unwrap().run(program), optimise(program));
+        for level in [OptimisationLevel::O1, OptimisationLevel::O3] {
+            let optimised = level.pipeline().unwrap().run(program);
+            assert_eq!(verify_optimisation(program, &optimised, &[""]), Ok(()));
+        }
+    }
+
+    #[test]
+    fn strict_pipeline() {
+        let strict = OptimisationLevel::O1.pipeline().unwrap();
+        let lenient = OptimisationLevel::O2.pipeline().unwrap();
+
+        // trailing moves are kept
+        assert_eq!(strict.run("+>+>>"), "+>+>>");
+        assert_eq!(lenient.run("+>+>>"), "+>+");
+
+        // moves before cell 0 are kept where they are
+        assert_eq!(strict.run("+<<>>.<>"), "+<<>>.");
+        assert_eq!(lenient.run("+<<>>.<>"), "+.");
+        assert_eq!(strict.run(",[<<<>>>->>>><<<<]"), ",[<<<>>>->>>><<<<]");
+        assert_eq!(strict.run(",[>]<<>>>+<<<"), ",[>]<<>>>+<<<");
+
+        // operations are still optimised, but not across moves reaching further
+        assert_eq!(strict.run(">+<+++>-<--"), "><+");
+        assert_eq!(strict.run("+++>-<--<<>>"), "+++>-<--<<>>");
+
+        // io and loops stay on the same side of loops which may never end, or fail
+        assert_eq!(strict.run("+>+<[]>."), "+>+<[]>.");
+        assert_eq!(strict.run(">>><<<----+++++--[-<+>>+<]>>>[--]."), ">>><<<-[-<+>>+<]>>>.");
+        assert_eq!(strict.run("+>+<[<<+>>-]>[]"), "+>+<[<<+>>-]>[]");
+    }
+
+    #[test]
+    fn fixed_point_pipeline() {
+        // the merge is only possible once the offsets are merged
+        let once = Pipeline::new([Pass::MergeMultiplications, Pass::MergeOffsets]);
+        let fixed = once.clone().with_fixed_point();
+        let program = ">[-]<,[->+<]>+<.>-[->+<]";
+        assert_eq!(once.run(program), ">[-]<,[->+<].>[->+<]");
+        assert_eq!(fixed.run(program), ">[-]<,[->>+<<].");
+
+        assert_eq!(Pipeline::new([]).run("+-+.>"), "+.");
+    }
+}
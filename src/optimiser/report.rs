@@ -0,0 +1,314 @@
+//! Statistics of the optimisation passes, used to find out which passes help a program.
+
+use std::mem;
+
+use colored::Colorize as _;
+
+use super::{operations_to_brainfuck, Operation, Pass};
+
+/// The width of the column of the operations before a pass, when printing the changes side by side.
+const DIFF_COLUMN_WIDTH: usize = 40;
+/// The maximum number of entries of the table used to match the operations kept by a pass (16 MiB).
+/// Past it, all the operations between the first and the last changed ones are shown as changed.
+const MAX_DIFF_TABLE_LEN: usize = 1 << 22;
+
+/// The size of a brainfuck program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BfStats {
+    /// The number of characters of the program, comments included.
+    pub length: usize,
+    /// The number of `<` and `>` of the program.
+    pub pointer_moves: usize,
+}
+
+impl BfStats {
+    /// Measures the brainfuck program.
+    pub fn of(bf: &str) -> BfStats {
+        BfStats {
+            length: bf.chars().count(),
+            pointer_moves: bf.chars().filter(|ch| matches!(ch, '<' | '>')).count(),
+        }
+    }
+}
+
+/// What a pipeline did to a program, pass by pass.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OptimisationReport {
+    /// The program before it was optimised.
+    pub input: BfStats,
+    /// The runs of the passes, in the order they ran.
+    pub passes: Vec<PassReport>,
+}
+
+/// What a single run of a pass did to a program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassReport {
+    /// The pass which ran.
+    pub pass: Pass,
+    /// The iteration of the pipeline the pass ran in, starting at 0.
+    /// Only pipelines running to a fixed point run more than one iteration.
+    pub iteration: usize,
+    /// The program before the pass.
+    pub before: BfStats,
+    /// The program after the pass.
+    pub after: BfStats,
+    /// The operations before the pass, one per line, the operations of blocks are indented within them.
+    pub operations_before: Vec<String>,
+    /// The operations after the pass, in the same format as [`operations_before`](PassReport::operations_before).
+    pub operations_after: Vec<String>,
+}
+
+/// A row of the side by side diff of the operations changed by a pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationChange {
+    /// The operation removed by the pass, if any.
+    pub before: Option<String>,
+    /// The operation added by the pass in its place, if any.
+    pub after: Option<String>,
+    /// The index of the row in the operations before the pass, where the operation removed is or would be.
+    pub line_before: usize,
+    /// The index of the row in the operations after the pass, where the operation added is or would be.
+    pub line_after: usize,
+}
+
+impl OptimisationReport {
+    /// The program once optimised, the same as the input if no pass ran.
+    pub fn output(&self) -> BfStats {
+        self.passes.last().map_or(self.input, |pass| pass.after)
+    }
+
+    /// Prints the statistics of each pass to stdout.
+    /// If `with_changes` is `true`, also prints the operations each pass changed side by side.
+    pub fn print(&self, with_changes: bool) {
+        println!("{}", "-- OPTIMISATION REPORT --".red().underline().bold());
+        println!("{} {}", "input:".black(), describe_stats(self.input));
+        for (i, pass) in self.passes.iter().enumerate() {
+            println!(
+                "{} {} {}",
+                format!("#{}:", i+1).black(),
+                pass.pass.to_string().bold(),
+                describe_change(pass.before, pass.after),
+            );
+
+            if with_changes {
+                for change in pass.changes() {
+                    let line = |line: usize, operation: &Option<String>| operation.as_ref()
+                        .map(|_| (line + 1).to_string())
+                        .unwrap_or_default();
+                    println!(
+                        "    {} {} {} {} {}",
+                        format!("{:>5}", line(change.line_before, &change.before)).black(),
+                        format!("{:DIFF_COLUMN_WIDTH$}", change.before.as_deref().map(clip).unwrap_or_default()).red(),
+                        "|".black(),
+                        format!("{:>5}", line(change.line_after, &change.after)).black(),
+                        change.after.as_deref().unwrap_or_default().green(),
+                    );
+                }
+            }
+        }
+        println!("{} {}", "output:".black(), describe_stats(self.output()));
+        println!();
+    }
+}
+
+impl PassReport {
+    pub(super) fn new(pass: Pass, iteration: usize, (before, operations_before): (&str, &[Operation]), (after, operations_after): (&str, &[Operation])) -> PassReport {
+        PassReport {
+            pass,
+            iteration,
+            before: BfStats::of(before),
+            after: BfStats::of(after),
+            operations_before: describe_operations(operations_before),
+            operations_after: describe_operations(operations_after),
+        }
+    }
+
+    /// Returns `true` if the pass changed the operations of the program.
+    pub fn changed(&self) -> bool {
+        self.operations_before != self.operations_after
+    }
+
+    /// Returns the operations changed by the pass, as the rows of a side by side diff.
+    /// Operations removed are put next to the operations added in their place, the operations kept are left out.
+    ///
+    /// Matching the operations kept takes memory proportional to the product of the number of operations changed
+    /// before and after the pass. When a pass changes too many operations (aka reordering most of a large program),
+    /// the operations between the first and the last changed ones are all shown as changed instead.
+    pub fn changes(&self) -> Vec<OperationChange> {
+        let (before, after) = (&self.operations_before, &self.operations_after);
+        let prefix = before.iter().zip(after).take_while(|(b, a)| b == a).count();
+        let suffix = before[prefix..].iter().rev().zip(after[prefix..].iter().rev()).take_while(|(b, a)| b == a).count();
+        let (before, after) = (&before[prefix..before.len()-suffix], &after[prefix..after.len()-suffix]);
+
+        if (before.len() + 1).saturating_mul(after.len() + 1) > MAX_DIFF_TABLE_LEN {
+            let mut changes = Vec::new();
+            push_changes(&mut changes, (&mut before.to_vec(), prefix), (&mut after.to_vec(), prefix));
+            return changes
+        }
+
+        // the longest common subsequence of `before[i..]` and `after[j..]` is at `i * (after.len() + 1) + j`
+        let width = after.len() + 1;
+        let mut common = vec![0u32; (before.len() + 1) * width];
+        for i in (0..before.len()).rev() {
+            for j in (0..after.len()).rev() {
+                common[i * width + j] = if before[i] == after[j] {
+                    common[(i+1) * width + j+1] + 1
+                } else {
+                    common[(i+1) * width + j].max(common[i * width + j+1])
+                };
+            }
+        }
+
+        let mut changes = Vec::new();
+        let (mut removed, mut added) = (Vec::new(), Vec::new());
+        let (mut i, mut j) = (0, 0);
+        // where the current removed and added operations start
+        let (mut start_i, mut start_j) = (0, 0);
+        while i < before.len() || j < after.len() {
+            if i < before.len() && j < after.len() && before[i] == after[j] {
+                push_changes(&mut changes, (&mut removed, prefix + start_i), (&mut added, prefix + start_j));
+                i += 1;
+                j += 1;
+                (start_i, start_j) = (i, j);
+            } else if j == after.len() || (i < before.len() && common[(i+1) * width + j] >= common[i * width + j+1]) {
+                removed.push(before[i].clone());
+                i += 1;
+            } else {
+                added.push(after[j].clone());
+                j += 1;
+            }
+        }
+        push_changes(&mut changes, (&mut removed, prefix + start_i), (&mut added, prefix + start_j));
+
+        changes
+    }
+}
+
+/// Puts the `removed` and `added` operations next to each other as rows of `changes`, along with the lines they start at.
+fn push_changes(changes: &mut Vec<OperationChange>, (removed, line_before): (&mut Vec<String>, usize), (added, line_after): (&mut Vec<String>, usize)) {
+    let rows = removed.len().max(added.len());
+    let mut removed = mem::take(removed).into_iter();
+    let mut added = mem::take(added).into_iter();
+    changes.extend((0..rows).map(|row| OperationChange {
+        before: removed.next(),
+        after: added.next(),
+        line_before: line_before + row,
+        line_after: line_after + row,
+    }));
+}
+
+/// Describes the operations one per line, as their brainfuck preceded by their cell,
+/// the operations of blocks are indented between the lines of the brackets.
+fn describe_operations(ops: &[Operation]) -> Vec<String> {
+    let mut lines = Vec::new();
+    describe_section(ops, 0, &mut lines);
+
+    lines
+}
+
+fn describe_section(ops: &[Operation], depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    for op in ops {
+        let cell = op.cell_position().map(|cell| format!("{cell}: ")).unwrap_or_default();
+        match op {
+            Operation::Block { block, .. } => {
+                lines.push(format!("{indent}{cell}["));
+                describe_section(&block.operations, depth + 1, lines);
+                if block.is_dynamic() {
+                    lines.push(format!("{indent}] ends at {}", block.endpoint()));
+                } else {
+                    lines.push(format!("{indent}]"));
+                }
+            },
+            Operation::Visit { .. } => lines.push(format!("{indent}{cell}visit")),
+            op => {
+                let bf = operations_to_brainfuck(std::slice::from_ref(op));
+                lines.push(format!("{indent}{cell}{}", bf.trim_start_matches(['<', '>'])));
+            },
+        }
+    }
+}
+
+fn describe_stats(stats: BfStats) -> String {
+    format!("{} chars, {} moves", stats.length, stats.pointer_moves)
+}
+
+fn describe_change(before: BfStats, after: BfStats) -> String {
+    let difference = |before: usize, after: usize| format!("{:+}", after as isize - before as isize);
+
+    format!(
+        "{} -> {} chars ({}), {} -> {} moves ({})",
+        before.length, after.length, difference(before.length, after.length),
+        before.pointer_moves, after.pointer_moves, difference(before.pointer_moves, after.pointer_moves),
+    )
+}
+
+/// Cuts the line to fit in the column of the side by side diff.
+fn clip(line: &str) -> String {
+    if line.chars().count() > DIFF_COLUMN_WIDTH {
+        let clipped = line.chars().take(DIFF_COLUMN_WIDTH - 1).collect::<String>();
+        format!("{clipped}…")
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimiser::Pipeline;
+
+    use super::*;
+
+    #[test]
+    fn pass_reports() {
+        let pipeline = Pipeline::new([Pass::MergeOffsets, Pass::DeadWrites]);
+        let (optimised, report) = pipeline.run_with_report("+>>-<<+>>->[.]");
+        assert_eq!(optimised, pipeline.run("+>>-<<+>>->[.]"));
+
+        assert_eq!(report.input, BfStats { length: 14, pointer_moves: 7 });
+        assert_eq!(report.output(), BfStats::of(&optimised));
+        assert_eq!(report.passes.iter().map(|pass| pass.pass).collect::<Vec<_>>(), [Pass::MergeOffsets, Pass::DeadWrites]);
+        assert_eq!(report.passes[0].before, BfStats { length: 14, pointer_moves: 7 });
+        assert_eq!(report.passes[0].after, BfStats { length: 10, pointer_moves: 3 });
+        assert_eq!(report.passes[1].before, report.passes[0].after);
+
+        // no write is overwritten
+        assert!(report.passes[0].changed());
+        assert!(!report.passes[1].changed());
+        assert_eq!(report.passes[1].changes(), []);
+    }
+
+    #[test]
+    fn pass_changes() {
+        let pipeline = Pipeline::new([Pass::KnownValues]);
+        let (_, report) = pipeline.run_with_report(",[->+<][.]>>++[-].");
+        assert_eq!(report.passes[0].operations_before, [
+            "0: ,", "0: [->+<]", "0: [", "  0: .", "]", "2: ++", "2: [-]", "2: .",
+        ].map(String::from));
+        assert_eq!(report.passes[0].operations_after, ["0: ,", "0: [->+<]", "2: ++", "2: --", "2: ."].map(String::from));
+
+        // the kept operations are left out, and the replaced ones are next to each other
+        let change = |before: Option<&str>, after: Option<&str>, line_before, line_after| OperationChange {
+            before: before.map(String::from),
+            after: after.map(String::from),
+            line_before,
+            line_after,
+        };
+        assert_eq!(report.passes[0].changes(), [
+            change(Some("0: ["), None, 2, 2),
+            change(Some("  0: ."), None, 3, 3),
+            change(Some("]"), None, 4, 4),
+            change(Some("2: [-]"), Some("2: --"), 6, 3),
+        ]);
+
+        // passes changing too many operations don't get their kept operations matched
+        let operations = (0..3000).map(|i| format!("{i}: +")).collect::<Vec<_>>();
+        let mut report = report.passes[0].clone();
+        report.operations_before = [vec!["0: ,".to_string()], operations.clone()].concat();
+        report.operations_after = [vec!["0: ,".to_string()], operations.into_iter().rev().collect()].concat();
+        let changes = report.changes();
+        assert_eq!(changes.len(), 3000);
+        assert_eq!(changes[0], change(Some("0: +"), Some("2999: +"), 1, 1));
+        assert_eq!(changes[2999], change(Some("2999: +"), Some("0: +"), 3000, 3000));
+    }
+}
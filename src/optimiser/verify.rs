@@ -0,0 +1,327 @@
+//! Differential verification of the optimiser, running the programs before and after optimisation side by side.
+
+use std::{error::Error, fmt::{self, Display}, mem};
+
+use crate::interpreter::{InterpreterBuilder, InterpreterError, InterpreterTrait, SharedBuffer};
+
+/// The number of byte codes the optimised program can execute per input, the unoptimised one can execute twice as many.
+/// The verification of an input stops once both programs reach their limit, only one of them reaching it is a divergence.
+pub const VERIFICATION_STEP_LIMIT: usize = 10_000_000;
+
+/// The inputs used to verify optimisations when none are specified.
+pub const DEFAULT_VERIFICATION_INPUTS: [&str; 4] = ["", "a", "Hello, World!\n", "0123456789 ~!?\n"];
+
+/// Checks that `optimised` behaves like `unoptimised` when given each of the `inputs`.
+/// Returns the first divergence found.
+///
+/// Both programs run on `u8` wrapping cells, with the input streamed and reading past its end giving 0.
+/// Their outputs are compared after each output operation, and the tapes once both programs end.
+/// The optimiser is allowed to change behaviour in a few documented ways, which are not considered divergences:
+/// - the position of the tape pointer at the end of the program (trailing moves are removed),
+/// - the number of allocated cells, since unallocated cells are 0,
+/// - the programs moving the tape pointer into negatives, moves may be merged so that the pointer does not go before cell 0 anymore.
+///   Both programs run on a tape infinite in both directions, so going into negatives is never an error.
+///
+/// Also, the verification of an input stops once both programs reach their step limit (see [`VERIFICATION_STEP_LIMIT`]).
+/// By then, the programs may have output different amounts, so the output of one only has to start with the output of the other,
+/// as long as the unoptimised program output something.
+pub fn verify_optimisation(unoptimised: &str, optimised: &str, inputs: &[&str]) -> Result<(), Box<Divergence>> {
+    verify_with_step_limits(unoptimised, optimised, inputs, 2 * VERIFICATION_STEP_LIMIT, VERIFICATION_STEP_LIMIT)
+}
+
+/// Same as [`verify_optimisation`], but with the given step limits for each program.
+pub(super) fn verify_with_step_limits(
+    unoptimised: &str,
+    optimised: &str,
+    inputs: &[&str],
+    unoptimised_step_limit: usize,
+    optimised_step_limit: usize,
+) -> Result<(), Box<Divergence>> {
+    for input in inputs {
+        let divergence = find_divergence(unoptimised, optimised, input, unoptimised_step_limit, optimised_step_limit);
+        if let Some((kind, unoptimised_offset, optimised_offset)) = divergence {
+            return Err(Box::new(Divergence { input: (*input).to_string(), kind, unoptimised_offset, optimised_offset }))
+        }
+    }
+
+    Ok(())
+}
+
+/// A difference of behaviour between a program and its optimised version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// The input given to the programs.
+    pub input: String,
+    /// How the programs diverged.
+    pub kind: DivergenceKind,
+    /// The offset in bytes in the unoptimised brainfuck of the operator where it diverged.
+    /// `None` if the program had ended.
+    pub unoptimised_offset: Option<usize>,
+    /// The offset in bytes in the optimised brainfuck of the operator where it diverged.
+    /// `None` if the program had ended.
+    pub optimised_offset: Option<usize>,
+}
+
+/// How a program and its optimised version diverged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DivergenceKind {
+    /// The programs output different things, or only one of them output something.
+    Output {
+        /// The output of the unoptimised program up to the divergence.
+        unoptimised: String,
+        /// The output of the optimised program up to the divergence.
+        optimised: String,
+    },
+    /// Only one of the programs stopped because of an error, or they stopped because of different errors.
+    Outcome {
+        /// The error which stopped the unoptimised program, if any.
+        unoptimised: Option<InterpreterError>,
+        /// The error which stopped the optimised program, if any.
+        optimised: Option<InterpreterError>,
+    },
+    /// The programs ended with a different value in a cell.
+    Tape {
+        /// The index of the first cell which differs, relative to the cell the programs started on.
+        cell: isize,
+        /// The decimal value of the cell in the unoptimised program.
+        unoptimised: String,
+        /// The decimal value of the cell in the optimised program.
+        optimised: String,
+    },
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let offset = |offset: Option<usize>| offset.map_or("the end".to_string(), |o| format!("bf offset {o}"));
+
+        write!(f, "the optimised program diverges with the input {:?}: ", self.input)?;
+        match &self.kind {
+            DivergenceKind::Output { unoptimised, optimised } => {
+                write!(f, "it outputs {optimised:?} instead of {unoptimised:?}")?;
+            },
+            DivergenceKind::Outcome { unoptimised, optimised } => {
+                let outcome = |error: &Option<InterpreterError>| error.as_ref()
+                    .map_or("completes".to_string(), |e| format!("errors with \"{e}\""));
+                write!(f, "it {} while the unoptimised program {}", outcome(optimised), outcome(unoptimised))?;
+            },
+            DivergenceKind::Tape { cell, unoptimised, optimised } => {
+                write!(f, "cell {cell} ends with {optimised} instead of {unoptimised}")?;
+            },
+        }
+
+        write!(f, " (unoptimised at {}, optimised at {})", offset(self.unoptimised_offset), offset(self.optimised_offset))
+    }
+}
+
+impl Error for Divergence {}
+
+/// Compares the programs for a single input, returns the kind of divergence and the offsets where it happened, if they diverge.
+fn find_divergence(
+    unoptimised: &str,
+    optimised: &str,
+    input: &str,
+    unoptimised_step_limit: usize,
+    optimised_step_limit: usize,
+) -> Option<(DivergenceKind, Option<usize>, Option<usize>)> {
+    let build = |bf: &str, step_limit: usize| InterpreterBuilder::new(bf)
+        .with_u8()
+        .with_wrapping_behaviour()
+        .with_bidirectional_tape()
+        .with_input(SharedBuffer::from_bytes(input))
+        .with_streamed_input()
+        .with_eof_as_zero()
+        .with_output(SharedBuffer::new())
+        .with_output_capture()
+        .with_step_limit(step_limit)
+        .finish();
+    let mut unoptimised = build(unoptimised, unoptimised_step_limit);
+    let mut optimised = build(optimised, optimised_step_limit);
+
+    loop {
+        let unoptimised_step = run_until_output(&mut *unoptimised);
+        let optimised_step = run_until_output(&mut *optimised);
+
+        match (unoptimised_step, optimised_step) {
+            // the other program may reach its step limit a little later
+            (Err(InterpreterError::StepLimitExceded { .. }), _) | (_, Err(InterpreterError::StepLimitExceded { .. })) => {
+                // a program which reached its step limit errors again right away
+                let u = unoptimised.complete().err();
+                let o = optimised.complete().err();
+                let reached_limit = |e: &Option<InterpreterError>| matches!(e, Some(InterpreterError::StepLimitExceded { .. }));
+                if !reached_limit(&u) || !reached_limit(&o) {
+                    let kind = DivergenceKind::Outcome { unoptimised: u, optimised: o };
+                    return Some((kind, unoptimised.bf_offset(), optimised.bf_offset()))
+                }
+
+                let unoptimised_output = unoptimised.captured_output().unwrap_or_default();
+                let optimised_output = optimised.captured_output().unwrap_or_default();
+                // either program can get further before reaching its limit, the unoptimised one has more steps
+                // but the optimised one may need much less of them per output.
+                // The optimised program is only allowed to get further if the unoptimised one output something,
+                // otherwise it may be stuck in a loop which never ends (and never outputs) in the unoptimised program
+                let optimised_further = !unoptimised_output.is_empty() && optimised_output.starts_with(unoptimised_output);
+                if unoptimised_output.starts_with(optimised_output) || optimised_further {
+                    return None
+                }
+
+                let kind = DivergenceKind::Output {
+                    unoptimised: unoptimised_output.to_string(),
+                    optimised: optimised_output.to_string(),
+                };
+                return Some((kind, unoptimised.bf_offset(), optimised.bf_offset()))
+            },
+            (Err(u), Err(o)) if mem::discriminant(&u) == mem::discriminant(&o) => return None,
+            (Ok(None), Ok(None)) => break,
+            (Ok(u), Ok(o)) => {
+                let unoptimised_output = unoptimised.captured_output().unwrap_or_default();
+                let optimised_output = optimised.captured_output().unwrap_or_default();
+                if u.is_some() && o.is_some() && unoptimised_output == optimised_output {
+                    continue
+                }
+
+                let kind = DivergenceKind::Output {
+                    unoptimised: unoptimised_output.to_string(),
+                    optimised: optimised_output.to_string(),
+                };
+                return Some((kind, u, o))
+            },
+            (u, o) => {
+                // errored programs are still on the operator which failed
+                let u_offset = u.as_ref().map_or_else(|_| unoptimised.bf_offset(), |offset| *offset);
+                let o_offset = o.as_ref().map_or_else(|_| optimised.bf_offset(), |offset| *offset);
+                let kind = DivergenceKind::Outcome { unoptimised: u.err(), optimised: o.err() };
+                return Some((kind, u_offset, o_offset))
+            },
+        }
+    }
+
+    // the tapes may have grown to the left by different amounts, so cells are compared relative to the starting cell
+    let value = |interpreter: &dyn InterpreterTrait, cell: isize| interpreter.tape_origin().checked_add_signed(cell)
+        .and_then(|index| interpreter.cell_i64(index))
+        .unwrap_or(0);
+    let right_len = |interpreter: &dyn InterpreterTrait| interpreter.tape_len() - interpreter.tape_origin();
+    let first = -(unoptimised.tape_origin().max(optimised.tape_origin()) as isize);
+    let last = right_len(&*unoptimised).max(right_len(&*optimised)) as isize;
+    for cell in first..last {
+        let (u, o) = (value(&*unoptimised, cell), value(&*optimised, cell));
+        if u != o {
+            let kind = DivergenceKind::Tape { cell, unoptimised: u.to_string(), optimised: o.to_string() };
+            return Some((kind, None, None))
+        }
+    }
+
+    None
+}
+
+/// Runs until the program outputs something.
+/// Returns the offset in bytes of the output operator, or `None` if the program completed without outputting.
+fn run_until_output(interpreter: &mut dyn InterpreterTrait) -> Result<Option<usize>, InterpreterError> {
+    loop {
+        let offset = interpreter.bf_offset();
+        let is_output = interpreter.next_operator() == Some('.');
+        if !interpreter.advance()? {
+            return Ok(None)
+        }
+
+        if is_output {
+            return Ok(offset)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+
+    use crate::{optimise, source::SourceFile, transpile};
+
+    use super::*;
+
+    /// Same as [`verify_optimisation`], with step limits small enough to reach quickly in programs which never end.
+    fn verify(unoptimised: &str, optimised: &str, inputs: &[&str]) -> Result<(), Box<Divergence>> {
+        verify_with_step_limits(unoptimised, optimised, inputs, 10_000, 5_000)
+    }
+
+    #[test]
+    fn optimiser_verification() {
+        let programs = [
+            include_str!("../../test-resources/fib.basm"),
+            include_str!("../../test-resources/hello-world.basm"),
+            include_str!("../../test-resources/custom-conditionals.basm"),
+            include_str!("../../test-resources/bf-interpreter.basm"),
+        ];
+
+        for program in programs {
+            let sf = SourceFile::from_raw_parts("testfile".into(), program.to_string()).leak();
+            let bf = transpile(sf).unwrap();
+            assert_eq!(verify(&bf, &optimise(&bf), &DEFAULT_VERIFICATION_INPUTS), Ok(()));
+        }
+
+        // documented exceptions
+        assert_eq!(verify("+>>.<<", "+>>.", &[""]), Ok(()));
+        assert_eq!(verify("+.<", "+.", &[""]), Ok(()));
+        assert_eq!(verify("+<<>>.", "+.", &[""]), Ok(()));
+        assert_eq!(verify("+>>[-]<<", "+", &[""]), Ok(()));
+    }
+
+    #[test]
+    fn optimiser_divergences() {
+        let divergence = verify(",+.", ",+.", &["a"]);
+        assert_eq!(divergence, Ok(()));
+
+        // the first input passes, the second does not
+        let divergence = verify(",[.,]", ",.,", &["a", "ab"]).unwrap_err();
+        assert_eq!(divergence.input, "ab");
+        assert_eq!(divergence.kind, DivergenceKind::Output { unoptimised: "ab".to_string(), optimised: "a".to_string() });
+        assert_eq!((divergence.unoptimised_offset, divergence.optimised_offset), (Some(2), None));
+
+        let divergence = verify("+++.+.", "++.+.", &[""]).unwrap_err();
+        assert_eq!((divergence.unoptimised_offset, divergence.optimised_offset), (Some(3), Some(2)));
+        assert_eq!(
+            divergence.to_string(),
+            "the optimised program diverges with the input \"\": it outputs \"\\u{2}\" instead of \"\\u{3}\" \
+            (unoptimised at bf offset 3, optimised at bf offset 2)",
+        );
+
+        let divergence = verify("+>+", "+>++", &[""]).unwrap_err();
+        assert_eq!(
+            divergence.kind,
+            DivergenceKind::Tape { cell: 1, unoptimised: "1".to_string(), optimised: "2".to_string() },
+        );
+
+        // moves into negatives are not errors
+        let divergence = verify("+.", "+.<+", &[""]).unwrap_err();
+        assert_eq!(
+            divergence.kind,
+            DivergenceKind::Tape { cell: -1, unoptimised: "0".to_string(), optimised: "1".to_string() },
+        );
+        for unoptimised in ["<", "<>"] {
+            let divergence = verify(unoptimised, "+.", &[""]).unwrap_err();
+            assert_eq!(divergence.kind, DivergenceKind::Output { unoptimised: String::new(), optimised: "\u{1}".to_string() });
+            assert_eq!((divergence.unoptimised_offset, divergence.optimised_offset), (None, Some(1)));
+        }
+
+        // only one of the programs reaching the step limit
+        for (unoptimised, input) in [("+.", ""), (",.", "a")] {
+            let divergence = verify(unoptimised, "+[]", &[input]).unwrap_err();
+            assert_matches!(
+                divergence.kind,
+                DivergenceKind::Outcome { unoptimised: None, optimised: Some(InterpreterError::StepLimitExceded { .. }) }
+            );
+            assert_eq!(divergence.unoptimised_offset, None);
+        }
+        let divergence = verify("+[]", "+.", &[""]).unwrap_err();
+        assert_matches!(
+            divergence.kind,
+            DivergenceKind::Outcome { unoptimised: Some(InterpreterError::StepLimitExceded { .. }), optimised: None }
+        );
+        assert_eq!(verify("+[]", "+[+-]", &[""]), Ok(()));
+
+        // the optimised program outputs before a loop the unoptimised one never leaves
+        let divergence = verify("+>+<[]>.", "+>+.<[]>", &[""]).unwrap_err();
+        assert_eq!(divergence.kind, DivergenceKind::Output { unoptimised: String::new(), optimised: "\u{1}".to_string() });
+        // the optimised program is faster, so outputs more before reaching its step limit
+        assert_eq!(verify("+[>++-[-]<.]", "+[>[-]<.]", &[""]), Ok(()));
+        assert_eq!(verify("-[[-++<>].+]", &optimise("-[[-++<>].+]"), &[""]), Ok(()));
+    }
+}
@@ -156,6 +156,19 @@ impl Pattern for InstructionPattern {
     }
 }
 
+impl_language_item!(ScopeIdent, left_bracket, right_bracket);
+
+impl LanguageItem for Argument {
+    fn slice(&self) -> SfSlice {
+        match self {
+            Argument::Expression(expression) => expression.slice(),
+            Argument::Scope(scope) => scope.slice(),
+            Argument::ScopeIdent(scope_ident) => scope_ident.slice(),
+            Argument::String(string) => string.slice(),
+        }
+    }
+}
+
 impl_language_item!(Instruction, name, semicolon);
 
 #[cfg(test)]
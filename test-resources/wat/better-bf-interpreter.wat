@@ -0,0 +1,2745 @@
+;; generated by basm
+(module
+  (import "env" "read" (func $read (result i32)))
+  (import "env" "write" (func $write (param i32)))
+  (memory (export "memory") 1)
+  (global $pointer (mut i32) (i32.const 0))
+
+  ;; returns the address of the cell at `offset` from the tape pointer, traps if it is outside of the tape
+  (func $at (param $offset i32) (result i32)
+    (local $index i32)
+    (local.set $index (i32.add (global.get $pointer) (local.get $offset)))
+    (if (i32.ge_u (local.get $index) (i32.const 30000))
+      (then unreachable))
+    (i32.mul (local.get $index) (i32.const 1)))
+
+  (func $add (param $offset i32) (param $amount i32)
+    (local $address i32)
+    (local.set $address (call $at (local.get $offset)))
+    (i32.store8 (local.get $address) (i32.add (i32.load8_u (local.get $address)) (local.get $amount))))
+
+  (func $input (param $offset i32)
+    (local $byte i32)
+    (local.set $byte (call $read))
+    (if (i32.lt_s (local.get $byte) (i32.const 0))
+      (then (drop (call $at (local.get $offset))))
+      (else (i32.store8 (call $at (local.get $offset)) (i32.and (local.get $byte) (i32.const 255))))))
+
+  (func (export "run")
+    (call $add (i32.const 29) (i32.const 255))
+    (call $add (i32.const 32) (i32.const 255))
+    (global.set $pointer (i32.add (global.get $pointer) (i32.const 32)))
+    (block $exit0 (loop $loop0
+      (br_if $exit0 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+      (call $add (i32.const 0) (i32.const 1))
+      (call $input (i32.const -1))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit1 (loop $loop1
+        (br_if $exit1 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop1)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit2 (loop $loop2
+        (br_if $exit2 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop2)))
+      (call $add (i32.const -1) (i32.const 213))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit3 (loop $loop3
+        (br_if $exit3 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop3)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit4 (loop $loop4
+        (br_if $exit4 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop4)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit5 (loop $loop5
+        (br_if $exit5 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop5)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit6 (loop $loop6
+        (br_if $exit6 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit7 (loop $loop7
+          (br_if $exit7 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 5) (i32.const 1))
+          (call $add (i32.const 6) (i32.const 1))
+          (br $loop7)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (block $exit8 (loop $loop8
+          (br_if $exit8 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop8)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit9 (loop $loop9
+          (br_if $exit9 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 1))
+          (br $loop9)))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit10 (loop $loop10
+          (br_if $exit10 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 1) (i32.const 1))
+          (call $add (i32.const 2) (i32.const 1))
+          (br $loop10)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit11 (loop $loop11
+          (br_if $exit11 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -2) (i32.const 1))
+          (br $loop11)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit12 (loop $loop12
+          (br_if $exit12 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -4) (i32.const 2))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+          (block $exit13 (loop $loop13
+            (br_if $exit13 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 4) (i32.const 1))
+            (call $add (i32.const 5) (i32.const 1))
+            (br $loop13)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+          (block $exit14 (loop $loop14
+            (br_if $exit14 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -5) (i32.const 1))
+            (br $loop14)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit15 (loop $loop15
+            (br_if $exit15 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop15)))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit16 (loop $loop16
+            (br_if $exit16 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 1) (i32.const 1))
+            (call $add (i32.const 2) (i32.const 1))
+            (br $loop16)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+          (block $exit17 (loop $loop17
+            (br_if $exit17 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -2) (i32.const 1))
+            (br $loop17)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit18 (loop $loop18
+            (br_if $exit18 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 2))
+            (call $add (i32.const -4) (i32.const 254))
+            (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (br $loop18)))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -5)) (i32.const 0))
+          (call $add (i32.const -5) (i32.const 1))
+          (call $add (i32.const -4) (i32.const 255))
+          (br $loop12)))
+        (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit19 (loop $loop19
+          (br_if $exit19 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 4) (i32.const 1))
+          (call $add (i32.const 5) (i32.const 1))
+          (br $loop19)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+        (block $exit20 (loop $loop20
+          (br_if $exit20 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -5) (i32.const 1))
+          (br $loop20)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit21 (loop $loop21
+          (br_if $exit21 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const 0) (i32.const 1))
+          (i32.store8 (call $at (i32.const -2)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop21)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (br $loop6)))
+      (call $add (i32.const 0) (i32.const 211))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit22 (loop $loop22
+        (br_if $exit22 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop22)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit23 (loop $loop23
+        (br_if $exit23 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop23)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit24 (loop $loop24
+        (br_if $exit24 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop24)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit25 (loop $loop25
+        (br_if $exit25 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop25)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit26 (loop $loop26
+        (br_if $exit26 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop26)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit27 (loop $loop27
+        (br_if $exit27 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const 0) (i32.const 254))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit28 (loop $loop28
+          (br_if $exit28 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 5) (i32.const 1))
+          (call $add (i32.const 6) (i32.const 1))
+          (br $loop28)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (block $exit29 (loop $loop29
+          (br_if $exit29 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop29)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit30 (loop $loop30
+          (br_if $exit30 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 1))
+          (br $loop30)))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit31 (loop $loop31
+          (br_if $exit31 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 1) (i32.const 1))
+          (call $add (i32.const 2) (i32.const 1))
+          (br $loop31)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit32 (loop $loop32
+          (br_if $exit32 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -2) (i32.const 1))
+          (br $loop32)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit33 (loop $loop33
+          (br_if $exit33 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -4) (i32.const 2))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+          (block $exit34 (loop $loop34
+            (br_if $exit34 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 4) (i32.const 1))
+            (call $add (i32.const 5) (i32.const 1))
+            (br $loop34)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+          (block $exit35 (loop $loop35
+            (br_if $exit35 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -5) (i32.const 1))
+            (br $loop35)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit36 (loop $loop36
+            (br_if $exit36 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop36)))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit37 (loop $loop37
+            (br_if $exit37 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 1) (i32.const 1))
+            (call $add (i32.const 2) (i32.const 1))
+            (br $loop37)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+          (block $exit38 (loop $loop38
+            (br_if $exit38 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -2) (i32.const 1))
+            (br $loop38)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit39 (loop $loop39
+            (br_if $exit39 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 2))
+            (call $add (i32.const -4) (i32.const 254))
+            (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (br $loop39)))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -5)) (i32.const 0))
+          (call $add (i32.const -5) (i32.const 2))
+          (call $add (i32.const -4) (i32.const 255))
+          (br $loop33)))
+        (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 254))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit40 (loop $loop40
+          (br_if $exit40 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 4) (i32.const 1))
+          (call $add (i32.const 5) (i32.const 1))
+          (br $loop40)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+        (block $exit41 (loop $loop41
+          (br_if $exit41 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -5) (i32.const 1))
+          (br $loop41)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit42 (loop $loop42
+          (br_if $exit42 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const 0) (i32.const 1))
+          (i32.store8 (call $at (i32.const -2)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 2))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop42)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (br $loop27)))
+      (call $add (i32.const 0) (i32.const 194))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit43 (loop $loop43
+        (br_if $exit43 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop43)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit44 (loop $loop44
+        (br_if $exit44 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop44)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit45 (loop $loop45
+        (br_if $exit45 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop45)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit46 (loop $loop46
+        (br_if $exit46 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop46)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit47 (loop $loop47
+        (br_if $exit47 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop47)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit48 (loop $loop48
+        (br_if $exit48 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const 0) (i32.const 253))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit49 (loop $loop49
+          (br_if $exit49 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 5) (i32.const 1))
+          (call $add (i32.const 6) (i32.const 1))
+          (br $loop49)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (block $exit50 (loop $loop50
+          (br_if $exit50 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop50)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit51 (loop $loop51
+          (br_if $exit51 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 1))
+          (br $loop51)))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit52 (loop $loop52
+          (br_if $exit52 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 1) (i32.const 1))
+          (call $add (i32.const 2) (i32.const 1))
+          (br $loop52)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit53 (loop $loop53
+          (br_if $exit53 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -2) (i32.const 1))
+          (br $loop53)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit54 (loop $loop54
+          (br_if $exit54 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -4) (i32.const 2))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+          (block $exit55 (loop $loop55
+            (br_if $exit55 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 4) (i32.const 1))
+            (call $add (i32.const 5) (i32.const 1))
+            (br $loop55)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+          (block $exit56 (loop $loop56
+            (br_if $exit56 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -5) (i32.const 1))
+            (br $loop56)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit57 (loop $loop57
+            (br_if $exit57 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop57)))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit58 (loop $loop58
+            (br_if $exit58 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 1) (i32.const 1))
+            (call $add (i32.const 2) (i32.const 1))
+            (br $loop58)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+          (block $exit59 (loop $loop59
+            (br_if $exit59 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -2) (i32.const 1))
+            (br $loop59)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit60 (loop $loop60
+            (br_if $exit60 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 2))
+            (call $add (i32.const -4) (i32.const 254))
+            (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (br $loop60)))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -5)) (i32.const 0))
+          (call $add (i32.const -5) (i32.const 3))
+          (call $add (i32.const -4) (i32.const 255))
+          (br $loop54)))
+        (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 253))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit61 (loop $loop61
+          (br_if $exit61 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 4) (i32.const 1))
+          (call $add (i32.const 5) (i32.const 1))
+          (br $loop61)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+        (block $exit62 (loop $loop62
+          (br_if $exit62 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -5) (i32.const 1))
+          (br $loop62)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit63 (loop $loop63
+          (br_if $exit63 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const 0) (i32.const 1))
+          (i32.store8 (call $at (i32.const -2)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 3))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop63)))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (br $loop48)))
+      (call $add (i32.const 0) (i32.const 196))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit64 (loop $loop64
+        (br_if $exit64 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop64)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit65 (loop $loop65
+        (br_if $exit65 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop65)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit66 (loop $loop66
+        (br_if $exit66 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop66)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit67 (loop $loop67
+        (br_if $exit67 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop67)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit68 (loop $loop68
+        (br_if $exit68 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop68)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit69 (loop $loop69
+        (br_if $exit69 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const 0) (i32.const 252))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit70 (loop $loop70
+          (br_if $exit70 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 5) (i32.const 1))
+          (call $add (i32.const 6) (i32.const 1))
+          (br $loop70)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (block $exit71 (loop $loop71
+          (br_if $exit71 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop71)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit72 (loop $loop72
+          (br_if $exit72 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 1))
+          (br $loop72)))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit73 (loop $loop73
+          (br_if $exit73 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 1) (i32.const 1))
+          (call $add (i32.const 2) (i32.const 1))
+          (br $loop73)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit74 (loop $loop74
+          (br_if $exit74 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -2) (i32.const 1))
+          (br $loop74)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit75 (loop $loop75
+          (br_if $exit75 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -4) (i32.const 2))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+          (block $exit76 (loop $loop76
+            (br_if $exit76 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 4) (i32.const 1))
+            (call $add (i32.const 5) (i32.const 1))
+            (br $loop76)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+          (block $exit77 (loop $loop77
+            (br_if $exit77 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -5) (i32.const 1))
+            (br $loop77)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit78 (loop $loop78
+            (br_if $exit78 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop78)))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit79 (loop $loop79
+            (br_if $exit79 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 1) (i32.const 1))
+            (call $add (i32.const 2) (i32.const 1))
+            (br $loop79)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+          (block $exit80 (loop $loop80
+            (br_if $exit80 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -2) (i32.const 1))
+            (br $loop80)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit81 (loop $loop81
+            (br_if $exit81 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 2))
+            (call $add (i32.const -4) (i32.const 254))
+            (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (br $loop81)))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -5)) (i32.const 0))
+          (call $add (i32.const -5) (i32.const 4))
+          (call $add (i32.const -4) (i32.const 255))
+          (br $loop75)))
+        (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 252))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit82 (loop $loop82
+          (br_if $exit82 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 4) (i32.const 1))
+          (call $add (i32.const 5) (i32.const 1))
+          (br $loop82)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+        (block $exit83 (loop $loop83
+          (br_if $exit83 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -5) (i32.const 1))
+          (br $loop83)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit84 (loop $loop84
+          (br_if $exit84 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const 0) (i32.const 1))
+          (i32.store8 (call $at (i32.const -2)) (i32.const 0))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 4))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop84)))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (br $loop69)))
+      (call $add (i32.const 0) (i32.const 165))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit85 (loop $loop85
+        (br_if $exit85 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop85)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit86 (loop $loop86
+        (br_if $exit86 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop86)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit87 (loop $loop87
+        (br_if $exit87 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop87)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit88 (loop $loop88
+        (br_if $exit88 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop88)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit89 (loop $loop89
+        (br_if $exit89 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop89)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit90 (loop $loop90
+        (br_if $exit90 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+        (call $add (i32.const -2) (i32.const 5))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop90)))
+      (call $add (i32.const 0) (i32.const 163))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit91 (loop $loop91
+        (br_if $exit91 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop91)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit92 (loop $loop92
+        (br_if $exit92 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop92)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit93 (loop $loop93
+        (br_if $exit93 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop93)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit94 (loop $loop94
+        (br_if $exit94 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop94)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit95 (loop $loop95
+        (br_if $exit95 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop95)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit96 (loop $loop96
+        (br_if $exit96 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+        (call $add (i32.const -2) (i32.const 6))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop96)))
+      (call $add (i32.const 0) (i32.const 212))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit97 (loop $loop97
+        (br_if $exit97 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop97)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit98 (loop $loop98
+        (br_if $exit98 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop98)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit99 (loop $loop99
+        (br_if $exit99 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop99)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit100 (loop $loop100
+        (br_if $exit100 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop100)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit101 (loop $loop101
+        (br_if $exit101 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop101)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit102 (loop $loop102
+        (br_if $exit102 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+        (call $add (i32.const -2) (i32.const 7))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop102)))
+      (call $add (i32.const 0) (i32.const 210))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit103 (loop $loop103
+        (br_if $exit103 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop103)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit104 (loop $loop104
+        (br_if $exit104 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop104)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit105 (loop $loop105
+        (br_if $exit105 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop105)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit106 (loop $loop106
+        (br_if $exit106 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop106)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit107 (loop $loop107
+        (br_if $exit107 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop107)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit108 (loop $loop108
+        (br_if $exit108 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+        (call $add (i32.const -2) (i32.const 8))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop108)))
+      (call $add (i32.const 0) (i32.const 223))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit109 (loop $loop109
+        (br_if $exit109 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (call $add (i32.const 4) (i32.const 1))
+        (br $loop109)))
+      (call $add (i32.const 1) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit110 (loop $loop110
+        (br_if $exit110 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -4) (i32.const 1))
+        (br $loop110)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit111 (loop $loop111
+        (br_if $exit111 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop111)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit112 (loop $loop112
+        (br_if $exit112 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop112)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit113 (loop $loop113
+        (br_if $exit113 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop113)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit114 (loop $loop114
+        (br_if $exit114 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (i32.store8 (call $at (i32.const -3)) (i32.const 0))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop114)))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -2)))
+      (br $loop0)))
+    (call $add (i32.const 0) (i32.const 2))
+    (call $add (i32.const -4) (i32.const 1))
+    (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+    (block $exit115 (loop $loop115
+      (br_if $exit115 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+      (call $add (i32.const 0) (i32.const 255))
+      (call $add (i32.const -1) (i32.const 1))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (br $loop115)))
+    (call $add (i32.const -19) (i32.const 255))
+    (global.set $pointer (i32.add (global.get $pointer) (i32.const -19)))
+    (block $exit116 (loop $loop116
+      (br_if $exit116 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+      (call $add (i32.const 0) (i32.const 1))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -2)))
+      (block $exit117 (loop $loop117
+        (br_if $exit117 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 4) (i32.const 1))
+        (call $add (i32.const 5) (i32.const 1))
+        (br $loop117)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit118 (loop $loop118
+        (br_if $exit118 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 17) (i32.const 1))
+        (br $loop118)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+      (block $exit119 (loop $loop119
+        (br_if $exit119 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -5) (i32.const 1))
+        (br $loop119)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 16)))
+      (block $exit120 (loop $loop120
+        (br_if $exit120 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit121 (loop $loop121
+          (br_if $exit121 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -9) (i32.const 1))
+          (br $loop121)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit122 (loop $loop122
+          (br_if $exit122 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop122)))
+        (call $add (i32.const -3) (i32.const 1))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit123 (loop $loop123
+          (br_if $exit123 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop123)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (br $loop120)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit124 (loop $loop124
+        (br_if $exit124 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -3) (i32.const 1))
+        (br $loop124)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit125 (loop $loop125
+        (br_if $exit125 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (block $exit126 (loop $loop126
+          (br_if $exit126 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop126)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit127 (loop $loop127
+          (br_if $exit127 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop127)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+        (block $exit128 (loop $loop128
+          (br_if $exit128 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 9) (i32.const 1))
+          (br $loop128)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop125)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit129 (loop $loop129
+        (br_if $exit129 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -22) (i32.const 1))
+        (br $loop129)))
+      (call $add (i32.const -14) (i32.const 251))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -22)))
+      (block $exit130 (loop $loop130
+        (br_if $exit130 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop130)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit131 (loop $loop131
+        (br_if $exit131 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop131)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit132 (loop $loop132
+        (br_if $exit132 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop132)))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit133 (loop $loop133
+        (br_if $exit133 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop133)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit134 (loop $loop134
+        (br_if $exit134 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop134)))
+      (i32.store8 (call $at (i32.const -2)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit135 (loop $loop135
+        (br_if $exit135 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -3) (i32.const 1))
+        (br $loop135)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit136 (loop $loop136
+        (br_if $exit136 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -9) (i32.const 1))
+        (br $loop136)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit137 (loop $loop137
+        (br_if $exit137 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop137)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit138 (loop $loop138
+        (br_if $exit138 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop138)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit139 (loop $loop139
+        (br_if $exit139 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop139)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit140 (loop $loop140
+        (br_if $exit140 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -7)))
+        (block $exit141 (loop $loop141
+          (br_if $exit141 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 1) (i32.const 1))
+          (call $add (i32.const 4) (i32.const 1))
+          (br $loop141)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+        (block $exit142 (loop $loop142
+          (br_if $exit142 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -4) (i32.const 1))
+          (br $loop142)))
+        (call $add (i32.const 0) (i32.const 1))
+        (block $exit143 (loop $loop143
+          (br_if $exit143 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const -3) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit144 (loop $loop144
+            (br_if $exit144 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 5) (i32.const 1))
+            (call $add (i32.const 6) (i32.const 1))
+            (br $loop144)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+          (block $exit145 (loop $loop145
+            (br_if $exit145 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 15) (i32.const 1))
+            (br $loop145)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+          (block $exit146 (loop $loop146
+            (br_if $exit146 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -6) (i32.const 1))
+            (br $loop146)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 14)))
+          (block $exit147 (loop $loop147
+            (br_if $exit147 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (block $exit148 (loop $loop148
+              (br_if $exit148 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const -9) (i32.const 1))
+              (br $loop148)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+            (block $exit149 (loop $loop149
+              (br_if $exit149 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const 3) (i32.const 1))
+              (br $loop149)))
+            (call $add (i32.const -3) (i32.const 1))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+            (block $exit150 (loop $loop150
+              (br_if $exit150 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const 3) (i32.const 1))
+              (br $loop150)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+            (br $loop147)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit151 (loop $loop151
+            (br_if $exit151 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop151)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+          (block $exit152 (loop $loop152
+            (br_if $exit152 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (block $exit153 (loop $loop153
+              (br_if $exit153 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const -3) (i32.const 1))
+              (br $loop153)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (block $exit154 (loop $loop154
+              (br_if $exit154 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const -3) (i32.const 1))
+              (br $loop154)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+            (block $exit155 (loop $loop155
+              (br_if $exit155 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const 9) (i32.const 1))
+              (br $loop155)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (br $loop152)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit156 (loop $loop156
+            (br_if $exit156 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -16) (i32.const 1))
+            (br $loop156)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -16)))
+          (block $exit157 (loop $loop157
+            (br_if $exit157 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 2) (i32.const 1))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop157)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit158 (loop $loop158
+            (br_if $exit158 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop158)))
+          (call $add (i32.const -1) (i32.const 251))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit159 (loop $loop159
+            (br_if $exit159 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop159)))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit160 (loop $loop160
+            (br_if $exit160 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 1) (i32.const 1))
+            (call $add (i32.const 2) (i32.const 1))
+            (br $loop160)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+          (block $exit161 (loop $loop161
+            (br_if $exit161 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -2) (i32.const 1))
+            (br $loop161)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit162 (loop $loop162
+            (br_if $exit162 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop162)))
+          (call $add (i32.const 0) (i32.const 250))
+          (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -2)))
+          (block $exit163 (loop $loop163
+            (br_if $exit163 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 2) (i32.const 1))
+            (call $add (i32.const 16) (i32.const 1))
+            (br $loop163)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+          (block $exit164 (loop $loop164
+            (br_if $exit164 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop164)))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit165 (loop $loop165
+            (br_if $exit165 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 1) (i32.const 1))
+            (call $add (i32.const 2) (i32.const 1))
+            (br $loop165)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+          (block $exit166 (loop $loop166
+            (br_if $exit166 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -2) (i32.const 1))
+            (br $loop166)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+          (block $exit167 (loop $loop167
+            (br_if $exit167 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+            (call $add (i32.const -3) (i32.const 255))
+            (br $loop167)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+          (block $exit168 (loop $loop168
+            (br_if $exit168 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 6) (i32.const 1))
+            (call $add (i32.const 17) (i32.const 1))
+            (br $loop168)))
+          (i32.store8 (call $at (i32.const 5)) (i32.const 0))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (block $exit169 (loop $loop169
+            (br_if $exit169 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -6) (i32.const 1))
+            (br $loop169)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 11)))
+          (block $exit170 (loop $loop170
+            (br_if $exit170 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+            (block $exit171 (loop $loop171
+              (br_if $exit171 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const -12) (i32.const 1))
+              (br $loop171)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+            (block $exit172 (loop $loop172
+              (br_if $exit172 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const 3) (i32.const 1))
+              (br $loop172)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+            (block $exit173 (loop $loop173
+              (br_if $exit173 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const 3) (i32.const 1))
+              (br $loop173)))
+            (call $add (i32.const -3) (i32.const 1))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+            (block $exit174 (loop $loop174
+              (br_if $exit174 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const 3) (i32.const 1))
+              (br $loop174)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+            (br $loop170)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit175 (loop $loop175
+            (br_if $exit175 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop175)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+          (block $exit176 (loop $loop176
+            (br_if $exit176 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (block $exit177 (loop $loop177
+              (br_if $exit177 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const -3) (i32.const 1))
+              (br $loop177)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+            (block $exit178 (loop $loop178
+              (br_if $exit178 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+              (call $add (i32.const 0) (i32.const 255))
+              (call $add (i32.const 12) (i32.const 1))
+              (br $loop178)))
+            (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+            (br $loop176)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -11)))
+          (br $loop143)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+        (block $exit179 (loop $loop179
+          (br_if $exit179 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 7) (i32.const 1))
+          (call $add (i32.const 19) (i32.const 1))
+          (br $loop179)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit180 (loop $loop180
+          (br_if $exit180 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 2) (i32.const 1))
+          (call $add (i32.const 5) (i32.const 1))
+          (br $loop180)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit181 (loop $loop181
+          (br_if $exit181 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 19) (i32.const 1))
+          (br $loop181)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit182 (loop $loop182
+          (br_if $exit182 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -5) (i32.const 1))
+          (br $loop182)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit183 (loop $loop183
+          (br_if $exit183 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -7) (i32.const 1))
+          (br $loop183)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 12)))
+        (block $exit184 (loop $loop184
+          (br_if $exit184 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (block $exit185 (loop $loop185
+            (br_if $exit185 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -12) (i32.const 1))
+            (br $loop185)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit186 (loop $loop186
+            (br_if $exit186 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop186)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit187 (loop $loop187
+            (br_if $exit187 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop187)))
+          (call $add (i32.const -3) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit188 (loop $loop188
+            (br_if $exit188 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop188)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (br $loop184)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit189 (loop $loop189
+          (br_if $exit189 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop189)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit190 (loop $loop190
+          (br_if $exit190 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (block $exit191 (loop $loop191
+            (br_if $exit191 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop191)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+          (block $exit192 (loop $loop192
+            (br_if $exit192 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 12) (i32.const 1))
+            (br $loop192)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop190)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -15)))
+        (block $exit193 (loop $loop193
+          (br_if $exit193 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 6) (i32.const 1))
+          (call $add (i32.const 18) (i32.const 1))
+          (br $loop193)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit194 (loop $loop194
+          (br_if $exit194 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (call $add (i32.const 6) (i32.const 1))
+          (br $loop194)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit195 (loop $loop195
+          (br_if $exit195 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 19) (i32.const 1))
+          (br $loop195)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit196 (loop $loop196
+          (br_if $exit196 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop196)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit197 (loop $loop197
+          (br_if $exit197 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop197)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 12)))
+        (block $exit198 (loop $loop198
+          (br_if $exit198 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (block $exit199 (loop $loop199
+            (br_if $exit199 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -12) (i32.const 1))
+            (br $loop199)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit200 (loop $loop200
+            (br_if $exit200 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop200)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit201 (loop $loop201
+            (br_if $exit201 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop201)))
+          (call $add (i32.const -3) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit202 (loop $loop202
+            (br_if $exit202 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop202)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (br $loop198)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit203 (loop $loop203
+          (br_if $exit203 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop203)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit204 (loop $loop204
+          (br_if $exit204 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (block $exit205 (loop $loop205
+            (br_if $exit205 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop205)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+          (block $exit206 (loop $loop206
+            (br_if $exit206 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 12) (i32.const 1))
+            (br $loop206)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop204)))
+        (i32.store8 (call $at (i32.const -15)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+        (br $loop140)))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -7)))
+      (block $exit207 (loop $loop207
+        (br_if $exit207 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 7) (i32.const 1))
+        (call $add (i32.const 18) (i32.const 1))
+        (br $loop207)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit208 (loop $loop208
+        (br_if $exit208 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 22) (i32.const 1))
+        (br $loop208)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 8)))
+      (block $exit209 (loop $loop209
+        (br_if $exit209 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop209)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 11)))
+      (block $exit210 (loop $loop210
+        (br_if $exit210 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (block $exit211 (loop $loop211
+          (br_if $exit211 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -12) (i32.const 1))
+          (br $loop211)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit212 (loop $loop212
+          (br_if $exit212 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop212)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit213 (loop $loop213
+          (br_if $exit213 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop213)))
+        (call $add (i32.const -3) (i32.const 1))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit214 (loop $loop214
+          (br_if $exit214 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop214)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (br $loop210)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit215 (loop $loop215
+        (br_if $exit215 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (br $loop215)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit216 (loop $loop216
+        (br_if $exit216 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (block $exit217 (loop $loop217
+          (br_if $exit217 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop217)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit218 (loop $loop218
+          (br_if $exit218 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 12) (i32.const 1))
+          (br $loop218)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop216)))
+      (call $add (i32.const -15) (i32.const 1))
+      (call $add (i32.const -13) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -13)))
+      (br $loop116)))
+    (i32.store8 (call $at (i32.const -2)) (i32.const 0))
+    (call $add (i32.const -8) (i32.const 255))
+    (global.set $pointer (i32.add (global.get $pointer) (i32.const -8)))
+    (block $exit219 (loop $loop219
+      (br_if $exit219 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+      (call $add (i32.const 0) (i32.const 1))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -2)))
+      (block $exit220 (loop $loop220
+        (br_if $exit220 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 8) (i32.const 1))
+        (call $add (i32.const 9) (i32.const 1))
+        (br $loop220)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 8)))
+      (block $exit221 (loop $loop221
+        (br_if $exit221 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 21) (i32.const 1))
+        (br $loop221)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+      (block $exit222 (loop $loop222
+        (br_if $exit222 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -9) (i32.const 1))
+        (br $loop222)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 20)))
+      (block $exit223 (loop $loop223
+        (br_if $exit223 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit224 (loop $loop224
+          (br_if $exit224 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -9) (i32.const 1))
+          (br $loop224)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit225 (loop $loop225
+          (br_if $exit225 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop225)))
+        (call $add (i32.const -3) (i32.const 1))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit226 (loop $loop226
+          (br_if $exit226 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop226)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (br $loop223)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit227 (loop $loop227
+        (br_if $exit227 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -3) (i32.const 1))
+        (br $loop227)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit228 (loop $loop228
+        (br_if $exit228 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (block $exit229 (loop $loop229
+          (br_if $exit229 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop229)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit230 (loop $loop230
+          (br_if $exit230 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop230)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+        (block $exit231 (loop $loop231
+          (br_if $exit231 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 9) (i32.const 1))
+          (br $loop231)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop228)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -26)))
+      (block $exit232 (loop $loop232
+        (br_if $exit232 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 8) (i32.const 1))
+        (call $add (i32.const 9) (i32.const 1))
+        (br $loop232)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 8)))
+      (block $exit233 (loop $loop233
+        (br_if $exit233 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 22) (i32.const 1))
+        (br $loop233)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+      (block $exit234 (loop $loop234
+        (br_if $exit234 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -9) (i32.const 1))
+        (br $loop234)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 20)))
+      (block $exit235 (loop $loop235
+        (br_if $exit235 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -26) (i32.const 1))
+        (br $loop235)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+      (block $exit236 (loop $loop236
+        (br_if $exit236 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit237 (loop $loop237
+          (br_if $exit237 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -9) (i32.const 1))
+          (br $loop237)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit238 (loop $loop238
+          (br_if $exit238 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop238)))
+        (call $add (i32.const -3) (i32.const 1))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit239 (loop $loop239
+          (br_if $exit239 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop239)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (br $loop236)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit240 (loop $loop240
+        (br_if $exit240 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -3) (i32.const 1))
+        (br $loop240)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit241 (loop $loop241
+        (br_if $exit241 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (block $exit242 (loop $loop242
+          (br_if $exit242 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop242)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit243 (loop $loop243
+          (br_if $exit243 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop243)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+        (block $exit244 (loop $loop244
+          (br_if $exit244 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 9) (i32.const 1))
+          (br $loop244)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop241)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit245 (loop $loop245
+        (br_if $exit245 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -26) (i32.const 1))
+        (br $loop245)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -27)))
+      (block $exit246 (loop $loop246
+        (br_if $exit246 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop246)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit247 (loop $loop247
+        (br_if $exit247 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop247)))
+      (call $add (i32.const -1) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit248 (loop $loop248
+        (br_if $exit248 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop248)))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit249 (loop $loop249
+        (br_if $exit249 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop249)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit250 (loop $loop250
+        (br_if $exit250 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop250)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit251 (loop $loop251
+        (br_if $exit251 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit252 (loop $loop252
+          (br_if $exit252 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (call $add (i32.const 4) (i32.const 1))
+          (br $loop252)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit253 (loop $loop253
+          (br_if $exit253 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -2) (i32.const 1))
+          (br $loop253)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit254 (loop $loop254
+          (br_if $exit254 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -4) (i32.const 1))
+          (br $loop254)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (br $loop251)))
+      (call $add (i32.const 0) (i32.const 254))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit255 (loop $loop255
+        (br_if $exit255 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop255)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit256 (loop $loop256
+        (br_if $exit256 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop256)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit257 (loop $loop257
+        (br_if $exit257 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop257)))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit258 (loop $loop258
+        (br_if $exit258 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop258)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit259 (loop $loop259
+        (br_if $exit259 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop259)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit260 (loop $loop260
+        (br_if $exit260 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+        (block $exit261 (loop $loop261
+          (br_if $exit261 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (call $add (i32.const 4) (i32.const 1))
+          (br $loop261)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit262 (loop $loop262
+          (br_if $exit262 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -2) (i32.const 255))
+          (br $loop262)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit263 (loop $loop263
+          (br_if $exit263 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -4) (i32.const 1))
+          (br $loop263)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (br $loop260)))
+      (call $add (i32.const 0) (i32.const 253))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit264 (loop $loop264
+        (br_if $exit264 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop264)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit265 (loop $loop265
+        (br_if $exit265 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop265)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit266 (loop $loop266
+        (br_if $exit266 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop266)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit267 (loop $loop267
+        (br_if $exit267 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop267)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit268 (loop $loop268
+        (br_if $exit268 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop268)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit269 (loop $loop269
+        (br_if $exit269 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -8)))
+        (block $exit270 (loop $loop270
+          (br_if $exit270 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 6) (i32.const 1))
+          (call $add (i32.const 7) (i32.const 1))
+          (br $loop270)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+        (block $exit271 (loop $loop271
+          (br_if $exit271 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 26) (i32.const 1))
+          (br $loop271)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit272 (loop $loop272
+          (br_if $exit272 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 21) (i32.const 1))
+          (br $loop272)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit273 (loop $loop273
+          (br_if $exit273 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -7) (i32.const 1))
+          (br $loop273)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 20)))
+        (block $exit274 (loop $loop274
+          (br_if $exit274 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (block $exit275 (loop $loop275
+            (br_if $exit275 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -12) (i32.const 1))
+            (br $loop275)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit276 (loop $loop276
+            (br_if $exit276 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop276)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit277 (loop $loop277
+            (br_if $exit277 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop277)))
+          (call $add (i32.const -3) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit278 (loop $loop278
+            (br_if $exit278 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop278)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (br $loop274)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit279 (loop $loop279
+          (br_if $exit279 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop279)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit280 (loop $loop280
+          (br_if $exit280 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (block $exit281 (loop $loop281
+            (br_if $exit281 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop281)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+          (block $exit282 (loop $loop282
+            (br_if $exit282 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 12) (i32.const 1))
+            (br $loop282)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop280)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -21)))
+        (block $exit283 (loop $loop283
+          (br_if $exit283 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (call $add (i32.const 4) (i32.const 1))
+          (br $loop283)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+        (block $exit284 (loop $loop284
+          (br_if $exit284 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -4) (i32.const 1))
+          (br $loop284)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit285 (loop $loop285
+          (br_if $exit285 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop285)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit286 (loop $loop286
+          (br_if $exit286 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 7) (i32.const 1))
+          (call $add (i32.const 30) (i32.const 1))
+          (br $loop286)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+        (block $exit287 (loop $loop287
+          (br_if $exit287 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -7) (i32.const 1))
+          (br $loop287)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 23)))
+        (block $exit288 (loop $loop288
+          (br_if $exit288 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit289 (loop $loop289
+            (br_if $exit289 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -9) (i32.const 1))
+            (br $loop289)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit290 (loop $loop290
+            (br_if $exit290 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop290)))
+          (call $add (i32.const -3) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit291 (loop $loop291
+            (br_if $exit291 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop291)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (br $loop288)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit292 (loop $loop292
+          (br_if $exit292 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop292)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit293 (loop $loop293
+          (br_if $exit293 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (block $exit294 (loop $loop294
+            (br_if $exit294 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop294)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit295 (loop $loop295
+            (br_if $exit295 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop295)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+          (block $exit296 (loop $loop296
+            (br_if $exit296 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 9) (i32.const 1))
+            (br $loop296)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop293)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit297 (loop $loop297
+          (br_if $exit297 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -26) (i32.const 1))
+          (br $loop297)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -22)))
+        (br $loop269)))
+      (call $add (i32.const 0) (i32.const 252))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit298 (loop $loop298
+        (br_if $exit298 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop298)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit299 (loop $loop299
+        (br_if $exit299 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop299)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit300 (loop $loop300
+        (br_if $exit300 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop300)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit301 (loop $loop301
+        (br_if $exit301 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop301)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit302 (loop $loop302
+        (br_if $exit302 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop302)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit303 (loop $loop303
+        (br_if $exit303 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -8)))
+        (block $exit304 (loop $loop304
+          (br_if $exit304 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 6) (i32.const 1))
+          (call $add (i32.const 7) (i32.const 1))
+          (br $loop304)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+        (block $exit305 (loop $loop305
+          (br_if $exit305 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 26) (i32.const 1))
+          (br $loop305)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit306 (loop $loop306
+          (br_if $exit306 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 21) (i32.const 1))
+          (br $loop306)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit307 (loop $loop307
+          (br_if $exit307 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -7) (i32.const 1))
+          (br $loop307)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 20)))
+        (block $exit308 (loop $loop308
+          (br_if $exit308 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (block $exit309 (loop $loop309
+            (br_if $exit309 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -12) (i32.const 1))
+            (br $loop309)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit310 (loop $loop310
+            (br_if $exit310 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop310)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit311 (loop $loop311
+            (br_if $exit311 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop311)))
+          (call $add (i32.const -3) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit312 (loop $loop312
+            (br_if $exit312 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop312)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (br $loop308)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit313 (loop $loop313
+          (br_if $exit313 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop313)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit314 (loop $loop314
+          (br_if $exit314 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (block $exit315 (loop $loop315
+            (br_if $exit315 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop315)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+          (block $exit316 (loop $loop316
+            (br_if $exit316 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 12) (i32.const 1))
+            (br $loop316)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop314)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -21)))
+        (block $exit317 (loop $loop317
+          (br_if $exit317 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (call $add (i32.const 4) (i32.const 1))
+          (br $loop317)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+        (block $exit318 (loop $loop318
+          (br_if $exit318 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -4) (i32.const 1))
+          (br $loop318)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit319 (loop $loop319
+          (br_if $exit319 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 255))
+          (br $loop319)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit320 (loop $loop320
+          (br_if $exit320 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 7) (i32.const 1))
+          (call $add (i32.const 30) (i32.const 1))
+          (br $loop320)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+        (block $exit321 (loop $loop321
+          (br_if $exit321 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -7) (i32.const 1))
+          (br $loop321)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 23)))
+        (block $exit322 (loop $loop322
+          (br_if $exit322 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit323 (loop $loop323
+            (br_if $exit323 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -9) (i32.const 1))
+            (br $loop323)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit324 (loop $loop324
+            (br_if $exit324 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop324)))
+          (call $add (i32.const -3) (i32.const 1))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+          (block $exit325 (loop $loop325
+            (br_if $exit325 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (br $loop325)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+          (br $loop322)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit326 (loop $loop326
+          (br_if $exit326 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop326)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit327 (loop $loop327
+          (br_if $exit327 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (block $exit328 (loop $loop328
+            (br_if $exit328 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop328)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit329 (loop $loop329
+            (br_if $exit329 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -3) (i32.const 1))
+            (br $loop329)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+          (block $exit330 (loop $loop330
+            (br_if $exit330 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 9) (i32.const 1))
+            (br $loop330)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (br $loop327)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit331 (loop $loop331
+          (br_if $exit331 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -26) (i32.const 1))
+          (br $loop331)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -22)))
+        (br $loop303)))
+      (call $add (i32.const 0) (i32.const 251))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit332 (loop $loop332
+        (br_if $exit332 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop332)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit333 (loop $loop333
+        (br_if $exit333 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop333)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit334 (loop $loop334
+        (br_if $exit334 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop334)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit335 (loop $loop335
+        (br_if $exit335 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop335)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit336 (loop $loop336
+        (br_if $exit336 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop336)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit337 (loop $loop337
+        (br_if $exit337 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+        (block $exit338 (loop $loop338
+          (br_if $exit338 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 4) (i32.const 1))
+          (call $add (i32.const 5) (i32.const 1))
+          (br $loop338)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 5)))
+        (block $exit339 (loop $loop339
+          (br_if $exit339 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -5) (i32.const 1))
+          (br $loop339)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit340 (loop $loop340
+          (br_if $exit340 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (call $add (i32.const -1) (i32.const 1))
+          (br $loop340)))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit341 (loop $loop341
+          (br_if $exit341 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 1) (i32.const 1))
+          (call $add (i32.const 2) (i32.const 1))
+          (br $loop341)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+        (block $exit342 (loop $loop342
+          (br_if $exit342 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -2) (i32.const 1))
+          (br $loop342)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+        (block $exit343 (loop $loop343
+          (br_if $exit343 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+          (block $exit344 (loop $loop344
+            (br_if $exit344 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (call $add (i32.const 4) (i32.const 1))
+            (br $loop344)))
+          (call $add (i32.const -2) (i32.const 2))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit345 (loop $loop345
+            (br_if $exit345 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop345)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+          (block $exit346 (loop $loop346
+            (br_if $exit346 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -4) (i32.const 1))
+            (br $loop346)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+          (br $loop343)))
+        (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+        (br $loop337)))
+      (call $add (i32.const 0) (i32.const 250))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit347 (loop $loop347
+        (br_if $exit347 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop347)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit348 (loop $loop348
+        (br_if $exit348 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop348)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit349 (loop $loop349
+        (br_if $exit349 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop349)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit350 (loop $loop350
+        (br_if $exit350 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop350)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit351 (loop $loop351
+        (br_if $exit351 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop351)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit352 (loop $loop352
+        (br_if $exit352 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+        (block $exit353 (loop $loop353
+          (br_if $exit353 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (call $add (i32.const 4) (i32.const 1))
+          (br $loop353)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (block $exit354 (loop $loop354
+          (br_if $exit354 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const -4)))
+          (block $exit355 (loop $loop355
+            (br_if $exit355 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const 3) (i32.const 1))
+            (call $add (i32.const 4) (i32.const 1))
+            (br $loop355)))
+          (call $add (i32.const -2) (i32.const 2))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+          (block $exit356 (loop $loop356
+            (br_if $exit356 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -1) (i32.const 1))
+            (br $loop356)))
+          (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+          (block $exit357 (loop $loop357
+            (br_if $exit357 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+            (call $add (i32.const 0) (i32.const 255))
+            (call $add (i32.const -4) (i32.const 1))
+            (br $loop357)))
+          (br $loop354)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+        (block $exit358 (loop $loop358
+          (br_if $exit358 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -4) (i32.const 1))
+          (br $loop358)))
+        (br $loop352)))
+      (call $add (i32.const 0) (i32.const 249))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit359 (loop $loop359
+        (br_if $exit359 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop359)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit360 (loop $loop360
+        (br_if $exit360 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop360)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit361 (loop $loop361
+        (br_if $exit361 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop361)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit362 (loop $loop362
+        (br_if $exit362 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop362)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit363 (loop $loop363
+        (br_if $exit363 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop363)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit364 (loop $loop364
+        (br_if $exit364 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $input (i32.const -4))
+        (br $loop364)))
+      (call $add (i32.const 0) (i32.const 248))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit365 (loop $loop365
+        (br_if $exit365 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 7) (i32.const 1))
+        (br $loop365)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 7)))
+      (block $exit366 (loop $loop366
+        (br_if $exit366 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop366)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit367 (loop $loop367
+        (br_if $exit367 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop367)))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit368 (loop $loop368
+        (br_if $exit368 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop368)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit369 (loop $loop369
+        (br_if $exit369 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop369)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit370 (loop $loop370
+        (br_if $exit370 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $write (i32.load8_u (call $at (i32.const -4))))
+        (br $loop370)))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit371 (loop $loop371
+        (br_if $exit371 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (call $add (i32.const 26) (i32.const 1))
+        (br $loop371)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+      (block $exit372 (loop $loop372
+        (br_if $exit372 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop372)))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit373 (loop $loop373
+        (br_if $exit373 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop373)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit374 (loop $loop374
+        (br_if $exit374 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop374)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit375 (loop $loop375
+        (br_if $exit375 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -7) (i32.const 1))
+        (br $loop375)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -9)))
+      (block $exit376 (loop $loop376
+        (br_if $exit376 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 9) (i32.const 1))
+        (call $add (i32.const 26) (i32.const 1))
+        (br $loop376)))
+      (i32.store8 (call $at (i32.const 8)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 9)))
+      (block $exit377 (loop $loop377
+        (br_if $exit377 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -9) (i32.const 1))
+        (br $loop377)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 17)))
+      (block $exit378 (loop $loop378
+        (br_if $exit378 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (block $exit379 (loop $loop379
+          (br_if $exit379 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -12) (i32.const 1))
+          (br $loop379)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit380 (loop $loop380
+          (br_if $exit380 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop380)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit381 (loop $loop381
+          (br_if $exit381 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop381)))
+        (call $add (i32.const -3) (i32.const 1))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit382 (loop $loop382
+          (br_if $exit382 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop382)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (br $loop378)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit383 (loop $loop383
+        (br_if $exit383 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (br $loop383)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit384 (loop $loop384
+        (br_if $exit384 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (block $exit385 (loop $loop385
+          (br_if $exit385 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop385)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit386 (loop $loop386
+          (br_if $exit386 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 12) (i32.const 1))
+          (br $loop386)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop384)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -23)))
+      (block $exit387 (loop $loop387
+        (br_if $exit387 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 8) (i32.const 1))
+        (call $add (i32.const 9) (i32.const 1))
+        (br $loop387)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit388 (loop $loop388
+        (br_if $exit388 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 26) (i32.const 1))
+        (br $loop388)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit389 (loop $loop389
+        (br_if $exit389 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 19) (i32.const 1))
+        (br $loop389)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+      (block $exit390 (loop $loop390
+        (br_if $exit390 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -9) (i32.const 1))
+        (br $loop390)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 18)))
+      (block $exit391 (loop $loop391
+        (br_if $exit391 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (block $exit392 (loop $loop392
+          (br_if $exit392 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -12) (i32.const 1))
+          (br $loop392)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit393 (loop $loop393
+          (br_if $exit393 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop393)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit394 (loop $loop394
+          (br_if $exit394 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop394)))
+        (call $add (i32.const -3) (i32.const 1))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit395 (loop $loop395
+          (br_if $exit395 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 3) (i32.const 1))
+          (br $loop395)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+        (br $loop391)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit396 (loop $loop396
+        (br_if $exit396 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 3) (i32.const 1))
+        (br $loop396)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+      (block $exit397 (loop $loop397
+        (br_if $exit397 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (block $exit398 (loop $loop398
+          (br_if $exit398 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -3) (i32.const 1))
+          (br $loop398)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -6)))
+        (block $exit399 (loop $loop399
+          (br_if $exit399 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const 12) (i32.const 1))
+          (br $loop399)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop397)))
+      (call $add (i32.const -15) (i32.const 254))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -22)))
+      (block $exit400 (loop $loop400
+        (br_if $exit400 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 7) (i32.const 1))
+        (call $add (i32.const 8) (i32.const 1))
+        (br $loop400)))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 8)))
+      (block $exit401 (loop $loop401
+        (br_if $exit401 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -8) (i32.const 1))
+        (br $loop401)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit402 (loop $loop402
+        (br_if $exit402 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop402)))
+      (call $add (i32.const 0) (i32.const 255))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit403 (loop $loop403
+        (br_if $exit403 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 2) (i32.const 1))
+        (br $loop403)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 2)))
+      (block $exit404 (loop $loop404
+        (br_if $exit404 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -2) (i32.const 1))
+        (br $loop404)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit405 (loop $loop405
+        (br_if $exit405 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+        (i32.store8 (call $at (i32.const -9)) (i32.const 0))
+        (call $add (i32.const -7) (i32.const 254))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+        (block $exit406 (loop $loop406
+          (br_if $exit406 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+          (call $add (i32.const 0) (i32.const 255))
+          (call $add (i32.const -6) (i32.const 1))
+          (br $loop406)))
+        (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+        (br $loop405)))
+      (i32.store8 (call $at (i32.const -1)) (i32.const 0))
+      (call $add (i32.const -9) (i32.const 1))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -7)))
+      (br $loop219)))
+    (call $add (i32.const 0) (i32.const 1))
+  )
+)
@@ -0,0 +1,60 @@
+;; generated by basm
+(module
+  (import "env" "read" (func $read (result i32)))
+  (import "env" "write" (func $write (param i32)))
+  (memory (export "memory") 1)
+  (global $pointer (mut i32) (i32.const 0))
+
+  ;; returns the address of the cell at `offset` from the tape pointer, traps if it is outside of the tape
+  (func $at (param $offset i32) (result i32)
+    (local $index i32)
+    (local.set $index (i32.add (global.get $pointer) (local.get $offset)))
+    (if (i32.ge_u (local.get $index) (i32.const 30000))
+      (then unreachable))
+    (i32.mul (local.get $index) (i32.const 1)))
+
+  (func $add (param $offset i32) (param $amount i32)
+    (local $address i32)
+    (local.set $address (call $at (local.get $offset)))
+    (i32.store8 (local.get $address) (i32.add (i32.load8_u (local.get $address)) (local.get $amount))))
+
+  (func $input (param $offset i32)
+    (local $byte i32)
+    (local.set $byte (call $read))
+    (if (i32.lt_s (local.get $byte) (i32.const 0))
+      (then (drop (call $at (local.get $offset))))
+      (else (i32.store8 (call $at (local.get $offset)) (i32.and (local.get $byte) (i32.const 255))))))
+
+  (func (export "run")
+    (call $add (i32.const 0) (i32.const 36))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $add (i32.const 0) (i32.const 197))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+    (call $add (i32.const 0) (i32.const 108))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $add (i32.const 0) (i32.const 3))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+    (call $add (i32.const 0) (i32.const 44))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $add (i32.const 0) (i32.const 244))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $add (i32.const 0) (i32.const 87))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $add (i32.const 0) (i32.const 125))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+    (call $add (i32.const 0) (i32.const 114))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $add (i32.const 0) (i32.const 250))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (call $add (i32.const 0) (i32.const 248))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+    (call $add (i32.const 0) (i32.const 33))
+    (call $write (i32.load8_u (call $at (i32.const 0))))
+    (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+  )
+)
@@ -0,0 +1,105 @@
+;; generated by basm
+(module
+  (import "env" "read" (func $read (result i32)))
+  (import "env" "write" (func $write (param i32)))
+  (memory (export "memory") 1)
+  (global $pointer (mut i32) (i32.const 0))
+
+  ;; returns the address of the cell at `offset` from the tape pointer, traps if it is outside of the tape
+  (func $at (param $offset i32) (result i32)
+    (local $index i32)
+    (local.set $index (i32.add (global.get $pointer) (local.get $offset)))
+    (if (i32.ge_u (local.get $index) (i32.const 30000))
+      (then unreachable))
+    (i32.mul (local.get $index) (i32.const 1)))
+
+  (func $add (param $offset i32) (param $amount i32)
+    (local $address i32)
+    (local.set $address (call $at (local.get $offset)))
+    (i32.store8 (local.get $address) (i32.add (i32.load8_u (local.get $address)) (local.get $amount))))
+
+  (func $input (param $offset i32)
+    (local $byte i32)
+    (local.set $byte (call $read))
+    (if (i32.lt_s (local.get $byte) (i32.const 0))
+      (then (drop (call $at (local.get $offset))))
+      (else (i32.store8 (call $at (local.get $offset)) (i32.and (local.get $byte) (i32.const 255))))))
+
+  (func (export "run")
+    (call $add (i32.const 1) (i32.const 1))
+    (call $input (i32.const 3))
+    (call $add (i32.const 3) (i32.const 255))
+    (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+    (block $exit0 (loop $loop0
+      (br_if $exit0 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+      (call $add (i32.const 0) (i32.const 255))
+      (call $add (i32.const 1) (i32.const 1))
+      (call $add (i32.const 3) (i32.const 1))
+      (br $loop0)))
+    (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+    (block $exit1 (loop $loop1
+      (br_if $exit1 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+      (call $add (i32.const 0) (i32.const 255))
+      (call $add (i32.const -3) (i32.const 1))
+      (br $loop1)))
+    (global.set $pointer (i32.add (global.get $pointer) (i32.const -2)))
+    (block $exit2 (loop $loop2
+      (br_if $exit2 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+      (i32.store8 (call $at (i32.const 0)) (i32.const 0))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit3 (loop $loop3
+        (br_if $exit3 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 4) (i32.const 1))
+        (call $add (i32.const 5) (i32.const 1))
+        (br $loop3)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 1)))
+      (block $exit4 (loop $loop4
+        (br_if $exit4 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -1) (i32.const 1))
+        (br $loop4)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 4)))
+      (block $exit5 (loop $loop5
+        (br_if $exit5 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -5) (i32.const 1))
+        (br $loop5)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -3)))
+      (block $exit6 (loop $loop6
+        (br_if $exit6 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 1) (i32.const 1))
+        (call $add (i32.const 3) (i32.const 1))
+        (br $loop6)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 3)))
+      (block $exit7 (loop $loop7
+        (br_if $exit7 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -3) (i32.const 1))
+        (br $loop7)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -1)))
+      (block $exit8 (loop $loop8
+        (br_if $exit8 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -3) (i32.const 1))
+        (br $loop8)))
+      (call $add (i32.const -5) (i32.const 1))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -5)))
+      (block $exit9 (loop $loop9
+        (br_if $exit9 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const 4) (i32.const 255))
+        (call $add (i32.const 6) (i32.const 1))
+        (br $loop9)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const 6)))
+      (block $exit10 (loop $loop10
+        (br_if $exit10 (i32.eqz (i32.load8_u (call $at (i32.const 0)))))
+        (call $add (i32.const 0) (i32.const 255))
+        (call $add (i32.const -6) (i32.const 1))
+        (br $loop10)))
+      (global.set $pointer (i32.add (global.get $pointer) (i32.const -2)))
+      (br $loop2)))
+    (call $write (i32.load8_u (call $at (i32.const -3))))
+  )
+)